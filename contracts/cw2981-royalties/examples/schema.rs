@@ -2,8 +2,6 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
-use cosmwasm_std::Empty;
-
 use cw721::{
     AllNftInfoResponse, ContractInfoResponse, NftInfoResponse, NumTokensResponse,
     OperatorsResponse, OwnerOfResponse, TokensResponse,
@@ -11,7 +9,8 @@ use cw721::{
 use cw721_base::{ExecuteMsg, Extension, MinterResponse, QueryMsg};
 
 use cw2981_royalties::msg::{
-    CheckRoyaltiesResponse, Cw2981QueryMsg, InstantiateMsg, RoyaltiesInfoResponse,
+    CheckRoyaltiesResponse, Cw2981QueryMsg, Cw4907ExecuteMsg, InstantiateMsg,
+    RoyaltiesInfoResponse, UserOfResponse,
 };
 
 fn main() {
@@ -22,7 +21,7 @@ fn main() {
 
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema_with_title(
-        &schema_for!(ExecuteMsg<Extension, Empty>),
+        &schema_for!(ExecuteMsg<Extension, Cw4907ExecuteMsg>),
         &out_dir,
         "ExecuteMsg",
     );
@@ -46,4 +45,5 @@ fn main() {
     export_schema(&schema_for!(Cw2981QueryMsg), &out_dir);
     export_schema(&schema_for!(RoyaltiesInfoResponse), &out_dir);
     export_schema(&schema_for!(CheckRoyaltiesResponse), &out_dir);
+    export_schema(&schema_for!(UserOfResponse), &out_dir);
 }
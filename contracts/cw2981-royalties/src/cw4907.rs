@@ -0,0 +1,126 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage};
+use cw721::Expiration;
+use cw_storage_plus::Map;
+
+use crate::msg::UserOfResponse;
+use crate::{ContractError, Cw2981Contract};
+
+#[cw_serde]
+pub struct UserInfo {
+    pub user: Addr,
+    pub expires: Expiration,
+    // the address which set the user, e.g. the marketplace renting the token
+    #[serde(default)]
+    pub set_by: Option<Addr>,
+}
+
+// the user of each token, the owner of the token is not changed by setting a user
+pub const USERS: Map<&str, UserInfo> = Map::new("users");
+
+// the unexpired user of a token which was not set by the allowed address
+fn locked_user(
+    storage: &dyn Storage,
+    env: &Env,
+    token_id: &str,
+    allowed: &Addr,
+) -> StdResult<Option<UserInfo>> {
+    Ok(USERS.may_load(storage, token_id)?.filter(|user_info| {
+        !user_info.expires.is_expired(&env.block)
+            && user_info
+                .set_by
+                .as_ref()
+                .is_some_and(|set_by| set_by != allowed)
+    }))
+}
+
+// a token cannot change its owner while it has an unexpired user set by another address than
+// its owner, so a rented token cannot be taken back from its renter
+pub fn check_no_locked_user(deps: Deps, env: &Env, token_id: &str) -> Result<(), ContractError> {
+    let token = match Cw2981Contract::default()
+        .tokens
+        .may_load(deps.storage, token_id)?
+    {
+        Some(token) => token,
+        None => return Ok(()),
+    };
+    if locked_user(deps.storage, env, token_id, &token.owner)?.is_some() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Cannot transfer a token before its user expires",
+        )));
+    }
+    Ok(())
+}
+
+pub fn set_user(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    user: Option<String>,
+    expires: Expiration,
+) -> Result<Response, ContractError> {
+    let contract = Cw2981Contract::default();
+    let token = contract.tokens.load(deps.storage, &token_id)?;
+
+    // the owner and the addresses which can transfer the token can set its user
+    contract.check_can_send(deps.as_ref(), &env, &info, &token)?;
+
+    // only the address which set an unexpired user can change it
+    if locked_user(deps.storage, &env, &token_id, &info.sender)?.is_some() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Cannot change the user of a token before it expires",
+        )));
+    }
+
+    let user = match user {
+        Some(user) => {
+            if expires.is_expired(&env.block) {
+                return Err(ContractError::Expired {});
+            }
+            let user = deps.api.addr_validate(&user)?;
+            USERS.save(
+                deps.storage,
+                &token_id,
+                &UserInfo {
+                    user: user.clone(),
+                    expires,
+                    set_by: Some(info.sender.clone()),
+                },
+            )?;
+            user.to_string()
+        }
+        None => {
+            USERS.remove(deps.storage, &token_id);
+            String::new()
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "set_user")
+        .add_attribute("sender", info.sender)
+        .add_attribute("token_id", token_id)
+        .add_attribute("user", user)
+        .add_attribute("expires", expires.to_string()))
+}
+
+pub fn query_user_of(deps: Deps, env: Env, token_id: String) -> StdResult<UserOfResponse> {
+    // the token must exist
+    Cw2981Contract::default()
+        .tokens
+        .load(deps.storage, &token_id)?;
+
+    let user_info = USERS
+        .may_load(deps.storage, &token_id)?
+        .filter(|user_info| !user_info.expires.is_expired(&env.block));
+    Ok(match user_info {
+        Some(user_info) => UserOfResponse {
+            user: Some(user_info.user.to_string()),
+            expires: Some(user_info.expires),
+        },
+        None => UserOfResponse {
+            user: None,
+            expires: None,
+        },
+    })
+}
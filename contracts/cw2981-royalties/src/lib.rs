@@ -1,3 +1,4 @@
+pub mod cw4907;
 pub mod msg;
 pub mod query;
 
@@ -12,7 +13,8 @@ pub use cw721_base::{
 };
 use cw_storage_plus::Item;
 
-use crate::msg::{Cw2981QueryMsg, InstantiateMsg};
+use crate::cw4907::{check_no_locked_user, query_user_of, set_user, USERS};
+use crate::msg::{Cw2981QueryMsg, Cw4907ExecuteMsg, InstantiateMsg};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -63,8 +65,8 @@ pub type Extension = Option<Metadata>;
 
 pub type MintExtension = Option<Extension>;
 
-pub type Cw2981Contract<'a> = Cw721Contract<'a, Extension, Empty, Empty, Cw2981QueryMsg>;
-pub type ExecuteMsg = cw721_base::ExecuteMsg<Extension, Empty>;
+pub type Cw2981Contract<'a> = Cw721Contract<'a, Extension, Empty, Cw4907ExecuteMsg, Cw2981QueryMsg>;
+pub type ExecuteMsg = cw721_base::ExecuteMsg<Extension, Cw4907ExecuteMsg>;
 pub type QueryMsg = cw721_base::QueryMsg<Cw2981QueryMsg>;
 
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
@@ -110,7 +112,7 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
@@ -145,6 +147,24 @@ pub fn execute(
                 cw721_base::ExecuteMsg::Mint(msg_with_royalty),
             )
         }
+        ExecuteMsg::Extension {
+            msg:
+                Cw4907ExecuteMsg::SetUser {
+                    token_id,
+                    user,
+                    expires,
+                },
+        } => set_user(deps, env, info, token_id, user, expires),
+        // the user of a token is removed when its owner changes
+        ExecuteMsg::TransferNft { ref token_id, .. }
+        | ExecuteMsg::SendNft { ref token_id, .. }
+        | ExecuteMsg::Burn { ref token_id } => {
+            let token_id = token_id.clone();
+            check_no_locked_user(deps.as_ref(), &env, &token_id)?;
+            let res = Cw2981Contract::default().execute(deps.branch(), env, info, msg)?;
+            USERS.remove(deps.storage, &token_id);
+            Ok(res)
+        }
         _ => Cw2981Contract::default().execute(deps, env, info, msg),
     }
 }
@@ -158,6 +178,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 sale_price,
             } => to_binary(&query_royalties_info(deps, token_id, sale_price)?),
             Cw2981QueryMsg::CheckRoyalties {} => to_binary(&check_royalties(deps)?),
+            Cw2981QueryMsg::UserOf { token_id } => to_binary(&query_user_of(deps, env, token_id)?),
         },
         _ => Cw2981Contract::default().query(deps, env, msg),
     }
@@ -166,12 +187,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::msg::{CheckRoyaltiesResponse, RoyaltiesInfoResponse};
+    use crate::msg::{CheckRoyaltiesResponse, RoyaltiesInfoResponse, UserOfResponse};
 
     use cosmwasm_std::{from_binary, Uint128};
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cw721::Cw721Query;
+    use cw721::{Cw721Query, Expiration};
 
     const CREATOR: &str = "creator";
 
@@ -488,4 +509,242 @@ mod tests {
             from_binary(&query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
         assert_eq!(query_res, expected);
     }
+
+    #[test]
+    fn set_and_query_user() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            minter: CREATOR.to_string(),
+            royalty_percentage: None,
+            royalty_payment_address: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let token_id = "Enterprise";
+        let mint_msg = MintMsg {
+            token_id: token_id.to_string(),
+            owner: "jeanluc".to_string(),
+            token_uri: None,
+            extension: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Mint(mint_msg)).unwrap();
+
+        let env = mock_env();
+        let expires = Expiration::AtHeight(env.block.height + 10);
+        let set_user_msg = ExecuteMsg::Extension {
+            msg: Cw4907ExecuteMsg::SetUser {
+                token_id: token_id.to_string(),
+                user: Some("wesley".to_string()),
+                expires,
+            },
+        };
+
+        // only the owner or an approved address can set the user
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("wesley", &[]),
+            set_user_msg.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("jeanluc", &[]),
+            set_user_msg,
+        )
+        .unwrap();
+
+        let query_msg = QueryMsg::Extension {
+            msg: Cw2981QueryMsg::UserOf {
+                token_id: token_id.to_string(),
+            },
+        };
+        let res: UserOfResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+        assert_eq!(
+            res,
+            UserOfResponse {
+                user: Some("wesley".to_string()),
+                expires: Some(expires),
+            }
+        );
+
+        // the user is not returned after it expires
+        let mut expired_env = env.clone();
+        expired_env.block.height += 10;
+        let res: UserOfResponse =
+            from_binary(&query(deps.as_ref(), expired_env, query_msg.clone()).unwrap()).unwrap();
+        assert_eq!(res.user, None);
+
+        // the user is removed when the token is transferred
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("jeanluc", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: "janeway".to_string(),
+                token_id: token_id.to_string(),
+            },
+        )
+        .unwrap();
+        let res: UserOfResponse =
+            from_binary(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+        assert_eq!(res.user, None);
+    }
+
+    #[test]
+    fn user_set_by_approved_address_is_locked_until_it_expires() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            minter: CREATOR.to_string(),
+            royalty_percentage: None,
+            royalty_payment_address: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let token_id = "Enterprise";
+        let mint_msg = MintMsg {
+            token_id: token_id.to_string(),
+            owner: "jeanluc".to_string(),
+            token_uri: None,
+            extension: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Mint(mint_msg)).unwrap();
+
+        // the owner approves a marketplace, which rents the token to wesley
+        let env = mock_env();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("jeanluc", &[]),
+            ExecuteMsg::Approve {
+                spender: "marketplace".to_string(),
+                token_id: token_id.to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+        let expires = Expiration::AtHeight(env.block.height + 10);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("marketplace", &[]),
+            ExecuteMsg::Extension {
+                msg: Cw4907ExecuteMsg::SetUser {
+                    token_id: token_id.to_string(),
+                    user: Some("wesley".to_string()),
+                    expires,
+                },
+            },
+        )
+        .unwrap();
+
+        // the owner cannot revoke the user
+        let revoke_msg = ExecuteMsg::Extension {
+            msg: Cw4907ExecuteMsg::SetUser {
+                token_id: token_id.to_string(),
+                user: None,
+                expires,
+            },
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("jeanluc", &[]),
+            revoke_msg.clone(),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Std(StdError::generic_err(
+                "Cannot change the user of a token before it expires"
+            ))
+        );
+
+        // nor transfer the token to remove the user
+        let transfer_msg = ExecuteMsg::TransferNft {
+            recipient: "janeway".to_string(),
+            token_id: token_id.to_string(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("jeanluc", &[]),
+            transfer_msg.clone(),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Std(StdError::generic_err(
+                "Cannot transfer a token before its user expires"
+            ))
+        );
+
+        let query_msg = QueryMsg::Extension {
+            msg: Cw2981QueryMsg::UserOf {
+                token_id: token_id.to_string(),
+            },
+        };
+        let res: UserOfResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.user, Some("wesley".to_string()));
+
+        // the owner can transfer the token after the user expires
+        let mut expired_env = env;
+        expired_env.block.height += 10;
+        execute(
+            deps.as_mut(),
+            expired_env,
+            mock_info("jeanluc", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn cannot_set_expired_user() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            minter: CREATOR.to_string(),
+            royalty_percentage: None,
+            royalty_payment_address: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let token_id = "Enterprise";
+        let mint_msg = MintMsg {
+            token_id: token_id.to_string(),
+            owner: "jeanluc".to_string(),
+            token_uri: None,
+            extension: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Mint(mint_msg)).unwrap();
+
+        let env = mock_env();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("jeanluc", &[]),
+            ExecuteMsg::Extension {
+                msg: Cw4907ExecuteMsg::SetUser {
+                    token_id: token_id.to_string(),
+                    user: Some("wesley".to_string()),
+                    expires: Expiration::AtHeight(env.block.height),
+                },
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Expired {});
+    }
 }
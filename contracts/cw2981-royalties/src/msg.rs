@@ -1,5 +1,6 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{CustomMsg, Uint128};
+use cw721::Expiration;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -36,6 +37,10 @@ pub enum Cw2981QueryMsg {
     /// if royalties are implemented at token level
     /// (i.e. always check on sale)
     CheckRoyalties {},
+    /// Returns the user of the NFT and when the use expires,
+    /// the user is None if it is not set or expired
+    /// See https://eips.ethereum.org/EIPS/eip-4907
+    UserOf { token_id: String },
 }
 
 impl Default for Cw2981QueryMsg {
@@ -46,6 +51,24 @@ impl Default for Cw2981QueryMsg {
 
 impl CustomMsg for Cw2981QueryMsg {}
 
+#[cw_serde]
+pub enum Cw4907ExecuteMsg {
+    /// Lets the user use the NFT until expires, while the ownership stays with the owner.
+    /// Only the owner or an address allowed to transfer the NFT can set the user,
+    /// a user of None removes the current user.
+    /// The user is removed when the NFT is transferred or burned.
+    /// An unexpired user can only be changed by the address which set it, and the NFT cannot be
+    /// transferred or burned before it expires unless its owner set it, e.g. during a rental
+    /// See https://eips.ethereum.org/EIPS/eip-4907
+    SetUser {
+        token_id: String,
+        user: Option<String>,
+        expires: Expiration,
+    },
+}
+
+impl CustomMsg for Cw4907ExecuteMsg {}
+
 #[cw_serde]
 pub struct RoyaltiesInfoResponse {
     pub address: String,
@@ -60,3 +83,10 @@ pub struct RoyaltiesInfoResponse {
 pub struct CheckRoyaltiesResponse {
     pub royalty_payments: bool,
 }
+
+/// The current user of the NFT, see https://eips.ethereum.org/EIPS/eip-4907
+#[cw_serde]
+pub struct UserOfResponse {
+    pub user: Option<String>,
+    pub expires: Option<Expiration>,
+}
@@ -0,0 +1,364 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Reply, ReplyOn,
+    Response, StdError, StdResult, SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cw_utils::parse_reply_instantiate_data;
+use nft_launchpad::msg::{ColectionInfo, InstantiateMsg as LaunchpadInstantiateMsg};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, LaunchpadsResponse, MigrateMsg, QueryMsg};
+use crate::state::{
+    launchpads, Config, LaunchpadRecord, LaunchpadStatus, CONFIG, LAST_LAUNCHPAD_ID,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:launchpad-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Handling contract instantiation
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // we will not take all the profit of creator ^^
+    if msg.launchpad_fee >= 100 {
+        return Err(ContractError::InvalidLaunchpadFee {});
+    }
+
+    // save contract config
+    let config = Config {
+        admin: info.sender.clone(),
+        launchpad_code_id: msg.launchpad_code_id,
+        colection_code_id: msg.colection_code_id,
+        launchpad_fee: msg.launchpad_fee,
+        launchpad_collector: deps.api.addr_validate(
+            &msg.launchpad_collector
+                .unwrap_or_else(|| info.sender.to_string()),
+        )?,
+        require_approval: msg.require_approval,
+        nois_proxy: msg
+            .nois_proxy
+            .map(|nois_proxy| deps.api.addr_validate(&nois_proxy))
+            .transpose()?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    LAST_LAUNCHPAD_ID.save(deps.storage, &0)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "instantiate_launchpad_factory"),
+        ("admin", config.admin.as_str()),
+        ("launchpad_code_id", &config.launchpad_code_id.to_string()),
+        ("colection_code_id", &config.colection_code_id.to_string()),
+    ]))
+}
+
+/// Handling the reply of the launchpad instantiation, the reply id is the launchpad id
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    let id = msg.id;
+    let reply =
+        parse_reply_instantiate_data(msg).map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    // store the address of the launchpad in the registry
+    let mut launchpad = launchpads().load(deps.storage, id)?;
+    launchpad.launchpad_address = Some(deps.api.addr_validate(&reply.contract_address)?);
+    launchpad.status = LaunchpadStatus::Instantiated;
+    launchpads().save(deps.storage, id, &launchpad)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "instantiate_launchpad"),
+        ("launchpad_id", &id.to_string()),
+        ("launchpad_address", &reply.contract_address),
+    ]))
+}
+
+/// Handling contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {}
+}
+
+/// Handling contract execution
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            admin,
+            launchpad_code_id,
+            colection_code_id,
+            launchpad_fee,
+            launchpad_collector,
+            require_approval,
+            nois_proxy,
+        } => execute_update_config(
+            deps,
+            info,
+            admin,
+            launchpad_code_id,
+            colection_code_id,
+            launchpad_fee,
+            launchpad_collector,
+            require_approval,
+            nois_proxy,
+        ),
+        ExecuteMsg::CreateLaunchpad {
+            collection_info,
+            random_seed,
+        } => execute_create_launchpad(deps, info, collection_info, random_seed),
+        ExecuteMsg::ApproveLaunchpad { id } => execute_approve_launchpad(deps, info, id),
+        ExecuteMsg::RejectLaunchpad { id } => execute_reject_launchpad(deps, info, id),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: Option<String>,
+    launchpad_code_id: Option<u64>,
+    colection_code_id: Option<u64>,
+    launchpad_fee: Option<u32>,
+    launchpad_collector: Option<String>,
+    require_approval: Option<bool>,
+    nois_proxy: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // only the admin can update the config
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(admin) = admin {
+        config.admin = deps.api.addr_validate(&admin)?;
+    }
+    if let Some(launchpad_code_id) = launchpad_code_id {
+        config.launchpad_code_id = launchpad_code_id;
+    }
+    if let Some(colection_code_id) = colection_code_id {
+        config.colection_code_id = colection_code_id;
+    }
+    if let Some(launchpad_fee) = launchpad_fee {
+        if launchpad_fee >= 100 {
+            return Err(ContractError::InvalidLaunchpadFee {});
+        }
+        config.launchpad_fee = launchpad_fee;
+    }
+    if let Some(launchpad_collector) = launchpad_collector {
+        config.launchpad_collector = deps.api.addr_validate(&launchpad_collector)?;
+    }
+    if let Some(require_approval) = require_approval {
+        config.require_approval = require_approval;
+    }
+    if let Some(nois_proxy) = nois_proxy {
+        config.nois_proxy = Some(deps.api.addr_validate(&nois_proxy)?);
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![("action", "update_config")]))
+}
+
+pub fn execute_create_launchpad(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection_info: ColectionInfo,
+    random_seed: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let id = LAST_LAUNCHPAD_ID.load(deps.storage)? + 1;
+    LAST_LAUNCHPAD_ID.save(deps.storage, &id)?;
+
+    // the sender is always the creator of the launchpad
+    let launchpad = LaunchpadRecord {
+        id,
+        creator: info.sender.clone(),
+        status: LaunchpadStatus::Pending,
+        launchpad_address: None,
+        collection_info: ColectionInfo {
+            creator: info.sender.to_string(),
+            ..collection_info
+        },
+        random_seed,
+    };
+    launchpads().save(deps.storage, id, &launchpad)?;
+
+    let res = Response::new().add_attributes(vec![
+        ("action", "create_launchpad"),
+        ("launchpad_id", &id.to_string()),
+        ("creator", info.sender.as_str()),
+    ]);
+
+    // the launchpad waits for the approval of the admin
+    if config.require_approval {
+        return Ok(res.add_attribute("status", LaunchpadStatus::Pending.as_str()));
+    }
+
+    Ok(res.add_submessage(instantiate_launchpad_msg(&config, &launchpad)?))
+}
+
+pub fn execute_approve_launchpad(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // only the admin can approve a launchpad
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let launchpad = launchpads().load(deps.storage, id)?;
+    if launchpad.status != LaunchpadStatus::Pending {
+        return Err(ContractError::LaunchpadNotPending { id });
+    }
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "approve_launchpad"),
+            ("launchpad_id", &id.to_string()),
+        ])
+        .add_submessage(instantiate_launchpad_msg(&config, &launchpad)?))
+}
+
+pub fn execute_reject_launchpad(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // only the admin can reject a launchpad
+    if CONFIG.load(deps.storage)?.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut launchpad = launchpads().load(deps.storage, id)?;
+    if launchpad.status != LaunchpadStatus::Pending {
+        return Err(ContractError::LaunchpadNotPending { id });
+    }
+    launchpad.status = LaunchpadStatus::Rejected;
+    launchpads().save(deps.storage, id, &launchpad)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "reject_launchpad"),
+        ("launchpad_id", &id.to_string()),
+    ]))
+}
+
+// the launchpad is instantiated with the approved code ids, nois proxy, the default fee and
+// collector, the creator manages the launchpad and the factory admin can migrate it
+fn instantiate_launchpad_msg(config: &Config, launchpad: &LaunchpadRecord) -> StdResult<SubMsg> {
+    Ok(SubMsg {
+        id: launchpad.id,
+        gas_limit: None,
+        msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
+            code_id: config.launchpad_code_id,
+            funds: vec![],
+            admin: Some(config.admin.to_string()),
+            label: format!("nft-launchpad-{}", launchpad.id),
+            msg: to_binary(&LaunchpadInstantiateMsg {
+                random_seed: launchpad.random_seed.clone(),
+                colection_code_id: config.colection_code_id,
+                launchpad_fee: config.launchpad_fee,
+                launchpad_collector: Some(config.launchpad_collector.to_string()),
+                admin: Some(launchpad.creator.to_string()),
+                collection_info: launchpad.collection_info.clone(),
+                nois_proxy: config.nois_proxy.as_ref().map(Addr::to_string),
+            })?,
+        }),
+        reply_on: ReplyOn::Success,
+    })
+}
+
+/// Handling contract query
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Launchpad { id } => to_binary(&launchpads().load(deps.storage, id)?),
+        QueryMsg::LaunchpadsByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_binary(&query_launchpads_by_creator(
+            deps,
+            deps.api.addr_validate(&creator)?,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::LaunchpadsByStatus {
+            status,
+            start_after,
+            limit,
+        } => to_binary(&query_launchpads_by_status(
+            deps,
+            status,
+            start_after,
+            limit,
+        )?),
+    }
+}
+
+pub fn query_launchpads_by_creator(
+    deps: Deps,
+    creator: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LaunchpadsResponse> {
+    let limit = limit.unwrap_or(30).min(30) as usize;
+
+    let launchpads = launchpads()
+        .idx
+        .creator
+        .prefix(creator)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, launchpad)| launchpad))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(LaunchpadsResponse { launchpads })
+}
+
+pub fn query_launchpads_by_status(
+    deps: Deps,
+    status: LaunchpadStatus,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LaunchpadsResponse> {
+    let limit = limit.unwrap_or(30).min(30) as usize;
+
+    let launchpads = launchpads()
+        .idx
+        .status
+        .prefix(status.as_str().to_string())
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, launchpad)| launchpad))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(LaunchpadsResponse { launchpads })
+}
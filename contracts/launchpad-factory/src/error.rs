@@ -0,0 +1,17 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid launchpad fee")]
+    InvalidLaunchpadFee {},
+
+    #[error("Launchpad {id} is not pending")]
+    LaunchpadNotPending { id: u64 },
+}
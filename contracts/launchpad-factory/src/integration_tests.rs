@@ -0,0 +1,484 @@
+#[cfg(test)]
+mod tests {
+    use crate::msg::{ExecuteMsg, InstantiateMsg, LaunchpadsResponse, QueryMsg};
+    use crate::state::{Config, LaunchpadRecord, LaunchpadStatus};
+    use crate::testing_config::env::{
+        instantiate_contracts, ContractInfo, ADMIN, CREATOR, LAUNCHPAD_COLLECTOR, NOIS_PROXY,
+        USER_1,
+    };
+    use crate::ContractError;
+
+    use cosmwasm_std::{coin, Addr};
+    use cw2981_royalties::QueryMsg as Cw721QueryMsg;
+    use cw721::ContractInfoResponse;
+    use cw_multi_test::{App, AppResponse, Executor};
+    use nft_launchpad::msg::{
+        ColectionInfo, ExecuteMsg as LaunchpadExecuteMsg, QueryMsg as LaunchpadQueryMsg,
+    };
+    use nft_launchpad::state::{
+        LaunchpadInfo, PhaseData, PriceType, TokenIdMode, CONFIG as LAUNCHPAD_CONFIG,
+    };
+
+    pub const COLLECTION_NAME: &str = "A launchpad collection";
+    pub const COLLECTION_SYMBOL: &str = "LPC";
+
+    // instantiate the launchpad factory and return its address
+    fn create_factory(app: &mut App, contracts: &[ContractInfo], require_approval: bool) -> Addr {
+        app.instantiate_contract(
+            contracts[2].contract_code_id,
+            Addr::unchecked(ADMIN),
+            &InstantiateMsg {
+                launchpad_code_id: contracts[1].contract_code_id,
+                colection_code_id: contracts[0].contract_code_id,
+                launchpad_fee: 10,
+                launchpad_collector: Some(LAUNCHPAD_COLLECTOR.to_string()),
+                require_approval,
+                nois_proxy: None,
+            },
+            &[],
+            "test instantiate launchpad factory contract",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn collection_info(creator: &str) -> ColectionInfo {
+        ColectionInfo {
+            creator: creator.to_string(),
+            name: COLLECTION_NAME.to_string(),
+            symbol: COLLECTION_SYMBOL.to_string(),
+            max_supply: 5000,
+            uri_prefix:
+                "ipfs://bafybeifm3xas2egfbwzo7cg5wiayw44sbvfn6h5am2bydp2zpnypl7g5tq/images/"
+                    .to_string(),
+            uri_suffix: ".json".to_string(),
+            royalty_percentage: None,
+            royalty_payment_address: None,
+            placeholder_uri: None,
+            provenance_hash: None,
+            token_id_mode: TokenIdMode::Random,
+            reserved_supply: 0,
+        }
+    }
+
+    fn create_launchpad(
+        app: &mut App,
+        sender: &str,
+        factory: &Addr,
+    ) -> anyhow::Result<AppResponse> {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            factory.clone(),
+            &ExecuteMsg::CreateLaunchpad {
+                collection_info: collection_info(sender),
+                random_seed: "9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d1237fa51ebeb1d8fe62"
+                    .to_string(),
+            },
+            &[],
+        )
+    }
+
+    fn query_launchpad(app: &App, factory: &Addr, id: u64) -> LaunchpadRecord {
+        app.wrap()
+            .query_wasm_smart(factory, &QueryMsg::Launchpad { id })
+            .unwrap()
+    }
+
+    fn query_launchpads_by_status(
+        app: &App,
+        factory: &Addr,
+        status: LaunchpadStatus,
+    ) -> Vec<LaunchpadRecord> {
+        let res: LaunchpadsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                factory,
+                &QueryMsg::LaunchpadsByStatus {
+                    status,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.launchpads
+    }
+
+    mod create_launchpad {
+        use super::*;
+
+        #[test]
+        fn creator_can_create_launchpad_without_approval() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory = create_factory(&mut app, &contracts, false);
+
+            let config: Config = app
+                .wrap()
+                .query_wasm_smart(&factory, &QueryMsg::Config {})
+                .unwrap();
+            assert_eq!(config.admin, Addr::unchecked(ADMIN));
+            assert_eq!(config.launchpad_fee, 10);
+
+            create_launchpad(&mut app, CREATOR, &factory).unwrap();
+
+            // the launchpad is instantiated and registered
+            let launchpad = query_launchpad(&app, &factory, 1);
+            assert_eq!(launchpad.creator, Addr::unchecked(CREATOR));
+            assert_eq!(launchpad.status, LaunchpadStatus::Instantiated);
+            let launchpad_address = launchpad.launchpad_address.unwrap();
+
+            // the launchpad has the creator and the fee of the factory, the factory admin can migrate it
+            let launchpad_info: LaunchpadInfo = app
+                .wrap()
+                .query_wasm_smart(&launchpad_address, &LaunchpadQueryMsg::GetLaunchpadInfo {})
+                .unwrap();
+            assert_eq!(launchpad_info.creator, Addr::unchecked(CREATOR));
+            assert_eq!(launchpad_info.launchpad_fee, 10);
+            assert_eq!(
+                app.wrap()
+                    .query_wasm_contract_info(&launchpad_address)
+                    .unwrap()
+                    .admin,
+                Some(ADMIN.to_string())
+            );
+
+            // the collection is instantiated by the launchpad
+            let res: ContractInfoResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    launchpad_info.collection_address,
+                    &Cw721QueryMsg::ContractInfo {},
+                )
+                .unwrap();
+            assert_eq!(res.name, COLLECTION_NAME.to_string());
+            assert_eq!(res.symbol, COLLECTION_SYMBOL.to_string());
+        }
+
+        #[test]
+        fn creator_is_the_admin_of_its_launchpad() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory = create_factory(&mut app, &contracts, false);
+
+            create_launchpad(&mut app, CREATOR, &factory).unwrap();
+            let launchpad_address = query_launchpad(&app, &factory, 1)
+                .launchpad_address
+                .unwrap();
+
+            // the creator is the admin of the launchpad and the wasm admin of the collection
+            let launchpad_config = LAUNCHPAD_CONFIG
+                .query(&app.wrap(), launchpad_address.clone())
+                .unwrap();
+            assert_eq!(launchpad_config.admin, Addr::unchecked(CREATOR));
+            let launchpad_info: LaunchpadInfo = app
+                .wrap()
+                .query_wasm_smart(&launchpad_address, &LaunchpadQueryMsg::GetLaunchpadInfo {})
+                .unwrap();
+            assert_eq!(
+                app.wrap()
+                    .query_wasm_contract_info(launchpad_info.collection_address)
+                    .unwrap()
+                    .admin,
+                Some(CREATOR.to_string())
+            );
+
+            let add_phase_msg = LaunchpadExecuteMsg::AddMintPhase {
+                after_phase_id: None,
+                phase_data: PhaseData {
+                    start_time: app.block_info().time.plus_seconds(200),
+                    end_time: app.block_info().time.plus_seconds(1000),
+                    max_supply: Some(100),
+                    max_nfts_per_address: 5,
+                    price: coin(100, "uaura"),
+                    is_public: true,
+                    price_type: PriceType::Native,
+                },
+            };
+
+            // the factory admin cannot configure the launchpad
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &add_phase_msg,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+
+            // the creator can add a phase
+            app.execute_contract(
+                Addr::unchecked(CREATOR),
+                launchpad_address,
+                &add_phase_msg,
+                &[],
+            )
+            .unwrap();
+        }
+
+        #[test]
+        fn sender_is_always_the_creator() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory = create_factory(&mut app, &contracts, false);
+
+            // USER_1 cannot create a launchpad for CREATOR
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                factory.clone(),
+                &ExecuteMsg::CreateLaunchpad {
+                    collection_info: collection_info(CREATOR),
+                    random_seed: "9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d1237fa51ebeb1d8fe62"
+                        .to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            let launchpad = query_launchpad(&app, &factory, 1);
+            assert_eq!(launchpad.creator, Addr::unchecked(USER_1));
+            let launchpad_info: LaunchpadInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    launchpad.launchpad_address.unwrap(),
+                    &LaunchpadQueryMsg::GetLaunchpadInfo {},
+                )
+                .unwrap();
+            assert_eq!(launchpad_info.creator, Addr::unchecked(USER_1));
+        }
+
+        #[test]
+        fn cannot_instantiate_factory_with_invalid_fee() {
+            let (mut app, contracts) = instantiate_contracts();
+
+            let res = app.instantiate_contract(
+                contracts[2].contract_code_id,
+                Addr::unchecked(ADMIN),
+                &InstantiateMsg {
+                    launchpad_code_id: contracts[1].contract_code_id,
+                    colection_code_id: contracts[0].contract_code_id,
+                    launchpad_fee: 100,
+                    launchpad_collector: None,
+                    require_approval: false,
+                    nois_proxy: None,
+                },
+                &[],
+                "test instantiate launchpad factory contract",
+                None,
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                ContractError::InvalidLaunchpadFee {}.to_string()
+            );
+        }
+    }
+
+    mod approve_launchpad {
+        use super::*;
+
+        #[test]
+        fn admin_can_approve_pending_launchpad() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory = create_factory(&mut app, &contracts, true);
+
+            create_launchpad(&mut app, CREATOR, &factory).unwrap();
+
+            // the launchpad is pending until approved
+            let launchpad = query_launchpad(&app, &factory, 1);
+            assert_eq!(launchpad.status, LaunchpadStatus::Pending);
+            assert_eq!(launchpad.launchpad_address, None);
+
+            // only the admin can approve the launchpad
+            let res = app.execute_contract(
+                Addr::unchecked(CREATOR),
+                factory.clone(),
+                &ExecuteMsg::ApproveLaunchpad { id: 1 },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                ContractError::Unauthorized {}.to_string()
+            );
+
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory.clone(),
+                &ExecuteMsg::ApproveLaunchpad { id: 1 },
+                &[],
+            )
+            .unwrap();
+
+            let launchpad = query_launchpad(&app, &factory, 1);
+            assert_eq!(launchpad.status, LaunchpadStatus::Instantiated);
+            assert!(launchpad.launchpad_address.is_some());
+
+            // the launchpad cannot be approved twice
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory.clone(),
+                &ExecuteMsg::ApproveLaunchpad { id: 1 },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                ContractError::LaunchpadNotPending { id: 1 }.to_string()
+            );
+        }
+
+        #[test]
+        fn admin_can_reject_pending_launchpad() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory = create_factory(&mut app, &contracts, true);
+
+            create_launchpad(&mut app, CREATOR, &factory).unwrap();
+
+            // only the admin can reject the launchpad
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                factory.clone(),
+                &ExecuteMsg::RejectLaunchpad { id: 1 },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                ContractError::Unauthorized {}.to_string()
+            );
+
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory.clone(),
+                &ExecuteMsg::RejectLaunchpad { id: 1 },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                query_launchpad(&app, &factory, 1).status,
+                LaunchpadStatus::Rejected
+            );
+
+            // a rejected launchpad cannot be approved
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory.clone(),
+                &ExecuteMsg::ApproveLaunchpad { id: 1 },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                ContractError::LaunchpadNotPending { id: 1 }.to_string()
+            );
+        }
+
+        #[test]
+        fn admin_can_update_config() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory = create_factory(&mut app, &contracts, true);
+
+            let update_msg = ExecuteMsg::UpdateConfig {
+                admin: None,
+                launchpad_code_id: None,
+                colection_code_id: None,
+                launchpad_fee: Some(5),
+                launchpad_collector: None,
+                require_approval: Some(false),
+                nois_proxy: Some(NOIS_PROXY.to_string()),
+            };
+            let res =
+                app.execute_contract(Addr::unchecked(USER_1), factory.clone(), &update_msg, &[]);
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                ContractError::Unauthorized {}.to_string()
+            );
+            app.execute_contract(Addr::unchecked(ADMIN), factory.clone(), &update_msg, &[])
+                .unwrap();
+
+            // the launchpad is instantiated directly with the new fee and nois proxy
+            create_launchpad(&mut app, CREATOR, &factory).unwrap();
+            let launchpad_address = query_launchpad(&app, &factory, 1)
+                .launchpad_address
+                .unwrap();
+            let launchpad_info: LaunchpadInfo = app
+                .wrap()
+                .query_wasm_smart(&launchpad_address, &LaunchpadQueryMsg::GetLaunchpadInfo {})
+                .unwrap();
+            assert_eq!(launchpad_info.launchpad_fee, 5);
+            assert_eq!(
+                LAUNCHPAD_CONFIG
+                    .query(&app.wrap(), launchpad_address)
+                    .unwrap()
+                    .nois_proxy,
+                Some(Addr::unchecked(NOIS_PROXY))
+            );
+        }
+    }
+
+    mod registry {
+        use super::*;
+
+        #[test]
+        fn launchpads_can_be_queried_by_creator_and_status() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory = create_factory(&mut app, &contracts, true);
+
+            create_launchpad(&mut app, CREATOR, &factory).unwrap();
+            create_launchpad(&mut app, USER_1, &factory).unwrap();
+            create_launchpad(&mut app, CREATOR, &factory).unwrap();
+
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory.clone(),
+                &ExecuteMsg::ApproveLaunchpad { id: 1 },
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory.clone(),
+                &ExecuteMsg::RejectLaunchpad { id: 2 },
+                &[],
+            )
+            .unwrap();
+
+            let res: LaunchpadsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &factory,
+                    &QueryMsg::LaunchpadsByCreator {
+                        creator: CREATOR.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res.launchpads.iter().map(|l| l.id).collect::<Vec<_>>(),
+                vec![1, 3]
+            );
+
+            // paginate the launchpads of the creator
+            let res: LaunchpadsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &factory,
+                    &QueryMsg::LaunchpadsByCreator {
+                        creator: CREATOR.to_string(),
+                        start_after: Some(1),
+                        limit: Some(1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res.launchpads.iter().map(|l| l.id).collect::<Vec<_>>(),
+                vec![3]
+            );
+
+            let ids = |status| {
+                query_launchpads_by_status(&app, &factory, status)
+                    .iter()
+                    .map(|l| l.id)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(ids(LaunchpadStatus::Instantiated), vec![1]);
+            assert_eq!(ids(LaunchpadStatus::Rejected), vec![2]);
+            assert_eq!(ids(LaunchpadStatus::Pending), vec![3]);
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod contract;
+mod error;
+pub mod integration_tests;
+pub mod msg;
+pub mod state;
+pub mod testing_config;
+
+pub use crate::error::ContractError;
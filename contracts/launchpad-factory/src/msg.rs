@@ -0,0 +1,71 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use nft_launchpad::msg::ColectionInfo;
+
+use crate::state::{Config, LaunchpadRecord, LaunchpadStatus};
+
+/// Message type for `instantiate` entry_point
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub launchpad_code_id: u64,
+    pub colection_code_id: u64,
+    pub launchpad_fee: u32,
+    pub launchpad_collector: Option<String>,
+    pub require_approval: bool,
+    pub nois_proxy: Option<String>,
+}
+
+/// Message type for `execute` entry_point
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateConfig {
+        admin: Option<String>,
+        launchpad_code_id: Option<u64>,
+        colection_code_id: Option<u64>,
+        launchpad_fee: Option<u32>,
+        launchpad_collector: Option<String>,
+        require_approval: Option<bool>,
+        nois_proxy: Option<String>,
+    },
+    // the sender is the creator of the launchpad, it is pending until approved if require_approval is set
+    CreateLaunchpad {
+        collection_info: ColectionInfo,
+        random_seed: String,
+    },
+    ApproveLaunchpad {
+        id: u64,
+    },
+    RejectLaunchpad {
+        id: u64,
+    },
+}
+
+/// Message type for `migrate` entry_point
+#[cw_serde]
+pub enum MigrateMsg {}
+
+/// Message type for `query` entry_point
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+    #[returns(LaunchpadRecord)]
+    Launchpad { id: u64 },
+    #[returns(LaunchpadsResponse)]
+    LaunchpadsByCreator {
+        creator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(LaunchpadsResponse)]
+    LaunchpadsByStatus {
+        status: LaunchpadStatus,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct LaunchpadsResponse {
+    pub launchpads: Vec<LaunchpadRecord>,
+}
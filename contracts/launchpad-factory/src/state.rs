@@ -0,0 +1,76 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use nft_launchpad::msg::ColectionInfo;
+
+#[cw_serde]
+pub struct Config {
+    pub admin: Addr, // the factory admin, also the wasm admin of the created launchpads
+    pub launchpad_code_id: u64, // the approved code id of the nft-launchpad contract
+    pub colection_code_id: u64, // the approved code id of the cw2981 collection contract
+    pub launchpad_fee: u32, // the default launchpad fee of the created launchpads
+    pub launchpad_collector: Addr, // the default address receiving the launchpad fee
+    pub require_approval: bool, // if set, the admin must approve a launchpad before it is created
+    pub nois_proxy: Option<Addr>, // the approved nois proxy of the created launchpads
+}
+
+#[cw_serde]
+pub enum LaunchpadStatus {
+    Pending,
+    Rejected,
+    Instantiated,
+}
+
+impl LaunchpadStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LaunchpadStatus::Pending => "pending",
+            LaunchpadStatus::Rejected => "rejected",
+            LaunchpadStatus::Instantiated => "instantiated",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct LaunchpadRecord {
+    pub id: u64,
+    pub creator: Addr,
+    pub status: LaunchpadStatus,
+    pub launchpad_address: Option<Addr>, // set when the launchpad is instantiated
+    pub collection_info: ColectionInfo,
+    pub random_seed: String,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const LAST_LAUNCHPAD_ID: Item<u64> = Item::new("last_launchpad_id");
+
+// launchpads can be indexed by creator and by status
+pub struct LaunchpadIndexes<'a> {
+    pub creator: MultiIndex<'a, Addr, LaunchpadRecord, u64>,
+    pub status: MultiIndex<'a, String, LaunchpadRecord, u64>,
+}
+
+impl<'a> IndexList<LaunchpadRecord> for LaunchpadIndexes<'a> {
+    // this method returns a list of all indexes
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<LaunchpadRecord>> + '_> {
+        let v: Vec<&dyn Index<LaunchpadRecord>> = vec![&self.creator, &self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+// helper function create a IndexedMap for launchpads
+pub fn launchpads<'a>() -> IndexedMap<'a, u64, LaunchpadRecord, LaunchpadIndexes<'a>> {
+    let indexes = LaunchpadIndexes {
+        creator: MultiIndex::new(
+            |_pk: &[u8], l: &LaunchpadRecord| l.creator.clone(),
+            "launchpads",
+            "launchpads__creator",
+        ),
+        status: MultiIndex::new(
+            |_pk: &[u8], l: &LaunchpadRecord| l.status.as_str().to_string(),
+            "launchpads",
+            "launchpads__status",
+        ),
+    };
+    IndexedMap::new("launchpads", indexes)
+}
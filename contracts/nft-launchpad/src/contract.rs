@@ -3,29 +3,45 @@ use std::vec;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coin, has_coins, to_binary, Addr, BalanceResponse, BankMsg, BankQuery, Binary, Coin, CosmosMsg,
-    Deps, DepsMut, Env, MessageInfo, QueryRequest, Reply, ReplyOn, Response, StdResult, Storage,
-    SubMsg, Timestamp, Uint128, WasmMsg,
+    coin, from_binary, has_coins, to_binary, Addr, BalanceResponse, BankMsg, BankQuery, Binary,
+    Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, QueryRequest, Reply, ReplyOn,
+    Response, StdError, StdResult, Storage, SubMsg, Timestamp, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw20::{
+    BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg,
+    TokenInfoResponse,
+};
 use cw2981_royalties::MintMsg;
 use cw2981_royalties::{
-    msg::InstantiateMsg as Cw2981InstantiateMsg, ExecuteMsg as Cw2981ExecuteMsg,
+    msg::InstantiateMsg as Cw2981InstantiateMsg, ExecuteMsg as Cw2981ExecuteMsg, Metadata,
+    QueryMsg as Cw2981QueryMsg,
 };
+use cw721::NftInfoResponse;
 use cw_utils::parse_reply_instantiate_data;
-use nois::{int_in_range, randomness_from_str, sub_randomness_with_key};
+use nois::{
+    int_in_range, randomness_from_str, sub_randomness_with_key, NoisCallback, ProxyExecuteMsg,
+};
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, MintableResponse, QueryMsg};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MerkleProof, MigrateMsg, MintVoucher, MintableResponse, QueryMsg,
+    ReceiveMsg, TokenUriResponse,
+};
 use crate::state::{
-    Config, LaunchpadInfo, PhaseConfig, PhaseConfigResponse, PhaseData, CONFIG, LAUNCHPAD_INFO,
-    PHASE_CONFIGS, RANDOM_SEED, REMAINING_TOKEN_IDS, WHITELIST,
+    Config, LaunchpadInfo, Payee, PendingMint, PhaseConfig, PhaseConfigResponse, PhaseData,
+    PriceType, TokenIdMode, CONFIG, LAST_JOB_ID, LAUNCHPAD_INFO, PAYEES, PENDING_MINTS,
+    PHASE_CONFIGS, RANDOM_SEED, REMAINING_TOKEN_IDS, VOUCHERS, WHITELIST,
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:nft-launchpad";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// the time after which a pending mint not settled by the nois proxy can be cancelled
+const PENDING_MINT_TIMEOUT_SECONDS: u64 = 3600;
+
 /// Handling contract instantiation
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -38,7 +54,10 @@ pub fn instantiate(
 
     // save contract config
     let config = Config {
-        admin: info.sender.clone(),
+        admin: match &msg.admin {
+            Some(admin) => deps.api.addr_validate(admin)?,
+            None => info.sender.clone(),
+        },
         launchpad_collector: deps
             .api
             .addr_validate(
@@ -46,9 +65,25 @@ pub fn instantiate(
                     .unwrap_or_else(|| info.sender.to_string()),
             )
             .unwrap(),
+        nois_proxy: msg
+            .nois_proxy
+            .map(|nois_proxy| deps.api.addr_validate(&nois_proxy))
+            .transpose()?,
+        nois_fee: vec![],
     };
     CONFIG.save(deps.storage, &config)?;
 
+    // the provenance hash of the final metadata must be committed before the sale
+    match &msg.collection_info.provenance_hash {
+        Some(provenance_hash) => {
+            decode_hash(provenance_hash).map_err(|_| ContractError::InvalidProvenanceHash {})?;
+        }
+        None if msg.collection_info.placeholder_uri.is_some() => {
+            return Err(ContractError::InvalidProvenanceHash {});
+        }
+        None => {}
+    }
+
     // store the address of the cw2981 collection contract
     LAUNCHPAD_INFO.save(
         deps.storage,
@@ -66,6 +101,19 @@ pub fn instantiate(
             uri_suffix: msg.collection_info.uri_suffix,
             max_supply: msg.collection_info.max_supply,
             is_active: false,
+            pending_supply: 0,
+            placeholder_uri: msg.collection_info.placeholder_uri,
+            provenance_hash: msg.collection_info.provenance_hash,
+            is_revealed: false,
+            token_id_mode: msg.collection_info.token_id_mode,
+            reserved_supply: if msg.collection_info.reserved_supply
+                <= msg.collection_info.max_supply
+            {
+                msg.collection_info.reserved_supply
+            } else {
+                return Err(ContractError::InvalidReservedSupply {});
+            },
+            airdropped_supply: 0,
             launchpad_fee: if msg.launchpad_fee < 100 {
                 // we will not take all the profit of creator ^^
                 msg.launchpad_fee
@@ -91,7 +139,8 @@ pub fn instantiate(
             msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
                 code_id: msg.colection_code_id,
                 funds: vec![],
-                admin: Some(info.sender.to_string()),
+                // the admin of the launchpad can migrate the collection
+                admin: Some(config.admin.to_string()),
                 label: "cw2981-instantiate".to_string(),
                 msg: to_binary(&Cw2981InstantiateMsg {
                     name: msg.collection_info.name,
@@ -121,6 +170,9 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
         max_nfts_per_address: 0,
         price: coin(0, "uaura"),
         is_public: false,
+        price_type: PriceType::Native,
+        merkle_root: None,
+        voucher_signer: None,
     };
 
     // store the dummy phase config with the phase id 0
@@ -174,10 +226,30 @@ pub fn execute(
             phase_id,
             addresses,
         } => remove_whitelist(deps, env, info, phase_id, addresses),
-        ExecuteMsg::Mint { phase_id, amount } => mint(deps, env, info, phase_id, amount),
+        ExecuteMsg::SetMerkleRoot {
+            phase_id,
+            merkle_root,
+        } => set_merkle_root(deps, env, info, phase_id, merkle_root),
+        ExecuteMsg::SetVoucherSigner {
+            phase_id,
+            voucher_signer,
+        } => set_voucher_signer(deps, env, info, phase_id, voucher_signer),
+        ExecuteMsg::Mint {
+            phase_id,
+            amount,
+            merkle_proof,
+            voucher,
+        } => mint(deps, env, info, phase_id, amount, merkle_proof, voucher),
         ExecuteMsg::ActivateLaunchpad {} => active_launchpad(deps, info),
         ExecuteMsg::DeactivateLaunchpad {} => deactive_launchpad(deps, info),
-        ExecuteMsg::Withdraw { denom } => withdraw(deps, env, info, denom),
+        ExecuteMsg::Receive(receive_msg) => receive_cw20(deps, env, info, receive_msg),
+        ExecuteMsg::AirdropMint { recipients } => airdrop_mint(deps, env, info, recipients),
+        ExecuteMsg::Reveal { uri_prefix } => reveal(deps, info, uri_prefix),
+        ExecuteMsg::NoisReceive { callback } => nois_receive(deps, env, info, callback),
+        ExecuteMsg::CancelPendingMint { job_id } => cancel_pending_mint(deps, env, info, job_id),
+        ExecuteMsg::SetNoisFee { nois_fee } => set_nois_fee(deps, info, nois_fee),
+        ExecuteMsg::SetPayees { payees } => set_payees(deps, env, info, payees),
+        ExecuteMsg::Withdraw { denom } => withdraw(deps, env, denom),
     }
 }
 
@@ -199,6 +271,9 @@ fn add_mint_phase(
         return Err(ContractError::Unauthorized {});
     }
 
+    // the price of a cw20 phase must be the address of the token
+    validate_phase_price(&deps, &phase_data)?;
+
     //load the launchpad_info
     let mut launchpad_info = LAUNCHPAD_INFO.load(deps.storage)?;
 
@@ -244,6 +319,9 @@ fn add_mint_phase(
                 max_nfts_per_address: phase_data.max_nfts_per_address,
                 price: phase_data.price,
                 is_public: phase_data.is_public,
+                price_type: phase_data.price_type,
+                merkle_root: None,
+                voucher_signer: None,
             };
             PHASE_CONFIGS.save(deps.storage, valid_phase_id, &phase_config_data)?;
 
@@ -295,6 +373,9 @@ fn add_mint_phase(
                 max_nfts_per_address: phase_data.max_nfts_per_address,
                 price: phase_data.price,
                 is_public: phase_data.is_public,
+                price_type: phase_data.price_type,
+                merkle_root: None,
+                voucher_signer: None,
             };
             PHASE_CONFIGS.save(deps.storage, valid_phase_id, &phase_config_data)?;
 
@@ -337,6 +418,9 @@ pub fn update_mint_phase(
         return Err(ContractError::InvalidPhaseId {});
     }
 
+    // the price of a cw20 phase must be the address of the token
+    validate_phase_price(&deps, &phase_data)?;
+
     // check the new time of the phase_data
     if !verify_phase_time(
         &deps,
@@ -365,6 +449,7 @@ pub fn update_mint_phase(
             max_nfts_per_address: phase_data.max_nfts_per_address,
             price: phase_data.price.clone(),
             is_public: phase_data.is_public,
+            price_type: phase_data.price_type.clone(),
             ..phase_config
         },
     )?;
@@ -532,12 +617,164 @@ pub fn remove_whitelist(
     Ok(Response::new().add_attributes([("action", "remove_whitelist")]))
 }
 
+pub fn set_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    phase_id: u64,
+    merkle_root: Option<String>,
+) -> Result<Response, ContractError> {
+    // check if the launchpad started, then return error
+    if is_launchpad_started(deps.storage, &env) {
+        return Err(ContractError::LaunchpadStarted {});
+    }
+
+    // check if the sender is not the owner, then return error
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the dummy phase cannot be minted
+    if phase_id == 0 {
+        return Err(ContractError::InvalidPhaseId {});
+    }
+    let mut phase_config = PHASE_CONFIGS
+        .may_load(deps.storage, phase_id)?
+        .ok_or(ContractError::InvalidPhaseId {})?;
+
+    // the merkle root must be a hex encoded sha256 hash
+    if let Some(merkle_root) = &merkle_root {
+        decode_hash(merkle_root).map_err(|_| ContractError::InvalidMerkleRoot {})?;
+    }
+
+    phase_config.merkle_root = merkle_root.clone();
+    PHASE_CONFIGS.save(deps.storage, phase_id, &phase_config)?;
+
+    Ok(Response::new().add_attributes([
+        ("action", "set_merkle_root"),
+        ("phase_id", &phase_id.to_string()),
+        ("merkle_root", &merkle_root.unwrap_or_default()),
+    ]))
+}
+
+pub fn set_voucher_signer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    phase_id: u64,
+    voucher_signer: Option<Binary>,
+) -> Result<Response, ContractError> {
+    // check if the launchpad started, then return error
+    if is_launchpad_started(deps.storage, &env) {
+        return Err(ContractError::LaunchpadStarted {});
+    }
+
+    // check if the sender is not the owner, then return error
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the dummy phase cannot be minted
+    if phase_id == 0 {
+        return Err(ContractError::InvalidPhaseId {});
+    }
+    let mut phase_config = PHASE_CONFIGS
+        .may_load(deps.storage, phase_id)?
+        .ok_or(ContractError::InvalidPhaseId {})?;
+
+    // the voucher signer must be a compressed or uncompressed secp256k1 public key
+    if let Some(voucher_signer) = &voucher_signer {
+        if voucher_signer.len() != 33 && voucher_signer.len() != 65 {
+            return Err(ContractError::InvalidVoucherSigner {});
+        }
+    }
+
+    phase_config.voucher_signer = voucher_signer.clone();
+    PHASE_CONFIGS.save(deps.storage, phase_id, &phase_config)?;
+
+    Ok(Response::new().add_attributes([
+        ("action", "set_voucher_signer"),
+        ("phase_id", &phase_id.to_string()),
+        (
+            "voucher_signer",
+            &voucher_signer
+                .map(|signer| signer.to_base64())
+                .unwrap_or_default(),
+        ),
+    ]))
+}
+
+// the payment sent by the minter
+enum Payment {
+    Native(Vec<Coin>),
+    Cw20 { token: Addr, amount: Uint128 },
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn mint(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     phase_id: u64,
     amount: Option<u64>,
+    merkle_proof: Option<MerkleProof>,
+    voucher: Option<MintVoucher>,
+) -> Result<Response, ContractError> {
+    mint_with_payment(
+        deps,
+        env,
+        info.sender,
+        Payment::Native(info.funds),
+        phase_id,
+        amount,
+        merkle_proof,
+        voucher,
+    )
+}
+
+// the cw20 token sends the payment to the launchpad
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let minter = deps.api.addr_validate(&receive_msg.sender)?;
+
+    match from_binary(&receive_msg.msg)? {
+        ReceiveMsg::Mint {
+            phase_id,
+            amount,
+            merkle_proof,
+            voucher,
+        } => mint_with_payment(
+            deps,
+            env,
+            minter,
+            Payment::Cw20 {
+                token: info.sender,
+                amount: receive_msg.amount,
+            },
+            phase_id,
+            amount,
+            merkle_proof,
+            voucher,
+        ),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn mint_with_payment(
+    deps: DepsMut,
+    env: Env,
+    minter: Addr,
+    payment: Payment,
+    phase_id: u64,
+    amount: Option<u64>,
+    merkle_proof: Option<MerkleProof>,
+    voucher: Option<MintVoucher>,
 ) -> Result<Response, ContractError> {
     let amount_nfts = amount.unwrap_or(1);
 
@@ -554,17 +791,55 @@ pub fn mint(
     }
 
     // load the phase_config of the phase_id
-    let mut phase_config: PhaseConfig = PHASE_CONFIGS.load(deps.storage, phase_id).unwrap();
+    let mut phase_config: PhaseConfig = PHASE_CONFIGS
+        .may_load(deps.storage, phase_id)?
+        .ok_or(ContractError::InvalidPhaseId {})?;
 
     // mayload the minted_nfts of sender
-    let minted_nfts_result = WHITELIST
-        .may_load(deps.storage, (phase_id, info.sender.clone()))
-        .unwrap();
+    let minted_nfts_result = WHITELIST.may_load(deps.storage, (phase_id, minter.clone()))?;
+
+    // get the maximum number of nfts the sender can mint in the phase
+    let mut voucher_hash = None;
+    let max_nfts_per_address = match (&phase_config.voucher_signer, &phase_config.merkle_root) {
+        // the sender must have a voucher signed by the voucher signer of the phase
+        (Some(voucher_signer), _) if !phase_config.is_public => {
+            let voucher = voucher.ok_or(ContractError::InvalidVoucher {})?;
+            let hash = verify_voucher(
+                deps.as_ref(),
+                &env,
+                voucher_signer,
+                phase_id,
+                &minter,
+                &voucher,
+            )?;
 
-    // check if the phase is not public and the sender is not in the whitelist of the phase_id, then return error
-    if !phase_config.is_public && minted_nfts_result.is_none() {
-        return Err(ContractError::Unauthorized {});
-    }
+            // each voucher can only be used up to its max_amount
+            let used_amount = VOUCHERS.may_load(deps.storage, &hash)?.unwrap_or(0);
+            if used_amount + amount_nfts > voucher.max_amount {
+                return Err(ContractError::UserMintedTooMuchNfts {});
+            }
+            VOUCHERS.save(deps.storage, &hash, &(used_amount + amount_nfts))?;
+            voucher_hash = Some(Binary::from(hash));
+
+            // the voucher replaces the limit of nfts per address
+            u64::MAX
+        }
+        // the sender must prove that it is a leaf of the merkle tree of the phase
+        (_, Some(merkle_root)) if !phase_config.is_public => {
+            let merkle_proof = merkle_proof.ok_or(ContractError::InvalidMerkleProof {})?;
+            verify_merkle_proof(merkle_root, &minter, &merkle_proof)?;
+            merkle_proof
+                .allowance
+                .unwrap_or(phase_config.max_nfts_per_address)
+        }
+        _ => {
+            // check if the phase is not public and the sender is not in the whitelist of the phase_id, then return error
+            if !phase_config.is_public && minted_nfts_result.is_none() {
+                return Err(ContractError::Unauthorized {});
+            }
+            phase_config.max_nfts_per_address
+        }
+    };
 
     // check if the current time is not in the phase_config, then return error
     if env.block.time < phase_config.start_time || env.block.time > phase_config.end_time {
@@ -572,7 +847,14 @@ pub fn mint(
     }
 
     // check if the total supply of the phase_id is greater than or equal to the max_supply, then return error
-    if launchpad_info.total_supply + amount_nfts > launchpad_info.max_supply
+    // the reserved supply cannot be minted in the phases
+    let remaining_reserved_supply =
+        launchpad_info.reserved_supply - launchpad_info.airdropped_supply;
+    if launchpad_info.total_supply
+        + launchpad_info.pending_supply
+        + remaining_reserved_supply
+        + amount_nfts
+        > launchpad_info.max_supply
         || (phase_config.max_supply.is_some()
             && phase_config.total_supply + amount_nfts > phase_config.max_supply.unwrap())
     {
@@ -584,81 +866,535 @@ pub fn mint(
 
     // check if the number of minted NFTs of the sender is greater than or equal to the max_mint of the phase_id, then return error
     let mut minted_nfts = minted_nfts_result.unwrap_or(0u64);
-    if minted_nfts + amount_nfts > phase_config.max_nfts_per_address {
+    if minted_nfts + amount_nfts > max_nfts_per_address {
         return Err(ContractError::UserMintedTooMuchNfts {});
     }
 
     // increase the number of minted NFTs of the sender
     minted_nfts += amount_nfts;
-    WHITELIST.save(deps.storage, (phase_id, info.sender.clone()), &minted_nfts)?;
+    WHITELIST.save(deps.storage, (phase_id, minter.clone()), &minted_nfts)?;
 
     // check if the funds is not enough, then return error
-    if !has_coins(
-        &info.funds,
-        &Coin {
-            denom: phase_config.price.denom,
-            amount: phase_config
-                .price
-                .amount
-                .checked_mul(Uint128::from(amount_nfts))
-                .unwrap(),
-        },
-    ) {
-        return Err(ContractError::NotEnoughFunds {});
+    let price = Coin {
+        denom: phase_config.price.denom,
+        amount: phase_config
+            .price
+            .amount
+            .checked_mul(Uint128::from(amount_nfts))
+            .map_err(StdError::overflow)?,
+    };
+    // the minter also pays the fee of the randomness request of the mint
+    let config = CONFIG.load(deps.storage)?;
+    let refund_msg = check_payment(
+        &minter,
+        &phase_config.price_type,
+        &price,
+        nois_fee(&config, &launchpad_info),
+        payment,
+    )?;
+
+    // mint NFT(s) for the sender
+    let (mint_msgs, job_id) = issue_nfts(
+        deps.storage,
+        &env,
+        &mut launchpad_info,
+        &minter,
+        amount_nfts,
+        Some(phase_id),
+        Some(price),
+        voucher_hash,
+    )?;
+    let mut res: Response = Response::new().add_messages(mint_msgs);
+    if let Some(job_id) = job_id {
+        res = res.add_attribute("job_id", job_id);
     }
 
-    // get current time
-    let current_time = env.block.time;
+    // the launchpad only keeps the price of the minted nfts
+    if let Some(refund_msg) = refund_msg {
+        res = res.add_message(refund_msg);
+    }
 
-    // mint NFT(s) for the sender
-    let mut res: Response = Response::new();
-    for _ in 0..amount_nfts {
-        // get the number of remaining nfts launchpad
-        let remaining_nfts = launchpad_info.max_supply - launchpad_info.total_supply;
+    Ok(res.add_attributes([
+        ("action", "launchpad_mint"),
+        ("owner", minter.as_ref()),
+        ("phase_id", &phase_id.to_string()),
+        ("amount", &amount_nfts.to_string()),
+    ]))
+}
+
+// mint the nfts for the minter, or request the randomness of the token_ids from the nois proxy
+// then return the messages and the job_id of the pending mint
+#[allow(clippy::too_many_arguments)]
+fn issue_nfts(
+    storage: &mut dyn Storage,
+    env: &Env,
+    launchpad_info: &mut LaunchpadInfo,
+    minter: &Addr,
+    amount_nfts: u64,
+    phase_id: Option<u64>,
+    price: Option<Coin>,
+    voucher_hash: Option<Binary>,
+) -> StdResult<(Vec<WasmMsg>, Option<String>)> {
+    let config = CONFIG.load(storage)?;
+    match config.nois_proxy {
+        // the token_ids are chosen when the nois proxy sends the randomness
+        Some(nois_proxy) if launchpad_info.token_id_mode == TokenIdMode::Random => {
+            let job_id = LAST_JOB_ID.may_load(storage)?.unwrap_or(0) + 1;
+            LAST_JOB_ID.save(storage, &job_id)?;
+            PENDING_MINTS.save(
+                storage,
+                &job_id.to_string(),
+                &PendingMint {
+                    minter: minter.clone(),
+                    amount: amount_nfts,
+                    requested_at: env.block.time,
+                    phase_id,
+                    price,
+                    voucher_hash,
+                },
+            )?;
+
+            // the pending nfts are reserved in the supply of the launchpad
+            launchpad_info.pending_supply += amount_nfts;
+            LAUNCHPAD_INFO.save(storage, launchpad_info)?;
+
+            let request_msg = WasmMsg::Execute {
+                contract_addr: nois_proxy.to_string(),
+                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness {
+                    job_id: job_id.to_string(),
+                })?,
+                funds: config.nois_fee,
+            };
+            Ok((vec![request_msg], Some(job_id.to_string())))
+        }
+        _ => {
+            let mint_msgs =
+                mint_token_ids(storage, launchpad_info, minter, amount_nfts, env.block.time)?;
+
+            // save the launchpad info
+            LAUNCHPAD_INFO.save(storage, launchpad_info)?;
+            Ok((mint_msgs, None))
+        }
+    }
+}
 
-        // generate random token_id
-        let token_id = generate_random_token_id(
+// mint the reserved nfts for free, before or after the phases
+pub fn airdrop_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipients: Vec<(String, u64)>,
+) -> Result<Response, ContractError> {
+    let mut launchpad_info: LaunchpadInfo = LAUNCHPAD_INFO.load(deps.storage)?;
+
+    // check if the sender is not the owner or the creator, then return error
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.admin != info.sender && launchpad_info.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // each recipient must receive at least one nft
+    if recipients.is_empty() || recipients.iter().any(|(_, amount)| *amount == 0) {
+        return Err(ContractError::InvalidAirdrop {});
+    }
+
+    // the airdropped nfts are taken from the reserved supply
+    let amount_nfts = recipients
+        .iter()
+        .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+        .ok_or(ContractError::ReservedSupplyReached {})?;
+    launchpad_info.airdropped_supply = launchpad_info
+        .airdropped_supply
+        .checked_add(amount_nfts)
+        .filter(|airdropped_supply| *airdropped_supply <= launchpad_info.reserved_supply)
+        .ok_or(ContractError::ReservedSupplyReached {})?;
+
+    // the sender pays the fee of the randomness request of each recipient
+    let mut nois_fees = vec![];
+    for _ in 0..recipients.len() {
+        add_coins(&mut nois_fees, nois_fee(&config, &launchpad_info))?;
+    }
+    let surplus = take_native_payment(info.funds, &nois_fees)?;
+
+    let mut res = Response::new();
+    if let Some(refund_msg) = refund_msg(&info.sender, Payment::Native(surplus))? {
+        res = res.add_message(refund_msg);
+    }
+    for (recipient, amount) in recipients {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        let (mint_msgs, job_id) = issue_nfts(
             deps.storage,
-            current_time,
-            info.sender.to_string(),
-            remaining_nfts,
-        )
-        .unwrap();
+            &env,
+            &mut launchpad_info,
+            &recipient,
+            amount,
+            None,
+            None,
+            None,
+        )?;
+        res = res.add_messages(mint_msgs);
+        if let Some(job_id) = job_id {
+            res = res.add_attribute("job_id", job_id);
+        }
+    }
+    LAUNCHPAD_INFO.save(deps.storage, &launchpad_info)?;
+
+    Ok(res.add_attributes([
+        ("action", "airdrop_mint"),
+        ("amount", &amount_nfts.to_string()),
+    ]))
+}
+
+// the nois proxy sends the randomness of a pending mint
+pub fn nois_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    callback: NoisCallback,
+) -> Result<Response, ContractError> {
+    // only the nois proxy can send the randomness
+    let config = CONFIG.load(deps.storage)?;
+    if config.nois_proxy != Some(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pending_mint = PENDING_MINTS
+        .may_load(deps.storage, &callback.job_id)?
+        .ok_or(ContractError::UnknownNoisJob {
+            job_id: callback.job_id.clone(),
+        })?;
+    PENDING_MINTS.remove(deps.storage, &callback.job_id);
+
+    // the randomness of the beacon is not known when minting, so the token_ids cannot be predicted
+    let randomness: [u8; 32] = callback
+        .randomness
+        .to_array()
+        .map_err(|_| ContractError::InvalidRandomness {})?;
+    RANDOM_SEED.save(deps.storage, &randomness)?;
+
+    let mut launchpad_info = LAUNCHPAD_INFO.load(deps.storage)?;
+    launchpad_info.pending_supply -= pending_mint.amount;
+    let mint_msgs = mint_token_ids(
+        deps.storage,
+        &mut launchpad_info,
+        &pending_mint.minter,
+        pending_mint.amount,
+        env.block.time,
+    )?;
+    LAUNCHPAD_INFO.save(deps.storage, &launchpad_info)?;
+
+    Ok(Response::new().add_messages(mint_msgs).add_attributes([
+        ("action", "nois_receive"),
+        ("job_id", &callback.job_id),
+        ("owner", pending_mint.minter.as_ref()),
+        ("amount", &pending_mint.amount.to_string()),
+    ]))
+}
+
+// the pending mint is cancelled when the nois proxy did not send the randomness in time,
+// its nfts are released from the supply and the price is refunded to the minter
+pub fn cancel_pending_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: String,
+) -> Result<Response, ContractError> {
+    let pending_mint =
+        PENDING_MINTS
+            .may_load(deps.storage, &job_id)?
+            .ok_or(ContractError::UnknownNoisJob {
+                job_id: job_id.clone(),
+            })?;
+
+    // check if the sender is not the owner or the minter, then return error
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin != info.sender && pending_mint.minter != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.time
+        < pending_mint
+            .requested_at
+            .plus_seconds(PENDING_MINT_TIMEOUT_SECONDS)
+    {
+        return Err(ContractError::PendingMintNotExpired { job_id });
+    }
+    PENDING_MINTS.remove(deps.storage, &job_id);
+
+    let mut launchpad_info = LAUNCHPAD_INFO.load(deps.storage)?;
+    launchpad_info.pending_supply -= pending_mint.amount;
+
+    let mut res = Response::new();
+    match pending_mint.phase_id {
+        Some(phase_id) => {
+            // the minter can mint the cancelled nfts again in the phase
+            let mut phase_config = PHASE_CONFIGS.load(deps.storage, phase_id)?;
+            phase_config.total_supply -= pending_mint.amount;
+            PHASE_CONFIGS.save(deps.storage, phase_id, &phase_config)?;
+            WHITELIST.update(
+                deps.storage,
+                (phase_id, pending_mint.minter.clone()),
+                |minted_nfts| -> StdResult<u64> {
+                    Ok(minted_nfts.unwrap_or_default() - pending_mint.amount)
+                },
+            )?;
+            if let Some(voucher_hash) = &pending_mint.voucher_hash {
+                VOUCHERS.update(
+                    deps.storage,
+                    voucher_hash,
+                    |used_amount| -> StdResult<u64> {
+                        Ok(used_amount.unwrap_or_default() - pending_mint.amount)
+                    },
+                )?;
+            }
+
+            if let Some(price) = pending_mint.price {
+                let payment = match phase_config.price_type {
+                    PriceType::Native => Payment::Native(vec![price]),
+                    PriceType::Cw20 => Payment::Cw20 {
+                        token: Addr::unchecked(price.denom),
+                        amount: price.amount,
+                    },
+                };
+                if let Some(refund_msg) = refund_msg(&pending_mint.minter, payment)? {
+                    res = res.add_message(refund_msg);
+                }
+            }
+        }
+        // the cancelled nfts are reserved for the airdrops again
+        None => launchpad_info.airdropped_supply -= pending_mint.amount,
+    }
+    LAUNCHPAD_INFO.save(deps.storage, &launchpad_info)?;
+
+    Ok(res.add_attributes([
+        ("action", "cancel_pending_mint"),
+        ("job_id", &job_id),
+        ("owner", pending_mint.minter.as_ref()),
+        ("amount", &pending_mint.amount.to_string()),
+    ]))
+}
+
+pub fn set_nois_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    nois_fee: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    // check if the sender is not the owner, then return error
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.nois_fee = nois_fee;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "set_nois_fee"))
+}
+
+// create the mint messages of the random token_ids for the minter
+fn mint_token_ids(
+    storage: &mut dyn Storage,
+    launchpad_info: &mut LaunchpadInfo,
+    minter: &Addr,
+    amount_nfts: u64,
+    current_time: Timestamp,
+) -> StdResult<Vec<WasmMsg>> {
+    let mut mint_msgs = vec![];
+    for _ in 0..amount_nfts {
+        let token_id = match launchpad_info.token_id_mode {
+            TokenIdMode::Random => {
+                // get the number of remaining nfts launchpad
+                let remaining_nfts = launchpad_info.max_supply - launchpad_info.total_supply;
+
+                // generate random token_id
+                generate_random_token_id(storage, current_time, minter.to_string(), remaining_nfts)?
+            }
+            // the next token_id is the number of minted nfts + 1, REMAINING_TOKEN_IDS is not used
+            TokenIdMode::Sequential => (launchpad_info.total_supply + 1).to_string(),
+        };
 
         // Move the increasing total supply of the the launchpad to here.
         // This ensures that the remaining NFTs is always updated.
         launchpad_info.total_supply += 1;
 
-        // get the token_uri based on the token_id
-        let token_uri = get_token_uri(
-            &launchpad_info.uri_prefix,
-            &token_id,
-            &launchpad_info.uri_suffix,
-        );
+        // get the token_uri based on the token_id, or the placeholder_uri until the reveal
+        let token_uri = match &launchpad_info.placeholder_uri {
+            Some(placeholder_uri) if !launchpad_info.is_revealed => placeholder_uri.clone(),
+            _ => get_token_uri(
+                &launchpad_info.uri_prefix,
+                &token_id,
+                &launchpad_info.uri_suffix,
+            ),
+        };
 
         // create mint message NFT for the sender
-        let mint_msg = WasmMsg::Execute {
+        mint_msgs.push(WasmMsg::Execute {
             contract_addr: launchpad_info.collection_address.to_string(),
             msg: to_binary(&Cw2981ExecuteMsg::Mint(MintMsg {
                 token_id,
-                owner: info.sender.to_string(),
+                owner: minter.to_string(),
                 token_uri: Some(token_uri),
                 extension: None,
             }))?,
             funds: vec![],
-        };
+        });
+    }
+    Ok(mint_msgs)
+}
 
-        res = res.add_message(mint_msg);
+// the fee of the nois proxy paid for each randomness request, empty if the token_ids are not
+// chosen with the randomness of the nois proxy
+fn nois_fee<'a>(config: &'a Config, launchpad_info: &LaunchpadInfo) -> &'a [Coin] {
+    match config.nois_proxy {
+        Some(_) if launchpad_info.token_id_mode == TokenIdMode::Random => &config.nois_fee,
+        _ => &[],
     }
+}
+
+// add the coins to the list of coins, merging the coins of the same denom
+fn add_coins(coins: &mut Vec<Coin>, added_coins: &[Coin]) -> StdResult<()> {
+    for added_coin in added_coins {
+        match coins.iter_mut().find(|coin| coin.denom == added_coin.denom) {
+            Some(coin) => coin.amount = coin.amount.checked_add(added_coin.amount)?,
+            None => coins.push(added_coin.clone()),
+        }
+    }
+    Ok(())
+}
+
+// take the required coins from the funds, then return the surplus of the funds
+fn take_native_payment(funds: Vec<Coin>, required: &[Coin]) -> Result<Vec<Coin>, ContractError> {
+    if required.iter().any(|coin| !has_coins(&funds, coin)) {
+        return Err(ContractError::NotEnoughFunds {});
+    }
+
+    Ok(funds
+        .into_iter()
+        .map(|fund| {
+            let paid_amount = required
+                .iter()
+                .filter(|coin| coin.denom == fund.denom)
+                .map(|coin| coin.amount)
+                .sum::<Uint128>();
+            Coin {
+                denom: fund.denom,
+                amount: fund.amount - paid_amount,
+            }
+        })
+        .collect())
+}
 
-    // save the launchpad info
+// check the payment of the price and the nois fee by the minter, then return the message
+// refunding the surplus and any other coins sent with the message
+fn check_payment(
+    minter: &Addr,
+    price_type: &PriceType,
+    price: &Coin,
+    nois_fee: &[Coin],
+    payment: Payment,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let surplus = match (price_type, payment) {
+        (PriceType::Native, Payment::Native(funds)) => {
+            let mut required = vec![price.clone()];
+            add_coins(&mut required, nois_fee)?;
+            Payment::Native(take_native_payment(funds, &required)?)
+        }
+        (PriceType::Cw20, Payment::Cw20 { token, amount }) => {
+            // the native coins of the nois fee cannot be sent with the cw20 tokens
+            if !nois_fee.is_empty() {
+                return Err(ContractError::InvalidNoisFeePayment {});
+            }
+            if token != price.denom {
+                return Err(ContractError::InvalidPaymentToken {});
+            }
+            if amount < price.amount {
+                return Err(ContractError::NotEnoughFunds {});
+            }
+
+            Payment::Cw20 {
+                token,
+                amount: amount - price.amount,
+            }
+        }
+        _ => return Err(ContractError::InvalidPaymentToken {}),
+    };
+    Ok(refund_msg(minter, surplus)?)
+}
+
+// the message sending the payment back to the recipient, None when there is nothing to send
+fn refund_msg(recipient: &Addr, payment: Payment) -> StdResult<Option<CosmosMsg>> {
+    match payment {
+        Payment::Native(funds) => {
+            let funds: Vec<Coin> = funds
+                .into_iter()
+                .filter(|fund| !fund.amount.is_zero())
+                .collect();
+            if funds.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: funds,
+            })))
+        }
+        Payment::Cw20 { token, amount } => {
+            if amount.is_zero() {
+                return Ok(None);
+            }
+            Ok(Some(transfer_msg(true, token.as_str(), recipient, amount)?))
+        }
+    }
+}
+
+fn validate_phase_price(deps: &DepsMut, phase_data: &PhaseData) -> Result<(), ContractError> {
+    if phase_data.price_type == PriceType::Cw20 {
+        deps.api
+            .addr_validate(&phase_data.price.denom)
+            .map_err(|_| ContractError::InvalidPaymentToken {})?;
+    }
+    Ok(())
+}
+
+pub fn reveal(
+    deps: DepsMut,
+    info: MessageInfo,
+    uri_prefix: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is not the owner, then return error
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut launchpad_info: LaunchpadInfo = LAUNCHPAD_INFO.load(deps.storage)?;
+    if launchpad_info.placeholder_uri.is_none() {
+        return Err(ContractError::NoDelayedReveal {});
+    }
+    if launchpad_info.is_revealed {
+        return Err(ContractError::AlreadyRevealed {});
+    }
+
+    // the revealed uri_prefix must be the one committed before the sale,
+    // the provenance hash is the hex encoded sha256 hash of the uri_prefix
+    let provenance_hash = launchpad_info
+        .provenance_hash
+        .as_deref()
+        .ok_or(ContractError::InvalidProvenanceHash {})?;
+    let committed_hash =
+        decode_hash(provenance_hash).map_err(|_| ContractError::InvalidProvenanceHash {})?;
+    if committed_hash[..] != Sha256::digest(uri_prefix.as_bytes())[..] {
+        return Err(ContractError::InvalidProvenanceHash {});
+    }
+
+    // the token_uris of the minted nfts are resolved with the TokenUri query from now on
+    launchpad_info.uri_prefix = uri_prefix.clone();
+    launchpad_info.is_revealed = true;
     LAUNCHPAD_INFO.save(deps.storage, &launchpad_info)?;
 
-    Ok(res.add_attributes([
-        ("action", "launchpad_mint"),
-        ("owner", info.sender.as_ref()),
-        ("phase_id", &phase_id.to_string()),
-        ("amount", &amount_nfts.to_string()),
+    Ok(Response::new().add_attributes([
+        ("action", "reveal"),
+        ("uri_prefix", &uri_prefix),
+        ("provenance_hash", provenance_hash),
     ]))
 }
 
@@ -769,6 +1505,79 @@ fn get_token_id_from_position(
     Ok(token_id.to_string())
 }
 
+fn decode_hash(hash: &str) -> Result<[u8; 32], hex::FromHexError> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hash, &mut bytes)?;
+    Ok(bytes)
+}
+
+// the root is computed from the leaf of the sender and the proof, then compared with the merkle_root
+fn verify_merkle_proof(
+    merkle_root: &str,
+    sender: &Addr,
+    merkle_proof: &MerkleProof,
+) -> Result<(), ContractError> {
+    // the leaf commits to the allowance of the sender if there is one
+    let leaf = match merkle_proof.allowance {
+        Some(allowance) => format!("{}:{}", sender, allowance),
+        None => sender.to_string(),
+    };
+    let leaf_hash: [u8; 32] = Sha256::digest(leaf.as_bytes()).into();
+
+    let root_hash = merkle_proof
+        .proof
+        .iter()
+        .try_fold(leaf_hash, |hash, sibling| {
+            let sibling_hash =
+                decode_hash(sibling).map_err(|_| ContractError::InvalidMerkleProof {})?;
+
+            // the pair is sorted, so the proof does not need the position of the hashes
+            let mut pair = [hash, sibling_hash];
+            pair.sort_unstable();
+            Ok::<[u8; 32], ContractError>(Sha256::digest(pair.concat()).into())
+        })?;
+
+    let merkle_root = decode_hash(merkle_root).map_err(|_| ContractError::InvalidMerkleRoot {})?;
+    if root_hash != merkle_root {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+    Ok(())
+}
+
+// check the voucher of the minter, then return the signed hash of the voucher
+fn verify_voucher(
+    deps: Deps,
+    env: &Env,
+    voucher_signer: &Binary,
+    phase_id: u64,
+    minter: &Addr,
+    voucher: &MintVoucher,
+) -> Result<[u8; 32], ContractError> {
+    if env.block.time > voucher.expiry {
+        return Err(ContractError::VoucherExpired {});
+    }
+
+    // the voucher is only valid for this launchpad, phase and minter
+    let message = format!(
+        "{}:{}:{}:{}:{}",
+        env.contract.address,
+        phase_id,
+        minter,
+        voucher.max_amount,
+        voucher.expiry.nanos()
+    );
+    let message_hash: [u8; 32] = Sha256::digest(message.as_bytes()).into();
+
+    let verified = deps
+        .api
+        .secp256k1_verify(&message_hash, &voucher.signature, voucher_signer)
+        .map_err(|_| ContractError::InvalidVoucher {})?;
+    if !verified {
+        return Err(ContractError::InvalidVoucher {});
+    }
+    Ok(message_hash)
+}
+
 fn get_token_uri(uri_prefix: &str, token_id: &str, uri_suffix: &str) -> String {
     // TODO: maybe we need the suffix of the token_uri, too
     // the token_uri is the uri_prefix + token_id + uri_suffix
@@ -834,18 +1643,49 @@ fn verify_phase_time(
     true
 }
 
-pub fn withdraw(
+pub fn set_payees(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    denom: String,
+    payees: Vec<(String, u32)>,
 ) -> Result<Response, ContractError> {
-    // check if the sender is the creator of collection
+    // check if the launchpad started, then return error
+    if is_launchpad_started(deps.storage, &env) {
+        return Err(ContractError::LaunchpadStarted {});
+    }
+
+    // only the creator of collection can set the payees of its revenue
     let launchpad_info = LAUNCHPAD_INFO.load(deps.storage)?;
     if info.sender != launchpad_info.creator {
         return Err(ContractError::Unauthorized {});
     }
 
+    let mut valid_payees: Vec<Payee> = Vec::with_capacity(payees.len());
+    for (address, shares) in payees {
+        let address = deps.api.addr_validate(&address)?;
+
+        // a payee is listed once and receives a part of the revenue
+        if shares == 0 || shares > 100 || valid_payees.iter().any(|payee| payee.address == address)
+        {
+            return Err(ContractError::InvalidPayees {});
+        }
+        valid_payees.push(Payee { address, shares });
+    }
+
+    // the shares are percentages of the revenue, they must sum to 100
+    if valid_payees.iter().map(|payee| payee.shares).sum::<u32>() != 100 {
+        return Err(ContractError::InvalidPayees {});
+    }
+    PAYEES.save(deps.storage, &valid_payees)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_payees")
+        .add_attribute("payees", valid_payees.len().to_string()))
+}
+
+pub fn withdraw(deps: DepsMut, env: Env, denom: String) -> Result<Response, ContractError> {
+    let launchpad_info = LAUNCHPAD_INFO.load(deps.storage)?;
+
     // cannot withdraw if the last phase of launchpad is not finished
     // load the phase config of the last phase
     let last_phase_config = PHASE_CONFIGS.load(deps.storage, launchpad_info.last_phase_id)?;
@@ -855,17 +1695,45 @@ pub fn withdraw(
         return Err(ContractError::LastPhaseNotFinished {});
     }
 
-    // get the balance of contract in bank
-    let contract_balance: BalanceResponse =
-        deps.querier.query(&QueryRequest::Bank(BankQuery::Balance {
-            address: env.contract.address.to_string(),
-            denom: denom.clone(),
-        }))?;
+    // the denom is a cw20 token if it is the address of a cw20 contract
+    let is_cw20 = deps.api.addr_validate(&denom).is_ok()
+        && deps
+            .querier
+            .query_wasm_smart::<TokenInfoResponse>(&denom, &Cw20QueryMsg::TokenInfo {})
+            .is_ok();
+
+    // get the balance of contract in bank or in the cw20 token
+    let contract_balance = if is_cw20 {
+        let balance: Cw20BalanceResponse = deps.querier.query_wasm_smart(
+            &denom,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        balance.balance
+    } else {
+        let balance: BalanceResponse =
+            deps.querier.query(&QueryRequest::Bank(BankQuery::Balance {
+                address: env.contract.address.to_string(),
+                denom: denom.clone(),
+            }))?;
+        balance.amount.amount
+    };
+
+    // the prices of the pending mints stay in the launchpad until they are settled or refunded
+    let mut pending_amount = Uint128::zero();
+    for item in PENDING_MINTS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, pending_mint) = item?;
+        if let Some(price) = pending_mint.price.filter(|price| price.denom == denom) {
+            pending_amount += price.amount;
+        }
+    }
+    let contract_balance = contract_balance
+        .checked_sub(pending_amount)
+        .map_err(StdError::overflow)?;
 
     // get the withdraw amount of creator
     let creator_withdraw_amount = contract_balance
-        .amount
-        .amount
         .checked_multiply_ratio(
             100u32.checked_sub(launchpad_info.launchpad_fee).unwrap(),
             100u32,
@@ -874,37 +1742,44 @@ pub fn withdraw(
 
     // fee amount of launchpad is the rest of the contract balance
     let launchpad_fee_amount = contract_balance
-        .amount
-        .amount
         .checked_sub(creator_withdraw_amount)
         .unwrap();
 
     // load the launchpad_collector from contract config
     let launchpad_collector = CONFIG.load(deps.storage)?.launchpad_collector;
 
-    // send the withdraw amount to the creator
     let mut res: Response = Response::new()
         .add_attribute("action", "withdraw")
-        .add_message(CosmosMsg::Bank(BankMsg::Send {
-            to_address: launchpad_info.creator.to_string(),
-            amount: vec![Coin {
-                denom: denom.clone(),
-                amount: creator_withdraw_amount,
-            }],
-        }))
-        .add_attribute("creator", launchpad_info.creator)
         .add_attribute("withdraw_amount", creator_withdraw_amount);
 
-    // if the launchpad fee is not 0, then send the launchpad fee to the launchpad_collector
-    if launchpad_info.launchpad_fee != 0 {
+    // split the withdraw amount between the payees, the last payee receives the rounding dust
+    let payees = query_payees(deps.as_ref())?;
+    let mut remaining_amount = creator_withdraw_amount;
+    for (index, payee) in payees.iter().enumerate() {
+        let payee_amount = if index == payees.len() - 1 {
+            remaining_amount
+        } else {
+            creator_withdraw_amount.multiply_ratio(payee.shares, 100u32)
+        };
+        remaining_amount -= payee_amount;
+
+        if !payee_amount.is_zero() {
+            res = res
+                .add_message(transfer_msg(is_cw20, &denom, &payee.address, payee_amount)?)
+                .add_attribute("payee", payee.address.to_string())
+                .add_attribute("payee_amount", payee_amount);
+        }
+    }
+
+    // if the launchpad fee amount is not 0, then send the launchpad fee to the launchpad_collector
+    if !launchpad_fee_amount.is_zero() {
         res = res
-            .add_message(CosmosMsg::Bank(BankMsg::Send {
-                to_address: launchpad_collector.to_string(),
-                amount: vec![Coin {
-                    denom,
-                    amount: launchpad_fee_amount,
-                }],
-            }))
+            .add_message(transfer_msg(
+                is_cw20,
+                &denom,
+                &launchpad_collector,
+                launchpad_fee_amount,
+            )?)
             .add_attribute("launchpad_collector", launchpad_collector)
             .add_attribute("launchpad_fee_amount", launchpad_fee_amount);
     }
@@ -912,6 +1787,33 @@ pub fn withdraw(
     Ok(res.add_attribute("withdraw_time", env.block.time.to_string()))
 }
 
+// the message sending the native coins or the cw20 tokens to the recipient
+fn transfer_msg(
+    is_cw20: bool,
+    denom: &str,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    if is_cw20 {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: denom.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }))
+    } else {
+        Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.to_string(),
+                amount,
+            }],
+        }))
+    }
+}
+
 // we need a function to check when the launchpad started
 fn is_launchpad_started(storage: &dyn Storage, env: &Env) -> bool {
     // load the status of the launchpad
@@ -940,9 +1842,43 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetLaunchpadInfo {} => to_binary(&query_launchpad_info(deps)?),
         QueryMsg::GetAllPhaseConfigs {} => to_binary(&query_all_phase_configs(deps)?),
         QueryMsg::Mintable { user } => to_binary(&query_mintable(deps, Addr::unchecked(user))?),
+        QueryMsg::TokenUri { token_id } => to_binary(&query_token_uri(deps, token_id)?),
+        QueryMsg::Payees {} => to_binary(&query_payees(deps)?),
     }
 }
 
+pub fn query_payees(deps: Deps) -> StdResult<Vec<Payee>> {
+    match PAYEES.may_load(deps.storage)? {
+        Some(payees) => Ok(payees),
+        None => Ok(vec![Payee {
+            address: LAUNCHPAD_INFO.load(deps.storage)?.creator,
+            shares: 100,
+        }]),
+    }
+}
+
+pub fn query_token_uri(deps: Deps, token_id: String) -> StdResult<TokenUriResponse> {
+    let launchpad_info = LAUNCHPAD_INFO.load(deps.storage)?;
+
+    // the nft must be minted
+    let _: NftInfoResponse<Option<Metadata>> = deps.querier.query_wasm_smart(
+        &launchpad_info.collection_address,
+        &Cw2981QueryMsg::NftInfo {
+            token_id: token_id.clone(),
+        },
+    )?;
+
+    let token_uri = match launchpad_info.placeholder_uri {
+        Some(placeholder_uri) if !launchpad_info.is_revealed => placeholder_uri,
+        _ => get_token_uri(
+            &launchpad_info.uri_prefix,
+            &token_id,
+            &launchpad_info.uri_suffix,
+        ),
+    };
+    Ok(TokenUriResponse { token_uri })
+}
+
 pub fn query_launchpad_info(deps: Deps) -> StdResult<LaunchpadInfo> {
     let launchpad_info = LAUNCHPAD_INFO.load(deps.storage)?;
     Ok(launchpad_info)
@@ -979,6 +1915,9 @@ pub fn query_all_phase_configs(deps: Deps) -> StdResult<Vec<PhaseConfigResponse>
             max_nfts_per_address: phase_config.max_nfts_per_address,
             price: phase_config.price,
             is_public: phase_config.is_public,
+            price_type: phase_config.price_type,
+            merkle_root: phase_config.merkle_root,
+            voucher_signer: phase_config.voucher_signer,
         });
     }
 
@@ -1007,17 +1946,24 @@ pub fn query_mintable(deps: Deps, user: Addr) -> StdResult<Vec<MintableResponse>
         phase_config = PHASE_CONFIGS.load(deps.storage, phase_id).unwrap();
 
         // load the number of minted nfts of the user from WHITELIST base of the phase_id and the address of user
+        // in a phase with a merkle root or a voucher signer, the mintable number is an upper bound
+        // since the proof or the voucher is not known
         let minted_nfts = if WHITELIST.has(deps.storage, (phase_id, user.clone())) {
             WHITELIST
                 .load(deps.storage, (phase_id, user.clone()))
                 .unwrap()
-        } else if phase_config.is_public {
+        } else if phase_config.is_public
+            || phase_config.merkle_root.is_some()
+            || phase_config.voucher_signer.is_some()
+        {
             0
         } else {
             phase_config.max_nfts_per_address
         };
 
-        let mintable = phase_config.max_nfts_per_address - minted_nfts;
+        let mintable = phase_config
+            .max_nfts_per_address
+            .saturating_sub(minted_nfts);
 
         mintable_response.push(MintableResponse {
             phase_id,
@@ -45,6 +45,57 @@ pub enum ContractError {
     #[error("Last phase not finished")]
     LastPhaseNotFinished {},
 
+    #[error("Invalid merkle root")]
+    InvalidMerkleRoot {},
+
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("Invalid voucher signer")]
+    InvalidVoucherSigner {},
+
+    #[error("Invalid voucher")]
+    InvalidVoucher {},
+
+    #[error("Voucher expired")]
+    VoucherExpired {},
+
+    #[error("Invalid payment token")]
+    InvalidPaymentToken {},
+
+    #[error("Unknown nois job {job_id}")]
+    UnknownNoisJob { job_id: String },
+
+    #[error("Pending mint of nois job {job_id} is not expired")]
+    PendingMintNotExpired { job_id: String },
+
+    #[error("Invalid randomness")]
+    InvalidRandomness {},
+
+    #[error("Nois fee must be paid in native coins")]
+    InvalidNoisFeePayment {},
+
+    #[error("Launchpad has no delayed reveal")]
+    NoDelayedReveal {},
+
+    #[error("Launchpad is already revealed")]
+    AlreadyRevealed {},
+
+    #[error("Invalid provenance hash")]
+    InvalidProvenanceHash {},
+
+    #[error("Invalid reserved supply")]
+    InvalidReservedSupply {},
+
+    #[error("Reserved supply reached")]
+    ReservedSupplyReached {},
+
+    #[error("Invalid airdrop")]
+    InvalidAirdrop {},
+
+    #[error("Invalid payees")]
+    InvalidPayees {},
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
     // Add any other custom errors you like here.
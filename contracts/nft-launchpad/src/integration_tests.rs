@@ -1,19 +1,21 @@
 #[cfg(test)]
 mod tests {
     use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-    use crate::testing_config::env::{instantiate_contracts, ADMIN, CREATOR, LAUNCHPAD_COLLECTOR};
+    use crate::testing_config::env::{
+        add_phase_and_activate, fund_user, instantiate_contracts, instantiate_launchpad,
+        instantiate_nois_proxy, launchpad_instantiate_msg, mint, phase_data, query_launchpad_info,
+        query_tokens, start_phase, ADMIN, COLLECTION_NAME, COLLECTION_SYMBOL, CREATOR,
+        LAUNCHPAD_COLLECTOR, PRICE,
+    };
 
     use cosmwasm_std::Addr;
 
     use crate::msg::ColectionInfo;
-    use crate::state::LaunchpadInfo;
+    use crate::state::{LaunchpadInfo, TokenIdMode};
     use cw2981_royalties::QueryMsg as Cw721QueryMsg;
     use cw721::ContractInfoResponse;
     use cw_multi_test::{App, Executor};
 
-    pub const COLLECTION_NAME: &str = "A launchpad collection";
-    pub const COLLECTION_SYMBOL: &str = "LPC";
-
     mod create_launchpad {
         use super::*;
 
@@ -32,6 +34,10 @@ mod tests {
                     symbol: COLLECTION_SYMBOL.to_string(),
                     royalty_percentage: None,
                     royalty_payment_address: None,
+                    placeholder_uri: None,
+                    provenance_hash: None,
+                    token_id_mode: TokenIdMode::Random,
+                    reserved_supply: 0,
                     max_supply: 5000,
                     uri_prefix:
                         "ipfs://bafybeifm3xas2egfbwzo7cg5wiayw44sbvfn6h5am2bydp2zpnypl7g5tq/images/"
@@ -43,6 +49,8 @@ mod tests {
                     .to_string(),
                 launchpad_fee: 10,
                 launchpad_collector: Some(LAUNCHPAD_COLLECTOR.to_string()),
+                admin: None,
+                nois_proxy: None,
             };
 
             // instantiate launchpad contract
@@ -94,6 +102,10 @@ mod tests {
                     symbol: COLLECTION_SYMBOL.to_string(),
                     royalty_percentage: None,
                     royalty_payment_address: None,
+                    placeholder_uri: None,
+                    provenance_hash: None,
+                    token_id_mode: TokenIdMode::Random,
+                    reserved_supply: 0,
                     max_supply: 5000,
                     uri_prefix:
                         "ipfs://bafybeifm3xas2egfbwzo7cg5wiayw44sbvfn6h5am2bydp2zpnypl7g5tq/images/"
@@ -105,6 +117,8 @@ mod tests {
                     .to_string(),
                 launchpad_fee: 100,
                 launchpad_collector: Some(LAUNCHPAD_COLLECTOR.to_string()),
+                admin: None,
+                nois_proxy: None,
             };
 
             // instantiate launchpad contract
@@ -137,6 +151,10 @@ mod tests {
                 symbol: COLLECTION_SYMBOL.to_string(),
                 royalty_percentage: None,
                 royalty_payment_address: None,
+                placeholder_uri: None,
+                provenance_hash: None,
+                token_id_mode: TokenIdMode::Random,
+                reserved_supply: 0,
                 max_supply: 5000,
                 uri_prefix:
                     "ipfs://bafybeifm3xas2egfbwzo7cg5wiayw44sbvfn6h5am2bydp2zpnypl7g5tq/images/"
@@ -148,6 +166,8 @@ mod tests {
                 .to_string(),
             launchpad_fee: 10,
             launchpad_collector: Some(LAUNCHPAD_COLLECTOR.to_string()),
+            admin: None,
+            nois_proxy: None,
         };
 
         // instantiate launchpad contract
@@ -179,6 +199,10 @@ mod tests {
                 symbol: COLLECTION_SYMBOL.to_string(),
                 royalty_percentage: None,
                 royalty_payment_address: None,
+                placeholder_uri: None,
+                provenance_hash: None,
+                token_id_mode: TokenIdMode::Random,
+                reserved_supply: 0,
                 max_supply: max_cap,
                 uri_prefix:
                     "ipfs://bafybeifm3xas2egfbwzo7cg5wiayw44sbvfn6h5am2bydp2zpnypl7g5tq/images/"
@@ -191,6 +215,8 @@ mod tests {
 
             launchpad_fee: 0,
             launchpad_collector: Some(LAUNCHPAD_COLLECTOR.to_string()),
+            admin: None,
+            nois_proxy: None,
         };
 
         // instantiate launchpad contract
@@ -214,7 +240,7 @@ mod tests {
 
         use crate::{
             msg::MintableResponse,
-            state::{PhaseConfigResponse, PhaseData},
+            state::{PhaseConfigResponse, PhaseData, PriceType},
             testing_config::env::{NATIVE_DENOM, USER_1, USER_2},
         };
 
@@ -235,6 +261,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -264,6 +291,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -296,6 +324,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -336,6 +365,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -380,6 +410,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -403,6 +434,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -446,6 +478,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -469,6 +502,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -501,6 +535,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -524,6 +559,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -555,6 +591,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -586,6 +623,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -618,6 +656,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -641,6 +680,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -683,6 +723,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -715,6 +756,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -747,6 +789,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -769,6 +812,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -812,6 +856,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -834,6 +879,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -866,6 +912,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -888,6 +935,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -920,6 +968,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -942,6 +991,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -974,6 +1024,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -997,6 +1048,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1064,6 +1116,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1087,6 +1140,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1153,6 +1207,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1240,6 +1295,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1287,6 +1343,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1344,6 +1401,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1367,6 +1425,7 @@ mod tests {
                     max_nfts_per_address: 1,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: true,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1457,6 +1516,8 @@ mod tests {
             let mint_nft_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint nft msg will fail because phase is not active
@@ -1594,6 +1655,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1617,6 +1679,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1699,6 +1762,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1771,6 +1835,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1833,6 +1898,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1856,6 +1922,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1890,6 +1957,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -1930,6 +1998,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint msg
@@ -1963,6 +2033,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -2032,6 +2103,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint msg
@@ -2079,6 +2152,7 @@ mod tests {
                     max_nfts_per_address: 2,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -2148,6 +2222,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint msg
@@ -2195,6 +2271,7 @@ mod tests {
                     max_nfts_per_address: 1,
                     price: coin(500000, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -2264,6 +2341,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint msg
@@ -2311,6 +2390,7 @@ mod tests {
                     max_nfts_per_address: 10,
                     price: coin(500, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -2385,6 +2465,8 @@ mod tests {
                 let mint_msg = ExecuteMsg::Mint {
                     phase_id: 1,
                     amount: Option::from(1),
+                    merkle_proof: None,
+                    voucher: None,
                 };
 
                 // execute mint msg
@@ -2415,6 +2497,8 @@ mod tests {
                 let mint_msg = ExecuteMsg::Mint {
                     phase_id: 1,
                     amount: Option::from(1),
+                    merkle_proof: None,
+                    voucher: None,
                 };
 
                 // execute mint msg
@@ -2464,6 +2548,7 @@ mod tests {
                     max_nfts_per_address: 50,
                     price: coin(50, NATIVE_DENOM),
                     is_public: false,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -2536,6 +2621,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
             // let's USER1 and ADMIN mint 80 nfts
             for i in 0..80 {
@@ -2589,6 +2676,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(11),
+                merkle_proof: None,
+                voucher: None,
             };
             // execute mint msg
             let res = app.execute_contract(
@@ -2615,6 +2704,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(10),
+                merkle_proof: None,
+                voucher: None,
             };
             // execute mint msg
             let res = app.execute_contract(
@@ -2649,6 +2740,7 @@ mod tests {
                     max_nfts_per_address: 1,
                     price: coin(50, NATIVE_DENOM),
                     is_public: true,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -2702,6 +2794,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint msg
@@ -2729,6 +2823,152 @@ mod tests {
             assert!(res.is_ok());
         }
 
+        #[test]
+        fn user_is_refunded_when_overpaying_mint() {
+            // get integration test app and launchpad address
+            let (mut app, launchpad_address) = create_launchpad();
+
+            // ADD FIRST PHASE to the first position
+            let add_first_phase_msg = ExecuteMsg::AddMintPhase {
+                after_phase_id: None,
+                phase_data: PhaseData {
+                    start_time: app.block_info().time.plus_seconds(200),
+                    end_time: app.block_info().time.plus_seconds(1610),
+                    max_supply: Some(100),
+                    max_nfts_per_address: 3,
+                    price: coin(50, NATIVE_DENOM),
+                    is_public: true,
+                    price_type: PriceType::Native,
+                },
+            };
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(launchpad_address.clone()),
+                &add_first_phase_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // change block time increase 400 seconds to make phase active
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(400),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Mint native token and another token to USER_1
+            app.sudo(cw_multi_test::SudoMsg::Bank(
+                cw_multi_test::BankSudo::Mint {
+                    to_address: USER_1.to_string(),
+                    amount: vec![coin(1000, NATIVE_DENOM), coin(1000, "uother")],
+                },
+            ))
+            .unwrap();
+
+            // admin active the launchpad
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(launchpad_address.clone()),
+                &ExecuteMsg::ActivateLaunchpad {},
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // USER_1 mints 2 nfts and pays more than the price with an unrelated coin
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(launchpad_address.clone()),
+                &ExecuteMsg::Mint {
+                    phase_id: 1,
+                    amount: Some(2),
+                    merkle_proof: None,
+                    voucher: None,
+                },
+                &[coin(300, NATIVE_DENOM), coin(200, "uother")],
+            );
+            assert!(res.is_ok());
+
+            // USER_1 only paid the price of 2 nfts
+            let user_balances = app.wrap().query_all_balances(USER_1).unwrap();
+            assert_eq!(
+                user_balances,
+                vec![coin(900, NATIVE_DENOM), coin(1000, "uother")]
+            );
+
+            // the launchpad only keeps the price of the minted nfts
+            let launchpad_balances = app
+                .wrap()
+                .query_all_balances(launchpad_address.clone())
+                .unwrap();
+            assert_eq!(launchpad_balances, vec![coin(100, NATIVE_DENOM)]);
+
+            // the funds are still checked
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(launchpad_address),
+                &ExecuteMsg::Mint {
+                    phase_id: 1,
+                    amount: Some(1),
+                    merkle_proof: None,
+                    voucher: None,
+                },
+                &[coin(49, NATIVE_DENOM), coin(200, "uother")],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Not enough funds"
+            );
+        }
+
+        #[test]
+        fn cannot_mint_in_unknown_phase_or_with_overflowing_price() {
+            // get integration test app and launchpad address
+            let (mut app, launchpad_address) = create_launchpad();
+
+            let phase_data = PhaseData {
+                price: coin(u128::MAX, NATIVE_DENOM),
+                ..phase_data(&app)
+            };
+            add_phase_and_activate(&mut app, &launchpad_address, phase_data, &[]);
+            start_phase(&mut app);
+
+            // the phase does not exist
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::Mint {
+                    phase_id: 2,
+                    amount: Some(1),
+                    merkle_proof: None,
+                    voucher: None,
+                },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid phase id"
+            );
+
+            // the price of 2 nfts overflows
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address,
+                &ExecuteMsg::Mint {
+                    phase_id: 1,
+                    amount: Some(2),
+                    merkle_proof: None,
+                    voucher: None,
+                },
+                &[],
+            );
+            assert!(res
+                .unwrap_err()
+                .source()
+                .unwrap()
+                .to_string()
+                .contains("Overflow"));
+        }
+
         #[test]
         fn cannot_mint_nft_in_public_phase_because_user_minted_too_much() {
             // get integration test app and launchpad address
@@ -2745,6 +2985,7 @@ mod tests {
                     max_nfts_per_address: 1,
                     price: coin(50, NATIVE_DENOM),
                     is_public: true,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -2798,6 +3039,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint msg
@@ -2949,6 +3192,7 @@ mod tests {
                     max_nfts_per_address: 1,
                     price: coin(50, NATIVE_DENOM),
                     is_public: true,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -2993,6 +3237,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(1),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint msg
@@ -3048,14 +3294,14 @@ mod tests {
         use cosmwasm_std::{coin, BlockInfo, Coin, Uint128};
 
         use crate::{
-            state::{PhaseConfigResponse, PhaseData},
+            state::{PhaseConfigResponse, PhaseData, PriceType},
             testing_config::env::{NATIVE_DENOM, USER_1},
         };
 
         use super::*;
 
         #[test]
-        fn only_creator_can_withdraw_nfts_profit() {
+        fn anyone_can_withdraw_nfts_profit_after_last_phase() {
             // get integration test app and launchpad address
             let (mut app, launchpad_address) = create_launchpad();
 
@@ -3070,6 +3316,7 @@ mod tests {
                     max_nfts_per_address: 50,
                     price: coin(100, NATIVE_DENOM),
                     is_public: true,
+                    price_type: PriceType::Native,
                 },
             };
 
@@ -3138,6 +3385,8 @@ mod tests {
             let mint_msg = ExecuteMsg::Mint {
                 phase_id: 1,
                 amount: Option::from(10),
+                merkle_proof: None,
+                voucher: None,
             };
 
             // execute mint msg
@@ -3161,7 +3410,7 @@ mod tests {
                 denom: NATIVE_DENOM.to_string(),
             };
 
-            // ADMIN execute withdraw nft profit msg
+            // ADMIN cannot execute withdraw nft profit msg before the last phase is ended
             let res = app.execute_contract(
                 Addr::unchecked(ADMIN),
                 Addr::unchecked(launchpad_address.clone()),
@@ -3170,7 +3419,7 @@ mod tests {
             );
             assert_eq!(
                 res.unwrap_err().source().unwrap().to_string(),
-                "Unauthorized"
+                "Last phase not finished"
             );
 
             // change block time increase 1209 seconds to make phase almost ended
@@ -3199,9 +3448,9 @@ mod tests {
                 chain_id: app.block_info().chain_id,
             });
 
-            // execute withdraw nft profit msg
+            // USER_1 executes withdraw nft profit msg, the profit is sent to the CREATOR
             let res = app.execute_contract(
-                Addr::unchecked(CREATOR),
+                Addr::unchecked(USER_1),
                 Addr::unchecked(launchpad_address),
                 &withdraw_nft_profit_msg,
                 &[],
@@ -3223,4 +3472,1981 @@ mod tests {
             assert_eq!(launchpad_balance.amount, Uint128::from(100u128));
         }
     }
+
+    mod merkle_whitelist {
+        use cosmwasm_std::coin;
+        use sha2::{Digest, Sha256};
+
+        use crate::{
+            msg::{MerkleProof, MintableResponse},
+            state::{PhaseConfigResponse, PhaseData, PriceType},
+            testing_config::env::{NATIVE_DENOM, USER_1, USER_2},
+        };
+
+        use super::*;
+
+        fn leaf_hash(leaf: &str) -> [u8; 32] {
+            Sha256::digest(leaf.as_bytes()).into()
+        }
+
+        fn pair_hash(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+            let mut pair = [a, b];
+            pair.sort_unstable();
+            Sha256::digest(pair.concat()).into()
+        }
+
+        // the merkle tree of the whitelist, USER_1 can mint 3 nfts and ADMIN can mint max_nfts_per_address
+        // returns the root and the proofs of USER_1 and ADMIN
+        fn merkle_tree() -> (String, Vec<String>, Vec<String>) {
+            let leaves = [
+                leaf_hash(&format!("{}:3", USER_1)),
+                leaf_hash(ADMIN),
+                leaf_hash(CREATOR),
+                leaf_hash(LAUNCHPAD_COLLECTOR),
+            ];
+            let left = pair_hash(leaves[0], leaves[1]);
+            let right = pair_hash(leaves[2], leaves[3]);
+            let root = pair_hash(left, right);
+
+            (
+                hex::encode(root),
+                vec![hex::encode(leaves[1]), hex::encode(right)],
+                vec![hex::encode(leaves[0]), hex::encode(right)],
+            )
+        }
+
+        // create a launchpad with a private phase whose whitelist is the merkle tree
+        fn create_merkle_launchpad() -> (App, Addr) {
+            let (mut app, launchpad_address) = create_launchpad();
+
+            let phase_data = PhaseData {
+                max_nfts_per_address: 1,
+                is_public: false,
+                ..phase_data(&app)
+            };
+            let (merkle_root, _, _) = merkle_tree();
+            add_phase_and_activate(
+                &mut app,
+                &launchpad_address,
+                phase_data,
+                &[ExecuteMsg::SetMerkleRoot {
+                    phase_id: 1,
+                    merkle_root: Some(merkle_root),
+                }],
+            );
+            fund_user(&mut app, USER_1);
+            fund_user(&mut app, USER_2);
+            start_phase(&mut app);
+
+            (app, launchpad_address)
+        }
+
+        #[test]
+        fn admin_can_set_merkle_root() {
+            let (app, launchpad_address) = create_merkle_launchpad();
+            let (merkle_root, _, _) = merkle_tree();
+
+            let phase_configs: Vec<PhaseConfigResponse> = app
+                .wrap()
+                .query_wasm_smart(launchpad_address, &QueryMsg::GetAllPhaseConfigs {})
+                .unwrap();
+            assert_eq!(phase_configs[0].merkle_root, Some(merkle_root));
+        }
+
+        #[test]
+        fn cannot_set_invalid_merkle_root() {
+            let (mut app, launchpad_address) = create_launchpad();
+
+            let add_phase_msg = ExecuteMsg::AddMintPhase {
+                after_phase_id: None,
+                phase_data: PhaseData {
+                    start_time: app.block_info().time.plus_seconds(200),
+                    end_time: app.block_info().time.plus_seconds(1000),
+                    max_supply: Some(100),
+                    max_nfts_per_address: 1,
+                    price: coin(PRICE, NATIVE_DENOM),
+                    is_public: false,
+                    price_type: PriceType::Native,
+                },
+            };
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &add_phase_msg,
+                &[],
+            )
+            .unwrap();
+
+            // only the admin can set the merkle root
+            let (merkle_root, _, _) = merkle_tree();
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::SetMerkleRoot {
+                    phase_id: 1,
+                    merkle_root: Some(merkle_root),
+                },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address,
+                &ExecuteMsg::SetMerkleRoot {
+                    phase_id: 1,
+                    merkle_root: Some("not a hash".to_string()),
+                },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid merkle root"
+            );
+        }
+
+        #[test]
+        fn user_can_mint_with_merkle_proof() {
+            let (mut app, launchpad_address) = create_merkle_launchpad();
+            let (_, user_1_proof, admin_proof) = merkle_tree();
+
+            // USER_1 can mint the 3 nfts of its allowance
+            mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                2,
+                Some(MerkleProof {
+                    allowance: Some(3),
+                    proof: user_1_proof.clone(),
+                }),
+                None,
+            )
+            .unwrap();
+            mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                1,
+                Some(MerkleProof {
+                    allowance: Some(3),
+                    proof: user_1_proof.clone(),
+                }),
+                None,
+            )
+            .unwrap();
+
+            // the minted nfts are still counted per phase and address
+            let res = mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                1,
+                Some(MerkleProof {
+                    allowance: Some(3),
+                    proof: user_1_proof.clone(),
+                }),
+                None,
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "User minted too much nfts"
+            );
+
+            // USER_1 cannot claim a larger allowance
+            let res = mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                1,
+                Some(MerkleProof {
+                    allowance: Some(10),
+                    proof: user_1_proof,
+                }),
+                None,
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid merkle proof"
+            );
+
+            // ADMIN can mint max_nfts_per_address
+            fund_user(&mut app, ADMIN);
+            let res = mint(
+                &mut app,
+                &launchpad_address,
+                ADMIN,
+                2,
+                Some(MerkleProof {
+                    allowance: None,
+                    proof: admin_proof.clone(),
+                }),
+                None,
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "User minted too much nfts"
+            );
+            mint(
+                &mut app,
+                &launchpad_address,
+                ADMIN,
+                1,
+                Some(MerkleProof {
+                    allowance: None,
+                    proof: admin_proof,
+                }),
+                None,
+            )
+            .unwrap();
+
+            let res: Vec<MintableResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    launchpad_address,
+                    &QueryMsg::Mintable {
+                        user: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res,
+                vec![MintableResponse {
+                    phase_id: 1,
+                    remaining_nfts: 0
+                }]
+            );
+        }
+
+        #[test]
+        fn cannot_mint_without_valid_merkle_proof() {
+            let (mut app, launchpad_address) = create_merkle_launchpad();
+            let (_, user_1_proof, _) = merkle_tree();
+
+            // a proof is required
+            let res = mint(&mut app, &launchpad_address, USER_1, 1, None, None);
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid merkle proof"
+            );
+
+            // USER_2 is not a leaf of the merkle tree
+            let res = mint(
+                &mut app,
+                &launchpad_address,
+                USER_2,
+                1,
+                Some(MerkleProof {
+                    allowance: Some(3),
+                    proof: user_1_proof,
+                }),
+                None,
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid merkle proof"
+            );
+        }
+    }
+
+    mod mint_voucher {
+        use cosmwasm_std::{coin, Binary, BlockInfo, Timestamp};
+        use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+        use crate::{
+            msg::MintVoucher,
+            state::{PhaseConfigResponse, PhaseData, PriceType},
+            testing_config::env::{NATIVE_DENOM, USER_1, USER_2},
+        };
+
+        use super::*;
+
+        const SIGNER_KEY: [u8; 32] = [7u8; 32];
+
+        fn signing_key() -> SigningKey {
+            SigningKey::from_bytes(&SIGNER_KEY).unwrap()
+        }
+
+        fn voucher_signer() -> Binary {
+            Binary::from(signing_key().verifying_key().to_bytes().as_slice())
+        }
+
+        // sign the voucher of the minter with the key of the voucher signer
+        fn sign_voucher(
+            signing_key: &SigningKey,
+            launchpad_address: &Addr,
+            minter: &str,
+            max_amount: u64,
+            expiry: Timestamp,
+        ) -> MintVoucher {
+            let message = format!(
+                "{}:{}:{}:{}:{}",
+                launchpad_address,
+                1,
+                minter,
+                max_amount,
+                expiry.nanos()
+            );
+            let signature: Signature = signing_key.sign(message.as_bytes());
+
+            MintVoucher {
+                max_amount,
+                expiry,
+                signature: Binary::from(signature.as_ref()),
+            }
+        }
+
+        // create a launchpad with a private phase whose whitelist is given by vouchers
+        fn create_voucher_launchpad() -> (App, Addr) {
+            let (mut app, launchpad_address) = create_launchpad();
+
+            let phase_data = PhaseData {
+                max_nfts_per_address: 1,
+                is_public: false,
+                ..phase_data(&app)
+            };
+            add_phase_and_activate(
+                &mut app,
+                &launchpad_address,
+                phase_data,
+                &[ExecuteMsg::SetVoucherSigner {
+                    phase_id: 1,
+                    voucher_signer: Some(voucher_signer()),
+                }],
+            );
+            fund_user(&mut app, USER_1);
+            fund_user(&mut app, USER_2);
+            start_phase(&mut app);
+
+            (app, launchpad_address)
+        }
+
+        #[test]
+        fn admin_can_set_voucher_signer() {
+            let (mut app, launchpad_address) = create_launchpad();
+
+            let add_phase_msg = ExecuteMsg::AddMintPhase {
+                after_phase_id: None,
+                phase_data: PhaseData {
+                    start_time: app.block_info().time.plus_seconds(200),
+                    end_time: app.block_info().time.plus_seconds(1000),
+                    max_supply: Some(100),
+                    max_nfts_per_address: 1,
+                    price: coin(PRICE, NATIVE_DENOM),
+                    is_public: false,
+                    price_type: PriceType::Native,
+                },
+            };
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &add_phase_msg,
+                &[],
+            )
+            .unwrap();
+
+            // only the admin can set the voucher signer
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::SetVoucherSigner {
+                    phase_id: 1,
+                    voucher_signer: Some(voucher_signer()),
+                },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+
+            // the voucher signer must be a public key
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &ExecuteMsg::SetVoucherSigner {
+                    phase_id: 1,
+                    voucher_signer: Some(Binary::from(b"not a public key")),
+                },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid voucher signer"
+            );
+
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &ExecuteMsg::SetVoucherSigner {
+                    phase_id: 1,
+                    voucher_signer: Some(voucher_signer()),
+                },
+                &[],
+            )
+            .unwrap();
+
+            let phase_configs: Vec<PhaseConfigResponse> = app
+                .wrap()
+                .query_wasm_smart(launchpad_address, &QueryMsg::GetAllPhaseConfigs {})
+                .unwrap();
+            assert_eq!(phase_configs[0].voucher_signer, Some(voucher_signer()));
+        }
+
+        #[test]
+        fn user_can_mint_up_to_voucher_max_amount() {
+            let (mut app, launchpad_address) = create_voucher_launchpad();
+            let expiry = app.block_info().time.plus_seconds(100);
+            let voucher = sign_voucher(&signing_key(), &launchpad_address, USER_1, 3, expiry);
+
+            // the voucher replaces max_nfts_per_address
+            mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                2,
+                None,
+                Some(voucher.clone()),
+            )
+            .unwrap();
+            mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                1,
+                None,
+                Some(voucher.clone()),
+            )
+            .unwrap();
+
+            // the voucher is used up
+            let res = mint(&mut app, &launchpad_address, USER_1, 1, None, Some(voucher));
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "User minted too much nfts"
+            );
+
+            let launchpad_info: LaunchpadInfo = app
+                .wrap()
+                .query_wasm_smart(launchpad_address, &QueryMsg::GetLaunchpadInfo {})
+                .unwrap();
+            assert_eq!(launchpad_info.total_supply, 3);
+        }
+
+        #[test]
+        fn cancelled_pending_mint_releases_the_voucher() {
+            // create a launchpad using the randomness of the mock nois proxy
+            let (mut app, contracts) = instantiate_contracts();
+            let nois_proxy_address =
+                instantiate_nois_proxy(&mut app, contracts[3].contract_code_id);
+            let instantiate_msg = InstantiateMsg {
+                nois_proxy: Some(nois_proxy_address.to_string()),
+                ..launchpad_instantiate_msg(contracts[0].contract_code_id)
+            };
+            let launchpad_address =
+                instantiate_launchpad(&mut app, contracts[1].contract_code_id, &instantiate_msg);
+
+            // the phase lasts longer than the timeout of the pending mints
+            let phase_data = PhaseData {
+                end_time: app.block_info().time.plus_seconds(10000),
+                is_public: false,
+                ..phase_data(&app)
+            };
+            add_phase_and_activate(
+                &mut app,
+                &launchpad_address,
+                phase_data,
+                &[ExecuteMsg::SetVoucherSigner {
+                    phase_id: 1,
+                    voucher_signer: Some(voucher_signer()),
+                }],
+            );
+            fund_user(&mut app, USER_1);
+            start_phase(&mut app);
+
+            // USER_1 uses the whole voucher in a pending mint
+            let expiry = app.block_info().time.plus_seconds(5000);
+            let voucher = sign_voucher(&signing_key(), &launchpad_address, USER_1, 2, expiry);
+            mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                2,
+                None,
+                Some(voucher.clone()),
+            )
+            .unwrap();
+            let res = mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                1,
+                None,
+                Some(voucher.clone()),
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "User minted too much nfts"
+            );
+
+            // the pending mint is cancelled after the timeout
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(3600),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::CancelPendingMint {
+                    job_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            // the voucher can be used again
+            mint(&mut app, &launchpad_address, USER_1, 2, None, Some(voucher)).unwrap();
+            assert_eq!(
+                query_launchpad_info(&app, &launchpad_address).pending_supply,
+                2
+            );
+        }
+
+        #[test]
+        fn cannot_mint_with_invalid_voucher() {
+            let (mut app, launchpad_address) = create_voucher_launchpad();
+            let expiry = app.block_info().time.plus_seconds(100);
+
+            // a voucher is required
+            let res = mint(&mut app, &launchpad_address, USER_1, 1, None, None);
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid voucher"
+            );
+
+            // the voucher of USER_1 cannot be used by USER_2
+            let voucher = sign_voucher(&signing_key(), &launchpad_address, USER_1, 3, expiry);
+            let res = mint(
+                &mut app,
+                &launchpad_address,
+                USER_2,
+                1,
+                None,
+                Some(voucher.clone()),
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid voucher"
+            );
+
+            // the max_amount of the voucher cannot be changed
+            let res = mint(
+                &mut app,
+                &launchpad_address,
+                USER_1,
+                1,
+                None,
+                Some(MintVoucher {
+                    max_amount: 10,
+                    ..voucher
+                }),
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid voucher"
+            );
+
+            // the voucher must be signed by the voucher signer
+            let other_key = SigningKey::from_bytes(&[9u8; 32]).unwrap();
+            let voucher = sign_voucher(&other_key, &launchpad_address, USER_1, 3, expiry);
+            let res = mint(&mut app, &launchpad_address, USER_1, 1, None, Some(voucher));
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid voucher"
+            );
+
+            // the voucher cannot be used after its expiry
+            let voucher = sign_voucher(&signing_key(), &launchpad_address, USER_1, 3, expiry);
+            app.set_block(BlockInfo {
+                time: expiry.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            let res = mint(&mut app, &launchpad_address, USER_1, 1, None, Some(voucher));
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Voucher expired"
+            );
+        }
+    }
+
+    mod cw20_price {
+        use cosmwasm_std::{coin, to_binary, BlockInfo, Uint128};
+        use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+        use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+
+        use crate::{
+            msg::ReceiveMsg,
+            state::{PhaseConfigResponse, PhaseData, PriceType},
+            testing_config::env::{NATIVE_DENOM, USER_1, USER_2},
+        };
+
+        use super::*;
+
+        // create a launchpad and a cw20 token, USER_1 owns 1000 tokens
+        fn create_launchpad_with_cw20_token() -> (App, Addr, Addr) {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let cw2981_code_id = contracts[0].contract_code_id;
+            let launchpad_code_id = contracts[1].contract_code_id;
+            let cw20_code_id = contracts[2].contract_code_id;
+
+            // instantiate launchpad contract
+            let launchpad_address = instantiate_launchpad(
+                &mut app,
+                launchpad_code_id,
+                &launchpad_instantiate_msg(cw2981_code_id),
+            );
+
+            // instantiate cw20 contract
+            let cw20_address = app
+                .instantiate_contract(
+                    cw20_code_id,
+                    Addr::unchecked(ADMIN),
+                    &Cw20InstantiateMsg {
+                        name: "Community Token".to_string(),
+                        symbol: "CMT".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: USER_1.to_string(),
+                            amount: Uint128::from(1000u128),
+                        }],
+                        mint: None,
+                        marketing: None,
+                    },
+                    &[],
+                    "test instantiate cw20 contract",
+                    None,
+                )
+                .unwrap();
+
+            (app, launchpad_address, cw20_address)
+        }
+
+        // add a public phase priced in the cw20 token and activate the launchpad
+        fn add_cw20_phase(app: &mut App, launchpad_address: &Addr, cw20_address: &Addr) {
+            let phase_data = PhaseData {
+                price: coin(PRICE, cw20_address.as_str()),
+                price_type: PriceType::Cw20,
+                ..phase_data(app)
+            };
+            add_phase_and_activate(app, launchpad_address, phase_data, &[]);
+            start_phase(app);
+        }
+
+        fn mint_with_cw20(
+            app: &mut App,
+            launchpad_address: &Addr,
+            cw20_address: &Addr,
+            amount_nfts: u64,
+            amount_tokens: u128,
+        ) -> anyhow::Result<cw_multi_test::AppResponse> {
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                cw20_address.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: launchpad_address.to_string(),
+                    amount: Uint128::from(amount_tokens),
+                    msg: to_binary(&ReceiveMsg::Mint {
+                        phase_id: 1,
+                        amount: Some(amount_nfts),
+                        merkle_proof: None,
+                        voucher: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            )
+        }
+
+        fn cw20_balance(app: &App, cw20_address: &Addr, address: &str) -> Uint128 {
+            let res: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw20_address,
+                    &Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        #[test]
+        fn user_can_mint_with_cw20_token() {
+            let (mut app, launchpad_address, cw20_address) = create_launchpad_with_cw20_token();
+            add_cw20_phase(&mut app, &launchpad_address, &cw20_address);
+
+            let phase_configs: Vec<PhaseConfigResponse> = app
+                .wrap()
+                .query_wasm_smart(launchpad_address.clone(), &QueryMsg::GetAllPhaseConfigs {})
+                .unwrap();
+            assert_eq!(phase_configs[0].price_type, PriceType::Cw20);
+
+            // USER_1 mints 2 nfts and sends more than the price, the surplus is refunded
+            mint_with_cw20(&mut app, &launchpad_address, &cw20_address, 2, 250).unwrap();
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, USER_1),
+                Uint128::from(800u128)
+            );
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, launchpad_address.as_str()),
+                Uint128::from(2 * PRICE)
+            );
+
+            let launchpad_info: LaunchpadInfo = app
+                .wrap()
+                .query_wasm_smart(launchpad_address.clone(), &QueryMsg::GetLaunchpadInfo {})
+                .unwrap();
+            assert_eq!(launchpad_info.total_supply, 2);
+
+            // the funds are still checked
+            let res = mint_with_cw20(&mut app, &launchpad_address, &cw20_address, 1, 99);
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                "Not enough funds"
+            );
+
+            // the phase cannot be paid with native coins
+            app.sudo(cw_multi_test::SudoMsg::Bank(
+                cw_multi_test::BankSudo::Mint {
+                    to_address: USER_1.to_string(),
+                    amount: vec![coin(1000, NATIVE_DENOM)],
+                },
+            ))
+            .unwrap();
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::Mint {
+                    phase_id: 1,
+                    amount: Some(1),
+                    merkle_proof: None,
+                    voucher: None,
+                },
+                &[coin(PRICE, NATIVE_DENOM)],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid payment token"
+            );
+
+            // CREATOR withdraws the cw20 tokens after the last phase is finished
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(600),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            app.execute_contract(
+                Addr::unchecked(CREATOR),
+                launchpad_address.clone(),
+                &ExecuteMsg::Withdraw {
+                    denom: cw20_address.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            // the CREATOR should have (100 * 2) * 90% tokens and the LAUNCHPAD_COLLECTOR (100 * 2) * 10%
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, CREATOR),
+                Uint128::from(180u128)
+            );
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, LAUNCHPAD_COLLECTOR),
+                Uint128::from(20u128)
+            );
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, launchpad_address.as_str()),
+                Uint128::zero()
+            );
+        }
+
+        #[test]
+        fn cw20_revenue_is_split_between_payees() {
+            let (mut app, launchpad_address, cw20_address) = create_launchpad_with_cw20_token();
+
+            // CREATOR shares the revenue with USER_2 before the launchpad starts
+            app.execute_contract(
+                Addr::unchecked(CREATOR),
+                launchpad_address.clone(),
+                &ExecuteMsg::SetPayees {
+                    payees: vec![(CREATOR.to_string(), 33), (USER_2.to_string(), 67)],
+                },
+                &[],
+            )
+            .unwrap();
+
+            add_cw20_phase(&mut app, &launchpad_address, &cw20_address);
+            mint_with_cw20(&mut app, &launchpad_address, &cw20_address, 3, 3 * PRICE).unwrap();
+
+            // USER_1 withdraws the cw20 tokens after the last phase is finished
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(600),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::Withdraw {
+                    denom: cw20_address.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            // the payees share (100 * 3) * 90% tokens, the last payee receives the rounding dust
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, CREATOR),
+                Uint128::from(89u128)
+            );
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, USER_2),
+                Uint128::from(181u128)
+            );
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, LAUNCHPAD_COLLECTOR),
+                Uint128::from(30u128)
+            );
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, launchpad_address.as_str()),
+                Uint128::zero()
+            );
+        }
+
+        #[test]
+        fn cw20_tokens_not_used_by_phases_can_be_withdrawn() {
+            let (mut app, launchpad_address, cw20_address) = create_launchpad_with_cw20_token();
+
+            // the only phase is priced in the native token
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &ExecuteMsg::AddMintPhase {
+                    after_phase_id: None,
+                    phase_data: PhaseData {
+                        start_time: app.block_info().time.plus_seconds(200),
+                        end_time: app.block_info().time.plus_seconds(1000),
+                        max_supply: Some(100),
+                        max_nfts_per_address: 5,
+                        price: coin(PRICE, NATIVE_DENOM),
+                        is_public: true,
+                        price_type: PriceType::Native,
+                    },
+                },
+                &[],
+            )
+            .unwrap();
+
+            // USER_1 sends cw20 tokens to the launchpad directly
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                cw20_address.clone(),
+                &Cw20ExecuteMsg::Transfer {
+                    recipient: launchpad_address.to_string(),
+                    amount: Uint128::from(PRICE),
+                },
+                &[],
+            )
+            .unwrap();
+
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1000),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::Withdraw {
+                    denom: cw20_address.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, CREATOR),
+                Uint128::from(90u128)
+            );
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, LAUNCHPAD_COLLECTOR),
+                Uint128::from(10u128)
+            );
+        }
+
+        #[test]
+        fn cannot_add_cw20_phase_with_invalid_token() {
+            let (mut app, launchpad_address, _) = create_launchpad_with_cw20_token();
+
+            let add_phase_msg = ExecuteMsg::AddMintPhase {
+                after_phase_id: None,
+                phase_data: PhaseData {
+                    start_time: app.block_info().time.plus_seconds(200),
+                    end_time: app.block_info().time.plus_seconds(1000),
+                    max_supply: Some(100),
+                    max_nfts_per_address: 5,
+                    price: coin(PRICE, "Not A Token"),
+                    is_public: true,
+                    price_type: PriceType::Cw20,
+                },
+            };
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address,
+                &add_phase_msg,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid payment token"
+            );
+        }
+
+        #[test]
+        fn native_phase_cannot_be_paid_with_cw20_token() {
+            let (mut app, launchpad_address, cw20_address) = create_launchpad_with_cw20_token();
+
+            let add_phase_msg = ExecuteMsg::AddMintPhase {
+                after_phase_id: None,
+                phase_data: PhaseData {
+                    start_time: app.block_info().time.plus_seconds(200),
+                    end_time: app.block_info().time.plus_seconds(1000),
+                    max_supply: Some(100),
+                    max_nfts_per_address: 5,
+                    price: coin(PRICE, NATIVE_DENOM),
+                    is_public: true,
+                    price_type: PriceType::Native,
+                },
+            };
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &add_phase_msg,
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &ExecuteMsg::ActivateLaunchpad {},
+                &[],
+            )
+            .unwrap();
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(400),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            let res = mint_with_cw20(&mut app, &launchpad_address, &cw20_address, 1, PRICE);
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                "Invalid payment token"
+            );
+            assert_eq!(
+                cw20_balance(&app, &cw20_address, USER_1),
+                Uint128::from(1000u128)
+            );
+        }
+    }
+
+    mod nois_randomness {
+        use cosmwasm_std::{coin, BlockInfo, HexBinary, Uint128};
+        use nois::NoisCallback;
+
+        use crate::testing_config::{
+            env::{NATIVE_DENOM, USER_1, USER_2},
+            mock_nois_proxy::ExecuteMsg as NoisProxyExecuteMsg,
+        };
+
+        use super::*;
+
+        const RANDOMNESS: &str = "aabbccddeeff00112233445566778899aabbccddeeff00112233445566778899";
+
+        // create a launchpad using the randomness of the mock nois proxy
+        fn create_launchpad_with_nois_proxy() -> (App, Addr, Addr) {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let cw2981_code_id = contracts[0].contract_code_id;
+            let launchpad_code_id = contracts[1].contract_code_id;
+            let nois_proxy_code_id = contracts[3].contract_code_id;
+
+            // instantiate mock nois proxy contract
+            let nois_proxy_address = instantiate_nois_proxy(&mut app, nois_proxy_code_id);
+
+            // instantiate launchpad contract
+            let instantiate_msg = InstantiateMsg {
+                nois_proxy: Some(nois_proxy_address.to_string()),
+                ..launchpad_instantiate_msg(cw2981_code_id)
+            };
+            let launchpad_address =
+                instantiate_launchpad(&mut app, launchpad_code_id, &instantiate_msg);
+
+            // add a public phase and activate the launchpad
+            let phase_data = phase_data(&app);
+            add_phase_and_activate(&mut app, &launchpad_address, phase_data, &[]);
+            fund_user(&mut app, USER_1);
+            start_phase(&mut app);
+
+            (app, launchpad_address, nois_proxy_address)
+        }
+
+        #[test]
+        fn pending_mint_is_settled_with_nois_randomness() {
+            let (mut app, launchpad_address, nois_proxy_address) =
+                create_launchpad_with_nois_proxy();
+
+            // USER_1 mints 2 nfts, the token_ids wait for the randomness
+            let res = mint(&mut app, &launchpad_address, USER_1, 2, None, None).unwrap();
+            assert!(res.events.iter().any(|event| event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "job_id" && attr.value == "1")));
+
+            let launchpad_info = query_launchpad_info(&app, &launchpad_address);
+            assert_eq!(launchpad_info.total_supply, 0);
+            assert_eq!(launchpad_info.pending_supply, 2);
+            assert!(query_tokens(&app, &launchpad_address, USER_1).is_empty());
+
+            // the nois proxy delivers the randomness of the job
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                nois_proxy_address,
+                &NoisProxyExecuteMsg::Deliver {
+                    job_id: "1".to_string(),
+                    randomness: HexBinary::from_hex(RANDOMNESS).unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            let launchpad_info = query_launchpad_info(&app, &launchpad_address);
+            assert_eq!(launchpad_info.total_supply, 2);
+            assert_eq!(launchpad_info.pending_supply, 0);
+            assert_eq!(query_tokens(&app, &launchpad_address, USER_1).len(), 2);
+        }
+
+        #[test]
+        fn only_nois_proxy_can_send_randomness() {
+            let (mut app, launchpad_address, nois_proxy_address) =
+                create_launchpad_with_nois_proxy();
+
+            mint(&mut app, &launchpad_address, USER_1, 1, None, None).unwrap();
+
+            let nois_receive_msg = |job_id: &str| ExecuteMsg::NoisReceive {
+                callback: NoisCallback {
+                    job_id: job_id.to_string(),
+                    randomness: HexBinary::from_hex(RANDOMNESS).unwrap(),
+                },
+            };
+
+            // USER_1 cannot choose the randomness of its mint
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &nois_receive_msg("1"),
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+
+            // the job must be pending
+            let res = app.execute_contract(
+                nois_proxy_address,
+                launchpad_address,
+                &nois_receive_msg("2"),
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unknown nois job 2"
+            );
+        }
+
+        #[test]
+        fn pending_mint_can_be_cancelled_after_timeout() {
+            let (mut app, launchpad_address, nois_proxy_address) =
+                create_launchpad_with_nois_proxy();
+            let balance_before = app.wrap().query_balance(USER_1, NATIVE_DENOM).unwrap();
+
+            mint(&mut app, &launchpad_address, USER_1, 2, None, None).unwrap();
+
+            let cancel_msg = ExecuteMsg::CancelPendingMint {
+                job_id: "1".to_string(),
+            };
+
+            // the nois proxy still has time to send the randomness
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &cancel_msg,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Pending mint of nois job 1 is not expired"
+            );
+
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(3600),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // only the admin or the minter can cancel the pending mint
+            let res = app.execute_contract(
+                Addr::unchecked(USER_2),
+                launchpad_address.clone(),
+                &cancel_msg,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &cancel_msg,
+                &[],
+            )
+            .unwrap();
+
+            // the price is refunded and the nfts are released
+            let balance_after = app.wrap().query_balance(USER_1, NATIVE_DENOM).unwrap();
+            assert_eq!(balance_after, balance_before);
+            let launchpad_info = query_launchpad_info(&app, &launchpad_address);
+            assert_eq!(launchpad_info.pending_supply, 0);
+            assert_eq!(launchpad_info.total_supply, 0);
+
+            // the randomness of the cancelled job cannot settle it anymore
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                nois_proxy_address,
+                &NoisProxyExecuteMsg::Deliver {
+                    job_id: "1".to_string(),
+                    randomness: HexBinary::from_hex(RANDOMNESS).unwrap(),
+                },
+                &[],
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn withdraw_keeps_the_price_of_pending_mints() {
+            let (mut app, launchpad_address, nois_proxy_address) =
+                create_launchpad_with_nois_proxy();
+
+            // USER_1 mints 1 nft settled by the nois proxy, then 2 nfts waiting for the randomness
+            mint(&mut app, &launchpad_address, USER_1, 1, None, None).unwrap();
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                nois_proxy_address,
+                &NoisProxyExecuteMsg::Deliver {
+                    job_id: "1".to_string(),
+                    randomness: HexBinary::from_hex(RANDOMNESS).unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+            mint(&mut app, &launchpad_address, USER_1, 2, None, None).unwrap();
+            let balance_before = app.wrap().query_balance(USER_1, NATIVE_DENOM).unwrap();
+
+            // the last phase is finished and the pending mint is expired
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(3600),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // only the price of the settled nft is withdrawn
+            app.execute_contract(
+                Addr::unchecked(CREATOR),
+                launchpad_address.clone(),
+                &ExecuteMsg::Withdraw {
+                    denom: NATIVE_DENOM.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                app.wrap().query_balance(CREATOR, NATIVE_DENOM).unwrap(),
+                coin(90, NATIVE_DENOM)
+            );
+            assert_eq!(
+                app.wrap()
+                    .query_balance(LAUNCHPAD_COLLECTOR, NATIVE_DENOM)
+                    .unwrap(),
+                coin(10, NATIVE_DENOM)
+            );
+
+            // the pending mint can still be refunded
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::CancelPendingMint {
+                    job_id: "2".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                app.wrap()
+                    .query_balance(USER_1, NATIVE_DENOM)
+                    .unwrap()
+                    .amount,
+                balance_before.amount + Uint128::from(2 * PRICE)
+            );
+            assert_eq!(
+                app.wrap()
+                    .query_balance(&launchpad_address, NATIVE_DENOM)
+                    .unwrap(),
+                coin(0, NATIVE_DENOM)
+            );
+        }
+
+        #[test]
+        fn anyone_can_withdraw_without_draining_pending_mints() {
+            let (mut app, launchpad_address, _) = create_launchpad_with_nois_proxy();
+
+            // the launchpad only holds the price of a pending mint
+            mint(&mut app, &launchpad_address, USER_1, 2, None, None).unwrap();
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(3600),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_2 triggers the withdraw, nothing is paid to the payees
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                launchpad_address.clone(),
+                &ExecuteMsg::Withdraw {
+                    denom: NATIVE_DENOM.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                app.wrap()
+                    .query_balance(&launchpad_address, NATIVE_DENOM)
+                    .unwrap(),
+                coin(2 * PRICE, NATIVE_DENOM)
+            );
+
+            // the pending mint can still be refunded
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::CancelPendingMint {
+                    job_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                app.wrap()
+                    .query_balance(&launchpad_address, NATIVE_DENOM)
+                    .unwrap(),
+                coin(0, NATIVE_DENOM)
+            );
+        }
+
+        #[test]
+        fn nois_fee_is_sent_with_randomness_request() {
+            let (mut app, launchpad_address, nois_proxy_address) =
+                create_launchpad_with_nois_proxy();
+            let nois_fee = coin(50, "unois");
+
+            let set_nois_fee_msg = ExecuteMsg::SetNoisFee {
+                nois_fee: vec![nois_fee.clone()],
+            };
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &set_nois_fee_msg,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &set_nois_fee_msg,
+                &[],
+            )
+            .unwrap();
+
+            // the minter must pay the fee of the nois proxy with the price
+            app.sudo(cw_multi_test::SudoMsg::Bank(
+                cw_multi_test::BankSudo::Mint {
+                    to_address: USER_1.to_string(),
+                    amount: vec![coin(100, "unois")],
+                },
+            ))
+            .unwrap();
+            let mint_msg = ExecuteMsg::Mint {
+                phase_id: 1,
+                amount: Some(1),
+                merkle_proof: None,
+                voucher: None,
+            };
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &mint_msg,
+                &[coin(PRICE, NATIVE_DENOM)],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Not enough funds"
+            );
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &mint_msg,
+                &[coin(PRICE, NATIVE_DENOM), coin(80, "unois")],
+            )
+            .unwrap();
+
+            // the nois proxy receives the fee, the surplus is refunded and the launchpad keeps the price
+            assert_eq!(
+                app.wrap()
+                    .query_balance(nois_proxy_address, "unois")
+                    .unwrap(),
+                nois_fee
+            );
+            assert_eq!(
+                app.wrap().query_balance(USER_1, "unois").unwrap(),
+                coin(50, "unois")
+            );
+            assert_eq!(
+                app.wrap().query_all_balances(&launchpad_address).unwrap(),
+                vec![coin(PRICE, NATIVE_DENOM)]
+            );
+        }
+    }
+
+    mod delayed_reveal {
+        use cosmwasm_std::StdResult;
+        use cw2981_royalties::{Metadata, QueryMsg as Cw2981QueryMsg};
+        use cw721::NftInfoResponse;
+
+        use crate::{msg::TokenUriResponse, testing_config::env::USER_1};
+
+        use super::*;
+
+        const PLACEHOLDER_URI: &str = "ipfs://placeholder/hidden.json";
+        // the sha256 hash of the revealed uri_prefix "ipfs://revealed/"
+        const PROVENANCE_HASH: &str =
+            "e025075e661b7130d163c7786f9d759b89d11e8cff684be659ca90e06639ee89";
+
+        fn instantiate_msg(cw2981_code_id: u64, provenance_hash: Option<String>) -> InstantiateMsg {
+            let mut instantiate_msg = launchpad_instantiate_msg(cw2981_code_id);
+            instantiate_msg.collection_info.placeholder_uri = Some(PLACEHOLDER_URI.to_string());
+            instantiate_msg.collection_info.provenance_hash = provenance_hash;
+            instantiate_msg.collection_info.uri_prefix = "".to_string();
+            instantiate_msg
+        }
+
+        // create a launchpad with a placeholder_uri, then USER_1 mints 1 nft
+        fn create_launchpad_and_mint() -> (App, Addr, Addr, String) {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // instantiate launchpad contract
+            let launchpad_address = instantiate_launchpad(
+                &mut app,
+                contracts[1].contract_code_id,
+                &instantiate_msg(
+                    contracts[0].contract_code_id,
+                    Some(PROVENANCE_HASH.to_string()),
+                ),
+            );
+
+            // add a public phase and activate the launchpad
+            let phase_data = phase_data(&app);
+            add_phase_and_activate(&mut app, &launchpad_address, phase_data, &[]);
+            fund_user(&mut app, USER_1);
+            start_phase(&mut app);
+
+            mint(&mut app, &launchpad_address, USER_1, 1, None, None).unwrap();
+
+            let launchpad_info = query_launchpad_info(&app, &launchpad_address);
+            let token_id = query_tokens(&app, &launchpad_address, USER_1)[0].clone();
+
+            (
+                app,
+                launchpad_address,
+                launchpad_info.collection_address,
+                token_id,
+            )
+        }
+
+        fn query_token_uri(app: &App, launchpad_address: &Addr, token_id: &str) -> String {
+            let res: TokenUriResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    launchpad_address,
+                    &QueryMsg::TokenUri {
+                        token_id: token_id.to_string(),
+                    },
+                )
+                .unwrap();
+            res.token_uri
+        }
+
+        #[test]
+        fn admin_can_reveal_token_uris() {
+            let (mut app, launchpad_address, collection_address, token_id) =
+                create_launchpad_and_mint();
+
+            // the nft is minted with the placeholder_uri
+            let nft_info: NftInfoResponse<Option<Metadata>> = app
+                .wrap()
+                .query_wasm_smart(
+                    collection_address,
+                    &Cw2981QueryMsg::NftInfo {
+                        token_id: token_id.clone(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(nft_info.token_uri, Some(PLACEHOLDER_URI.to_string()));
+            assert_eq!(
+                query_token_uri(&app, &launchpad_address, &token_id),
+                PLACEHOLDER_URI
+            );
+
+            let reveal_msg = ExecuteMsg::Reveal {
+                uri_prefix: "ipfs://revealed/".to_string(),
+            };
+
+            // only the admin can reveal
+            let res = app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &reveal_msg,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+
+            // the uri_prefix must match the committed provenance hash
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &ExecuteMsg::Reveal {
+                    uri_prefix: "ipfs://other/".to_string(),
+                },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid provenance hash"
+            );
+
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &reveal_msg,
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                query_token_uri(&app, &launchpad_address, &token_id),
+                format!("ipfs://revealed/{}.json", token_id)
+            );
+
+            // the launchpad can only be revealed once
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &reveal_msg,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Launchpad is already revealed"
+            );
+
+            // the token_uri of a nft not minted cannot be queried
+            let res: StdResult<TokenUriResponse> = app.wrap().query_wasm_smart(
+                launchpad_address,
+                &QueryMsg::TokenUri {
+                    token_id: "unknown".to_string(),
+                },
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn cannot_reveal_without_placeholder_uri() {
+            let (mut app, launchpad_address) = create_launchpad();
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address,
+                &ExecuteMsg::Reveal {
+                    uri_prefix: "ipfs://revealed/".to_string(),
+                },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Launchpad has no delayed reveal"
+            );
+        }
+
+        #[test]
+        fn cannot_instantiate_placeholder_uri_without_valid_provenance_hash() {
+            let (mut app, contracts) = instantiate_contracts();
+
+            // the provenance hash must be a hex encoded sha256 hash
+            for provenance_hash in [None, Some("00".to_string())] {
+                let res = app.instantiate_contract(
+                    contracts[1].contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &instantiate_msg(contracts[0].contract_code_id, provenance_hash),
+                    &[],
+                    "test instantiate launchpad contract",
+                    None,
+                );
+                assert_eq!(
+                    res.unwrap_err().source().unwrap().to_string(),
+                    "Invalid provenance hash"
+                );
+            }
+        }
+    }
+
+    mod sequential_token_ids {
+        use crate::{state::PhaseData, testing_config::env::USER_1};
+
+        use super::*;
+
+        // create a sequential launchpad of 5 nfts with a public phase
+        fn create_sequential_launchpad() -> (App, Addr) {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // prepare instantiate msg for launchpad contract
+            let mut instantiate_msg = launchpad_instantiate_msg(contracts[0].contract_code_id);
+            instantiate_msg.collection_info.token_id_mode = TokenIdMode::Sequential;
+            instantiate_msg.collection_info.max_supply = 5;
+            instantiate_msg.launchpad_fee = 0;
+
+            // instantiate launchpad contract
+            let launchpad_address =
+                instantiate_launchpad(&mut app, contracts[1].contract_code_id, &instantiate_msg);
+
+            // add a public phase and activate the launchpad
+            let phase_data = PhaseData {
+                max_supply: None,
+                max_nfts_per_address: 10,
+                ..phase_data(&app)
+            };
+            add_phase_and_activate(&mut app, &launchpad_address, phase_data, &[]);
+            fund_user(&mut app, USER_1);
+            start_phase(&mut app);
+
+            (app, launchpad_address)
+        }
+
+        #[test]
+        fn token_ids_are_issued_in_order() {
+            let (mut app, launchpad_address) = create_sequential_launchpad();
+
+            mint(&mut app, &launchpad_address, USER_1, 3, None, None).unwrap();
+            assert_eq!(
+                query_tokens(&app, &launchpad_address, USER_1),
+                vec!["1", "2", "3"]
+            );
+
+            mint(&mut app, &launchpad_address, USER_1, 2, None, None).unwrap();
+            assert_eq!(
+                query_tokens(&app, &launchpad_address, USER_1),
+                vec!["1", "2", "3", "4", "5"]
+            );
+
+            // all the nfts are minted
+            let res = mint(&mut app, &launchpad_address, USER_1, 1, None, None);
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Max supply reached"
+            );
+        }
+    }
+
+    mod airdrop_mint {
+        use cosmwasm_std::BlockInfo;
+
+        use crate::{
+            state::PhaseData,
+            testing_config::env::{USER_1, USER_2},
+        };
+
+        use super::*;
+
+        fn instantiate_msg(cw2981_code_id: u64, reserved_supply: u64) -> InstantiateMsg {
+            let mut instantiate_msg = launchpad_instantiate_msg(cw2981_code_id);
+            instantiate_msg.collection_info.reserved_supply = reserved_supply;
+            instantiate_msg.collection_info.max_supply = 5;
+            instantiate_msg.launchpad_fee = 0;
+            instantiate_msg
+        }
+
+        // create a launchpad of 5 nfts, 3 of them are reserved, with a public phase
+        fn create_launchpad_with_reserve() -> (App, Addr) {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // instantiate launchpad contract
+            let launchpad_address = instantiate_launchpad(
+                &mut app,
+                contracts[1].contract_code_id,
+                &instantiate_msg(contracts[0].contract_code_id, 3),
+            );
+
+            // add a public phase and activate the launchpad
+            let phase_data = PhaseData {
+                max_supply: None,
+                max_nfts_per_address: 10,
+                ..phase_data(&app)
+            };
+            add_phase_and_activate(&mut app, &launchpad_address, phase_data, &[]);
+            fund_user(&mut app, USER_1);
+
+            (app, launchpad_address)
+        }
+
+        fn airdrop(
+            app: &mut App,
+            launchpad_address: &Addr,
+            sender: &str,
+            recipients: Vec<(&str, u64)>,
+        ) -> anyhow::Result<cw_multi_test::AppResponse> {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                launchpad_address.clone(),
+                &ExecuteMsg::AirdropMint {
+                    recipients: recipients
+                        .into_iter()
+                        .map(|(recipient, amount)| (recipient.to_string(), amount))
+                        .collect(),
+                },
+                &[],
+            )
+        }
+
+        #[test]
+        fn creator_can_airdrop_reserved_supply() {
+            let (mut app, launchpad_address) = create_launchpad_with_reserve();
+
+            // only the admin or the creator can airdrop
+            let res = airdrop(&mut app, &launchpad_address, USER_1, vec![(USER_1, 1)]);
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+
+            // the creator airdrops before the phases
+            airdrop(&mut app, &launchpad_address, CREATOR, vec![(USER_2, 2)]).unwrap();
+            assert_eq!(query_tokens(&app, &launchpad_address, USER_2).len(), 2);
+
+            // the airdrops cannot exceed the reserved supply
+            let res = airdrop(&mut app, &launchpad_address, CREATOR, vec![(USER_2, 2)]);
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Reserved supply reached"
+            );
+
+            start_phase(&mut app);
+
+            // the remaining reserved nft cannot be minted in the phase
+            let res = mint(&mut app, &launchpad_address, USER_1, 3, None, None);
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Max supply reached"
+            );
+            mint(&mut app, &launchpad_address, USER_1, 2, None, None).unwrap();
+
+            // the admin airdrops the last reserved nft after the phases
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1000),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            airdrop(&mut app, &launchpad_address, ADMIN, vec![(USER_1, 1)]).unwrap();
+            assert_eq!(query_tokens(&app, &launchpad_address, USER_1).len(), 3);
+
+            let launchpad_info = query_launchpad_info(&app, &launchpad_address);
+            assert_eq!(launchpad_info.total_supply, 5);
+            assert_eq!(launchpad_info.airdropped_supply, 3);
+        }
+
+        #[test]
+        fn cannot_airdrop_invalid_amounts() {
+            let (mut app, launchpad_address) = create_launchpad_with_reserve();
+
+            for recipients in [vec![], vec![(USER_1, 1), (USER_2, 0)]] {
+                let res = airdrop(&mut app, &launchpad_address, CREATOR, recipients);
+                assert_eq!(
+                    res.unwrap_err().source().unwrap().to_string(),
+                    "Invalid airdrop"
+                );
+            }
+
+            // the total amount overflows
+            let res = airdrop(
+                &mut app,
+                &launchpad_address,
+                CREATOR,
+                vec![(USER_1, u64::MAX), (USER_2, 2)],
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Reserved supply reached"
+            );
+        }
+
+        #[test]
+        fn cannot_reserve_more_than_max_supply() {
+            let (mut app, contracts) = instantiate_contracts();
+
+            let res = app.instantiate_contract(
+                contracts[1].contract_code_id,
+                Addr::unchecked(ADMIN),
+                &instantiate_msg(contracts[0].contract_code_id, 6),
+                &[],
+                "test instantiate launchpad contract",
+                None,
+            );
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Invalid reserved supply"
+            );
+        }
+    }
+
+    mod payees {
+        use cosmwasm_std::{coin, BlockInfo, Uint128};
+
+        use crate::{
+            state::{Payee, PhaseData, PriceType},
+            testing_config::env::{NATIVE_DENOM, USER_1, USER_2},
+        };
+
+        use super::*;
+
+        const ARTIST: &str = "aura10000000000000000000000000000000artist";
+        const DAO: &str = "aura100000000000000000000000000000000000dao";
+
+        fn set_payees(
+            app: &mut App,
+            launchpad_address: &Addr,
+            sender: &str,
+            payees: Vec<(&str, u32)>,
+        ) -> anyhow::Result<cw_multi_test::AppResponse> {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                launchpad_address.clone(),
+                &ExecuteMsg::SetPayees {
+                    payees: payees
+                        .into_iter()
+                        .map(|(address, shares)| (address.to_string(), shares))
+                        .collect(),
+                },
+                &[],
+            )
+        }
+
+        fn query_payees(app: &App, launchpad_address: &Addr) -> Vec<Payee> {
+            app.wrap()
+                .query_wasm_smart(launchpad_address, &QueryMsg::Payees {})
+                .unwrap()
+        }
+
+        #[test]
+        fn revenue_is_split_between_payees() {
+            // get integration test app and launchpad address
+            let (mut app, launchpad_address) = create_launchpad();
+
+            // the creator receives all the revenue by default
+            assert_eq!(
+                query_payees(&app, &launchpad_address),
+                vec![Payee {
+                    address: Addr::unchecked(CREATOR),
+                    shares: 100,
+                }]
+            );
+
+            let payees = vec![(CREATOR, 50), (ARTIST, 30), (DAO, 20)];
+
+            // only the creator can set the payees
+            let res = set_payees(&mut app, &launchpad_address, ADMIN, payees.clone());
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized"
+            );
+
+            set_payees(&mut app, &launchpad_address, CREATOR, payees.clone()).unwrap();
+            assert_eq!(
+                query_payees(&app, &launchpad_address),
+                vec![
+                    Payee {
+                        address: Addr::unchecked(CREATOR),
+                        shares: 50,
+                    },
+                    Payee {
+                        address: Addr::unchecked(ARTIST),
+                        shares: 30,
+                    },
+                    Payee {
+                        address: Addr::unchecked(DAO),
+                        shares: 20,
+                    },
+                ]
+            );
+
+            // add a public phase and activate the launchpad
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &ExecuteMsg::AddMintPhase {
+                    after_phase_id: None,
+                    phase_data: PhaseData {
+                        start_time: app.block_info().time.plus_seconds(200),
+                        end_time: app.block_info().time.plus_seconds(1000),
+                        max_supply: Some(100),
+                        max_nfts_per_address: 50,
+                        price: coin(100, NATIVE_DENOM),
+                        is_public: true,
+                        price_type: PriceType::Native,
+                    },
+                },
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                launchpad_address.clone(),
+                &ExecuteMsg::ActivateLaunchpad {},
+                &[],
+            )
+            .unwrap();
+
+            // the payees cannot be changed after the launchpad started
+            let res = set_payees(&mut app, &launchpad_address, CREATOR, vec![(CREATOR, 100)]);
+            assert_eq!(
+                res.unwrap_err().source().unwrap().to_string(),
+                "Launchpad started"
+            );
+
+            // change block time to make the phase active
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(400),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 mints 10 nfts
+            app.sudo(cw_multi_test::SudoMsg::Bank(
+                cw_multi_test::BankSudo::Mint {
+                    to_address: USER_1.to_string(),
+                    amount: vec![coin(1000, NATIVE_DENOM)],
+                },
+            ))
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                launchpad_address.clone(),
+                &ExecuteMsg::Mint {
+                    phase_id: 1,
+                    amount: Some(10),
+                    merkle_proof: None,
+                    voucher: None,
+                },
+                &[coin(1000, NATIVE_DENOM)],
+            )
+            .unwrap();
+
+            // USER_2 withdraws the revenue after the last phase is finished
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(600),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                launchpad_address.clone(),
+                &ExecuteMsg::Withdraw {
+                    denom: NATIVE_DENOM.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            // the payees share (100 * 10) * 90% native token, the LAUNCHPAD_COLLECTOR has 10%
+            for (address, amount) in [
+                (CREATOR, 450u128),
+                (ARTIST, 270u128),
+                (DAO, 180u128),
+                (LAUNCHPAD_COLLECTOR, 100u128),
+                (USER_2, 0u128),
+            ] {
+                assert_eq!(
+                    app.wrap()
+                        .query_balance(address, NATIVE_DENOM)
+                        .unwrap()
+                        .amount,
+                    Uint128::from(amount)
+                );
+            }
+        }
+
+        #[test]
+        fn cannot_set_invalid_payees() {
+            // get integration test app and launchpad address
+            let (mut app, launchpad_address) = create_launchpad();
+
+            for payees in [
+                vec![],
+                vec![(CREATOR, 60), (ARTIST, 30)],
+                vec![(CREATOR, 60), (ARTIST, 50)],
+                vec![(CREATOR, 100), (ARTIST, 0)],
+                vec![(CREATOR, 100), (ARTIST, u32::MAX)],
+                vec![(CREATOR, u32::MAX - 99), (ARTIST, 100)],
+                vec![(CREATOR, 50), (CREATOR, 50)],
+            ] {
+                let res = set_payees(&mut app, &launchpad_address, CREATOR, payees);
+                assert_eq!(
+                    res.unwrap_err().source().unwrap().to_string(),
+                    "Invalid payees"
+                );
+            }
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Coin, Timestamp};
+use cw20::Cw20ReceiveMsg;
+use nois::NoisCallback;
 
-use crate::state::{LaunchpadInfo, PhaseConfigResponse, PhaseData};
+use crate::state::{LaunchpadInfo, Payee, PhaseConfigResponse, PhaseData, TokenIdMode};
 
 /// Message type for `instantiate` entry_point
 #[cw_serde]
@@ -9,7 +12,13 @@ pub struct InstantiateMsg {
     pub colection_code_id: u64,
     pub launchpad_fee: u32,
     pub launchpad_collector: Option<String>,
+    // the launchpad admin, the sender if not set (e.g. the creator of a launchpad of the factory),
+    // the admin is also the wasm admin of the collection
+    #[serde(default)]
+    pub admin: Option<String>,
     pub collection_info: ColectionInfo,
+    // if set, the token_ids are chosen with the randomness of the nois proxy instead of the random_seed
+    pub nois_proxy: Option<String>,
 }
 
 /// Message type for `execute` entry_point
@@ -26,7 +35,6 @@ pub enum ExecuteMsg {
     RemoveMintPhase {
         phase_id: u64,
     },
-    // TODO: should implement Merkle Airdop instead of whitelist
     AddWhitelist {
         phase_id: u64,
         whitelists: Vec<String>,
@@ -35,17 +43,76 @@ pub enum ExecuteMsg {
         phase_id: u64,
         addresses: Vec<String>,
     },
+    // set the merkle root of the whitelist of a phase, the users prove they are in it when minting
+    // the stored whitelist is not used by a phase with a merkle root
+    SetMerkleRoot {
+        phase_id: u64,
+        merkle_root: Option<String>,
+    },
+    // set the public key signing the mint vouchers of a phase, the users mint with a voucher
+    // the stored whitelist and the merkle root are not used by a phase with a voucher signer
+    SetVoucherSigner {
+        phase_id: u64,
+        voucher_signer: Option<Binary>,
+    },
     DeactivateLaunchpad {},
     ActivateLaunchpad {},
     Mint {
         phase_id: u64,
         amount: Option<u64>,
+        merkle_proof: Option<MerkleProof>,
+        voucher: Option<MintVoucher>,
+    },
+    // mint in a phase priced in a cw20 token, the msg of the cw20 token is a ReceiveMsg
+    Receive(Cw20ReceiveMsg),
+    // mint the reserved nfts for free to the recipients, the admin or the creator can airdrop
+    AirdropMint {
+        recipients: Vec<(String, u64)>,
+    },
+    // reveal the final token_uris of the nfts minted with the placeholder_uri,
+    // the sha256 hash of the uri_prefix must be the provenance_hash committed at instantiate,
+    // the collection is not updated, the TokenUri query returns the revealed token_uris
+    Reveal {
+        uri_prefix: String,
+    },
+    // the callback of the nois proxy, the randomness settles the pending mint of the job
+    NoisReceive {
+        callback: NoisCallback,
     },
+    // cancel a pending mint the nois proxy did not settle in time, the minter is refunded
+    // the admin or the minter can cancel it
+    CancelPendingMint {
+        job_id: String,
+    },
+    // set the fee sent to the nois proxy with each randomness request, the minter pays it with the
+    // price, or the sender of an airdrop pays it for each recipient
+    SetNoisFee {
+        nois_fee: Vec<Coin>,
+    },
+    // set the payees of the revenue of the creator with their shares in percentage,
+    // the shares must sum to 100 and the payees cannot be changed after the launchpad started
+    SetPayees {
+        payees: Vec<(String, u32)>,
+    },
+    // the denom is the native denom or the address of the cw20 token, anyone can withdraw
+    // the revenue to the payees and the launchpad fee to the launchpad_collector,
+    // the prices of the pending mints are not withdrawn
     Withdraw {
         denom: String,
     },
 }
 
+/// Message type sent with the cw20 tokens to the launchpad
+#[cw_serde]
+pub enum ReceiveMsg {
+    Mint {
+        phase_id: u64,
+        amount: Option<u64>,
+        merkle_proof: Option<MerkleProof>,
+        voucher: Option<MintVoucher>,
+    },
+}
+
 /// Message type for `migrate` entry_point
 #[cw_serde]
 pub enum MigrateMsg {}
@@ -60,6 +127,36 @@ pub enum QueryMsg {
     GetAllPhaseConfigs {},
     #[returns(Vec<MintableResponse>)]
     Mintable { user: String },
+    // the token_uri of a minted nft, it is the placeholder_uri until the reveal
+    // the token_uri stored in the collection stays the placeholder_uri, so this query is
+    // the authoritative token_uri after the reveal
+    #[returns(TokenUriResponse)]
+    TokenUri { token_id: String },
+    // the payees of the revenue of the creator, the creator with all shares if not set
+    #[returns(Vec<Payee>)]
+    Payees {},
+}
+
+// the leaf of a user is the sha256 hash of the address, or of "address:allowance" if it has an allowance
+// the pairs of hashes are sorted before being hashed together
+#[cw_serde]
+pub struct MerkleProof {
+    pub allowance: Option<u64>, // the number of nfts the user can mint in the phase, instead of max_nfts_per_address
+    pub proof: Vec<String>,     // the hex encoded hashes from the leaf to the root
+}
+
+// the voucher is signed with secp256k1 over the sha256 hash of
+// "launchpad_address:phase_id:minter:max_amount:expiry", where expiry is in nanoseconds
+#[cw_serde]
+pub struct MintVoucher {
+    pub max_amount: u64,   // the number of nfts that can be minted with the voucher
+    pub expiry: Timestamp, // the voucher cannot be used after this time
+    pub signature: Binary, // the 64 bytes serialized signature
+}
+
+#[cw_serde]
+pub struct TokenUriResponse {
+    pub token_uri: String,
 }
 
 #[cw_serde]
@@ -78,4 +175,13 @@ pub struct ColectionInfo {
     pub uri_suffix: String,
     pub royalty_percentage: Option<u64>,
     pub royalty_payment_address: Option<String>,
+    // the token_uri of all nfts until the reveal, the provenance_hash must be committed with it
+    // the provenance_hash is the hex encoded sha256 hash of the uri_prefix revealed later
+    pub placeholder_uri: Option<String>,
+    pub provenance_hash: Option<String>,
+    #[serde(default)]
+    pub token_id_mode: TokenIdMode,
+    // the nfts of max_supply set aside for the airdrops of the creator
+    #[serde(default)]
+    pub reserved_supply: u64,
 }
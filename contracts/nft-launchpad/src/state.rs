@@ -1,11 +1,23 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Timestamp};
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp};
 use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
     pub admin: Addr,               // the launchpad admin
     pub launchpad_collector: Addr, // the address that will receive the launchpad fee
+    #[serde(default)]
+    pub nois_proxy: Option<Addr>, // the nois proxy giving the randomness of the token_ids
+    #[serde(default)]
+    pub nois_fee: Vec<Coin>, // sent by the launchpad to the nois proxy with each randomness request
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum PriceType {
+    #[default]
+    Native,
+    Cw20, // the denom of the price is the address of the cw20 token
 }
 
 #[cw_serde]
@@ -18,6 +30,8 @@ pub struct PhaseData {
     pub max_nfts_per_address: u64,
     pub price: Coin,
     pub is_public: bool,
+    #[serde(default)]
+    pub price_type: PriceType,
 }
 
 #[cw_serde]
@@ -31,6 +45,12 @@ pub struct PhaseConfig {
     pub max_nfts_per_address: u64,
     pub price: Coin,
     pub is_public: bool,
+    #[serde(default)]
+    pub price_type: PriceType,
+    #[serde(default)]
+    pub merkle_root: Option<String>, // the hex encoded root of the whitelist merkle tree
+    #[serde(default)]
+    pub voucher_signer: Option<Binary>, // the secp256k1 public key signing the mint vouchers
 }
 
 #[cw_serde]
@@ -43,6 +63,17 @@ pub struct PhaseConfigResponse {
     pub max_nfts_per_address: u64,
     pub price: Coin,
     pub is_public: bool,
+    pub price_type: PriceType,
+    pub merkle_root: Option<String>,
+    pub voucher_signer: Option<Binary>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum TokenIdMode {
+    #[default]
+    Random,
+    Sequential, // the token_ids are issued from 1 to max_supply in order
 }
 
 #[cw_serde]
@@ -58,6 +89,20 @@ pub struct LaunchpadInfo {
     pub last_phase_id: u64,
     pub last_issued_id: u64, // for the unique id of phases
     pub is_active: bool,     // admin can update phases when launchpad is not active only
+    #[serde(default)]
+    pub pending_supply: u64, // the nfts waiting for the randomness of the nois proxy
+    #[serde(default)]
+    pub placeholder_uri: Option<String>, // the token_uri of the nfts until the reveal
+    #[serde(default)]
+    pub provenance_hash: Option<String>, // the hex sha256 of the revealed uri_prefix, committed at instantiate
+    #[serde(default)]
+    pub is_revealed: bool,
+    #[serde(default)]
+    pub token_id_mode: TokenIdMode,
+    #[serde(default)]
+    pub reserved_supply: u64, // the nfts set aside for the airdrops, they cannot be minted in the phases
+    #[serde(default)]
+    pub airdropped_supply: u64,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -65,11 +110,42 @@ pub const LAUNCHPAD_INFO: Item<LaunchpadInfo> = Item::new("launchpad_info");
 pub const PHASE_CONFIGS: Map<u64, PhaseConfig> = Map::new("phase_configs");
 
 // The whitelist !!! key = (phase_id, user_address), value = number of minted_nft in phase_id
+// In a phase with a merkle root, it only stores the number of minted nfts
 pub const WHITELIST: Map<(u64, Addr), u64> = Map::new("whitelist");
 
+// The used vouchers !!! key = the signed hash of the voucher, value = number of nfts minted with the voucher
+pub const VOUCHERS: Map<&[u8], u64> = Map::new("vouchers");
+
 // The length of the token_ids will be the same as the max_supply of the launchpad
 // The remaining token_ids
 // To get a token_id from REMAINING_TOKEN_IDS, we must random a position then get token_id from that position
 pub const REMAINING_TOKEN_IDS: Map<u64, u64> = Map::new("remaining_token_ids");
 
 pub const RANDOM_SEED: Item<[u8; 32]> = Item::new("random_seed");
+
+#[cw_serde]
+pub struct Payee {
+    pub address: Addr,
+    pub shares: u32, // the percentage of the revenue of the creator
+}
+
+// the payees of the revenue of the creator, the creator receives all of it if not set
+pub const PAYEES: Item<Vec<Payee>> = Item::new("payees");
+
+#[cw_serde]
+pub struct PendingMint {
+    pub minter: Addr,
+    pub amount: u64,
+    #[serde(default)]
+    pub requested_at: Timestamp,
+    #[serde(default)]
+    pub phase_id: Option<u64>, // the phase of the mint, None for an airdrop
+    #[serde(default)]
+    pub price: Option<Coin>, // the price paid by the minter, refunded if the mint is cancelled
+    #[serde(default)]
+    pub voucher_hash: Option<Binary>, // the voucher used by the minter, released if the mint is cancelled
+}
+
+// The mints waiting for the randomness of the nois proxy !!! key = job_id
+pub const PENDING_MINTS: Map<&str, PendingMint> = Map::new("pending_mints");
+pub const LAST_JOB_ID: Item<u64> = Item::new("last_job_id");
@@ -4,11 +4,27 @@ pub mod env {
         execute as LaunchpadExecute, instantiate as LaunchpadInstantiate, query as LaunchpadQuery,
         reply as LaunchpadReply,
     };
-    use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+    use crate::msg::{
+        ColectionInfo, ExecuteMsg, InstantiateMsg, MerkleProof, MintVoucher, QueryMsg,
+    };
+    use crate::state::{LaunchpadInfo, PhaseData, PriceType, TokenIdMode};
+    use cosmwasm_std::{coin, Addr, BlockInfo, Coin, Empty, Uint128};
+    use cw20_base::contract::{
+        execute as cw20_execute, instantiate as cw20_instantiate, query as cw20_query,
+    };
     use cw2981_royalties::{
         execute as cw2981_execute, instantiate as cw2981_instantiate, query as cw2981_query,
+        QueryMsg as Cw2981QueryMsg,
+    };
+    use cw721::TokensResponse;
+    use cw_multi_test::{
+        App, AppBuilder, AppResponse, BankSudo, Contract, ContractWrapper, Executor, SudoMsg,
+    };
+
+    use super::mock_nois_proxy::{
+        execute as nois_proxy_execute, instantiate as nois_proxy_instantiate,
+        query as nois_proxy_query,
     };
-    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper};
 
     // ****************************************
     // You MUST define the constants value here
@@ -24,6 +40,11 @@ pub mod env {
 
     pub const TOKEN_INITIAL_BALANCE: u128 = 1_000_000_000_000u128;
 
+    pub const COLLECTION_NAME: &str = "A launchpad collection";
+    pub const COLLECTION_SYMBOL: &str = "LPC";
+
+    pub const PRICE: u128 = 100;
+
     pub struct ContractInfo {
         pub contract_code_id: u64,
     }
@@ -49,6 +70,17 @@ pub mod env {
         Box::new(contract)
     }
 
+    fn cw20_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(cw20_execute, cw20_instantiate, cw20_query);
+        Box::new(contract)
+    }
+
+    fn nois_proxy_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract =
+            ContractWrapper::new(nois_proxy_execute, nois_proxy_instantiate, nois_proxy_query);
+        Box::new(contract)
+    }
+
     fn nft_launchpad_contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(LaunchpadExecute, LaunchpadInstantiate, LaunchpadQuery)
             .with_reply(LaunchpadReply);
@@ -79,7 +111,246 @@ pub mod env {
             contract_code_id: launchpad_contract_code_id,
         });
 
+        // Cw20 contract
+        // store the code of all contracts to the app and get the code ids
+        let cw20_contract_code_id = app.store_code(cw20_contract_template());
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_code_id: cw20_contract_code_id,
+        });
+
+        // Mock nois proxy contract
+        // store the code of all contracts to the app and get the code ids
+        let nois_proxy_contract_code_id = app.store_code(nois_proxy_contract_template());
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_code_id: nois_proxy_contract_code_id,
+        });
+
         // return the app instance, the addresses and code IDs of all contracts
         (app, contract_info_vec)
     }
+
+    // the instantiate msg of a launchpad of 5000 random nfts of CREATOR, the tests change its fields
+    pub fn launchpad_instantiate_msg(cw2981_code_id: u64) -> InstantiateMsg {
+        InstantiateMsg {
+            colection_code_id: cw2981_code_id,
+            collection_info: ColectionInfo {
+                name: COLLECTION_NAME.to_string(),
+                symbol: COLLECTION_SYMBOL.to_string(),
+                royalty_percentage: None,
+                royalty_payment_address: None,
+                placeholder_uri: None,
+                provenance_hash: None,
+                token_id_mode: TokenIdMode::Random,
+                reserved_supply: 0,
+                max_supply: 5000,
+                uri_prefix:
+                    "ipfs://bafybeifm3xas2egfbwzo7cg5wiayw44sbvfn6h5am2bydp2zpnypl7g5tq/images/"
+                        .to_string(),
+                uri_suffix: ".json".to_string(),
+                creator: CREATOR.to_string(),
+            },
+            random_seed: "9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62"
+                .to_string(),
+            launchpad_fee: 10,
+            launchpad_collector: Some(LAUNCHPAD_COLLECTOR.to_string()),
+            admin: None,
+            nois_proxy: None,
+        }
+    }
+
+    pub fn instantiate_launchpad(
+        app: &mut App,
+        launchpad_code_id: u64,
+        instantiate_msg: &InstantiateMsg,
+    ) -> Addr {
+        app.instantiate_contract(
+            launchpad_code_id,
+            Addr::unchecked(ADMIN),
+            instantiate_msg,
+            &[],
+            "test instantiate launchpad contract",
+            None,
+        )
+        .unwrap()
+    }
+
+    pub fn instantiate_nois_proxy(app: &mut App, nois_proxy_code_id: u64) -> Addr {
+        app.instantiate_contract(
+            nois_proxy_code_id,
+            Addr::unchecked(ADMIN),
+            &Empty {},
+            &[],
+            "test instantiate nois proxy contract",
+            None,
+        )
+        .unwrap()
+    }
+
+    // a public phase of 100 nfts priced PRICE native tokens, it starts in 200 seconds and ends in 1000 seconds
+    pub fn phase_data(app: &App) -> PhaseData {
+        PhaseData {
+            start_time: app.block_info().time.plus_seconds(200),
+            end_time: app.block_info().time.plus_seconds(1000),
+            max_supply: Some(100),
+            max_nfts_per_address: 5,
+            price: coin(PRICE, NATIVE_DENOM),
+            is_public: true,
+            price_type: PriceType::Native,
+        }
+    }
+
+    // add the phase 1 to the launchpad, then the admin configures it with the admin_msgs
+    // and activates the launchpad
+    pub fn add_phase_and_activate(
+        app: &mut App,
+        launchpad_address: &Addr,
+        phase_data: PhaseData,
+        admin_msgs: &[ExecuteMsg],
+    ) {
+        let add_phase_msg = ExecuteMsg::AddMintPhase {
+            after_phase_id: None,
+            phase_data,
+        };
+        for msg in [&add_phase_msg]
+            .into_iter()
+            .chain(admin_msgs)
+            .chain([&ExecuteMsg::ActivateLaunchpad {}])
+        {
+            app.execute_contract(Addr::unchecked(ADMIN), launchpad_address.clone(), msg, &[])
+                .unwrap();
+        }
+    }
+
+    // change block time to make the phase of phase_data active
+    pub fn start_phase(app: &mut App) {
+        app.set_block(BlockInfo {
+            time: app.block_info().time.plus_seconds(400),
+            height: app.block_info().height + 1,
+            chain_id: app.block_info().chain_id,
+        });
+    }
+
+    // mint native token to the user
+    pub fn fund_user(app: &mut App, user: &str) {
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: user.to_string(),
+            amount: vec![coin(1000000000u128, NATIVE_DENOM)],
+        }))
+        .unwrap();
+    }
+
+    // the user mints the amount of nfts in the phase 1, paying PRICE native tokens per nft
+    pub fn mint(
+        app: &mut App,
+        launchpad_address: &Addr,
+        user: &str,
+        amount: u64,
+        merkle_proof: Option<MerkleProof>,
+        voucher: Option<MintVoucher>,
+    ) -> anyhow::Result<AppResponse> {
+        app.execute_contract(
+            Addr::unchecked(user),
+            launchpad_address.clone(),
+            &ExecuteMsg::Mint {
+                phase_id: 1,
+                amount: Some(amount),
+                merkle_proof,
+                voucher,
+            },
+            &[coin(PRICE * amount as u128, NATIVE_DENOM)],
+        )
+    }
+
+    pub fn query_launchpad_info(app: &App, launchpad_address: &Addr) -> LaunchpadInfo {
+        app.wrap()
+            .query_wasm_smart(launchpad_address, &QueryMsg::GetLaunchpadInfo {})
+            .unwrap()
+    }
+
+    // the token_ids of the nfts of the launchpad collection owned by the owner
+    pub fn query_tokens(app: &App, launchpad_address: &Addr, owner: &str) -> Vec<String> {
+        let launchpad_info = query_launchpad_info(app, launchpad_address);
+        let res: TokensResponse = app
+            .wrap()
+            .query_wasm_smart(
+                launchpad_info.collection_address,
+                &Cw2981QueryMsg::Tokens {
+                    owner: owner.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.tokens
+    }
+}
+
+// a local nois proxy, the randomness of a job is delivered by the test instead of the beacon
+#[cfg(test)]
+pub mod mock_nois_proxy {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, HexBinary, MessageInfo, Response,
+        StdError, StdResult, WasmMsg,
+    };
+    use cw_storage_plus::Map;
+    use nois::{NoisCallback, ReceiverExecuteMsg};
+
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        GetNextRandomness {
+            job_id: String,
+        },
+        // send the randomness of the job to the contract requesting it
+        Deliver {
+            job_id: String,
+            randomness: HexBinary,
+        },
+    }
+
+    // the contract requesting the randomness of a job !!! key = job_id
+    const JOBS: Map<&str, Addr> = Map::new("jobs");
+
+    pub fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    pub fn execute(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            ExecuteMsg::GetNextRandomness { job_id } => {
+                JOBS.save(deps.storage, &job_id, &info.sender)?;
+                Ok(Response::new())
+            }
+            ExecuteMsg::Deliver { job_id, randomness } => {
+                let requester = JOBS.load(deps.storage, &job_id)?;
+                JOBS.remove(deps.storage, &job_id);
+
+                Ok(Response::new().add_message(WasmMsg::Execute {
+                    contract_addr: requester.to_string(),
+                    msg: to_binary(&ReceiverExecuteMsg::NoisReceive {
+                        callback: NoisCallback { job_id, randomness },
+                    })?,
+                    funds: vec![],
+                }))
+            }
+        }
+    }
+
+    pub fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("the mock nois proxy has no query"))
+    }
 }
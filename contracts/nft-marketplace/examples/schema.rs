@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use nft_marketplace::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use nft_marketplace::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
-        query: QueryMsg
+        query: QueryMsg,
+        sudo: SudoMsg
     }
 }
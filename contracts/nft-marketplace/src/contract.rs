@@ -1,19 +1,19 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_slice, to_binary, to_vec, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
 };
 use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::order_state::orders;
-use crate::state::{contract, Config, ConfigOld};
+use crate::hooks::{LISTING_HOOK_REPLY_ID, SALE_HOOK_REPLY_ID};
+use crate::migrations::migrate_storage;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
+use crate::state::{contract, Config, MAX_BASIS_POINTS};
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:nft-marketplace";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CONTRACT_NAME: &str = "crates.io:nft-marketplace";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -26,6 +26,9 @@ pub fn instantiate(
     let conf = Config {
         owner: msg.owner,
         vaura_address: Addr::unchecked("aura0"),
+        max_royalty_bps: MAX_BASIS_POINTS,
+        protocol_fee_bps: 0,
+        referral_fee_bps: 0,
     };
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     contract().config.save(deps.storage, &conf)?;
@@ -59,12 +62,18 @@ pub fn execute(
         ExecuteMsg::Buy {
             contract_address,
             token_id,
+            max_price,
+            referrer,
+            pay_with_vaura,
         } => contract().execute_buy(
             deps,
             _env,
             info,
             api.addr_validate(&contract_address)?,
             token_id,
+            max_price,
+            referrer.map(|r| api.addr_validate(&r)).transpose()?,
+            pay_with_vaura.unwrap_or(false),
         ),
         ExecuteMsg::Cancel {
             contract_address,
@@ -79,12 +88,16 @@ pub fn execute(
         ExecuteMsg::OfferNft {
             nft,
             funds_amount,
+            start_time,
             end_time,
-        } => contract().execute_offer_nft(deps, _env, info, nft, funds_amount, end_time),
+        } => {
+            contract().execute_offer_nft(deps, _env, info, nft, funds_amount, start_time, end_time)
+        }
         ExecuteMsg::AcceptNftOffer {
             offerer,
             nft,
             funds_amount,
+            referrer,
         } => contract().execute_accept_nft_offer(
             deps,
             _env,
@@ -92,36 +105,161 @@ pub fn execute(
             api.addr_validate(&offerer)?,
             nft,
             funds_amount,
+            referrer.map(|r| api.addr_validate(&r)).transpose()?,
         ),
         ExecuteMsg::CancelOffer { nfts } => contract().execute_cancel_offer(deps, _env, info, nfts),
+        ExecuteMsg::IncrementCounter {} => contract().execute_increment_counter(deps, _env, info),
         ExecuteMsg::EditVauraToken { token_address } => {
             contract().execute_edit_vaura_token(deps, _env, info, token_address)
         }
+        ExecuteMsg::EditReferralFee { referral_fee_bps } => {
+            contract().execute_edit_referral_fee(deps, _env, info, referral_fee_bps)
+        }
+        ExecuteMsg::EditProtocolFee { protocol_fee_bps } => {
+            contract().execute_edit_protocol_fee(deps, _env, info, protocol_fee_bps)
+        }
+        ExecuteMsg::EditMaxRoyalty { max_royalty_bps } => {
+            contract().execute_edit_max_royalty(deps, _env, info, max_royalty_bps)
+        }
+        ExecuteMsg::SetRoyaltyPolicy {
+            contract_address,
+            policy,
+        } => contract().execute_set_royalty_policy(
+            deps,
+            _env,
+            info,
+            api.addr_validate(&contract_address)?,
+            policy,
+        ),
+        ExecuteMsg::SetMinOfferAmount {
+            contract_address,
+            min_funds_amount,
+        } => contract().execute_set_min_offer_amount(
+            deps,
+            _env,
+            info,
+            api.addr_validate(&contract_address)?,
+            min_funds_amount,
+        ),
+        ExecuteMsg::AddSaleHook { hook } => {
+            contract().execute_add_sale_hook(deps, _env, info, hook)
+        }
+        ExecuteMsg::RemoveSaleHook { hook } => {
+            contract().execute_remove_sale_hook(deps, _env, info, hook)
+        }
+        ExecuteMsg::AddListingHook { hook } => {
+            contract().execute_add_listing_hook(deps, _env, info, hook)
+        }
+        ExecuteMsg::RemoveListingHook { hook } => {
+            contract().execute_remove_listing_hook(deps, _env, info, hook)
+        }
+        ExecuteMsg::ListRental {
+            contract_address,
+            token_id,
+            price_per_unit,
+            unit_seconds,
+            max_units,
+        } => contract().execute_list_rental(
+            deps,
+            _env,
+            info,
+            api.addr_validate(&contract_address)?,
+            token_id,
+            price_per_unit,
+            unit_seconds,
+            max_units,
+        ),
+        ExecuteMsg::CancelRental {
+            contract_address,
+            token_id,
+        } => contract().execute_cancel_rental(
+            deps,
+            _env,
+            info,
+            api.addr_validate(&contract_address)?,
+            token_id,
+        ),
+        ExecuteMsg::Rent {
+            contract_address,
+            token_id,
+            units,
+        } => contract().execute_rent(
+            deps,
+            _env,
+            info,
+            api.addr_validate(&contract_address)?,
+            token_id,
+            units,
+        ),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let data_config = deps
-        .storage
-        .get(b"config")
-        .ok_or_else(|| StdError::not_found("Config"))?;
-    let config: ConfigOld = from_slice(&data_config)?;
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    let api = deps.api;
+    match msg {
+        SudoMsg::UpdateOwner { owner } => {
+            contract().sudo_update_owner(deps, api.addr_validate(&owner)?)
+        }
+        SudoMsg::Pause {} => contract().sudo_set_paused(deps, true),
+        SudoMsg::Unpause {} => contract().sudo_set_paused(deps, false),
+        SudoMsg::SetFees {
+            protocol_fee_bps,
+            referral_fee_bps,
+        } => contract().sudo_set_fees(deps, protocol_fee_bps, referral_fee_bps),
+        SudoMsg::RemoveListing {
+            contract_address,
+            token_id,
+        } => contract().sudo_remove_listing(
+            deps,
+            env,
+            api.addr_validate(&contract_address)?,
+            token_id,
+        ),
+        SudoMsg::RemoveOffer {
+            offerer,
+            contract_address,
+            token_id,
+        } => contract().sudo_remove_offer(
+            deps,
+            env,
+            api.addr_validate(&offerer)?,
+            api.addr_validate(&contract_address)?,
+            token_id,
+        ),
+    }
+}
 
-    // the default value of vaura_address is equal to "aura0" and MUST BE SET before offer nft
-    let conf = Config {
-        owner: config.owner,
-        vaura_address: Addr::unchecked("aura0"),
-    };
-    deps.storage.set(b"config", &to_vec(&conf)?);
+// the hook submessages reply on error only
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SALE_HOOK_REPLY_ID | LISTING_HOOK_REPLY_ID => {
+            // a failing hook does not revert the trade
+            let error = msg.result.into_result().err().unwrap_or_default();
+            Ok(Response::new()
+                .add_attribute("method", "hook_failed")
+                .add_attribute("reply_id", msg.id.to_string())
+                .add_attribute("error", error))
+        }
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
 
-    contract().offers = orders();
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // the migration steps newer than the stored version are applied in order
+    let previous_version = migrate_storage(deps.branch())?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_version", previous_version)
+        .add_attribute("new_version", CONTRACT_VERSION))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     let api = deps.api;
     match msg {
         // get config
@@ -166,6 +304,30 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after_offerer,
             limit,
         )?),
+        QueryMsg::NftOffersByPrice {
+            contract_address,
+            token_id,
+            start_after,
+            limit,
+            include_expired,
+        } => to_binary(&contract().query_nft_offers_by_price(
+            deps,
+            env,
+            api.addr_validate(&contract_address)?,
+            token_id,
+            start_after,
+            limit,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::BestOffer {
+            contract_address,
+            token_id,
+        } => to_binary(&contract().query_best_offer(
+            deps,
+            env,
+            api.addr_validate(&contract_address)?,
+            token_id,
+        )?),
         QueryMsg::UserOffers {
             offerer,
             start_after_nft,
@@ -176,5 +338,70 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after_nft,
             limit,
         )?),
+        QueryMsg::Counter { user } => {
+            to_binary(&contract().counter(deps.storage, &api.addr_validate(&user)?)?)
+        }
+        QueryMsg::CollectionStats { contract_address } => to_binary(
+            &contract().query_collection_stats(deps, env, api.addr_validate(&contract_address)?)?,
+        ),
+        QueryMsg::CollectionsByVolume {
+            denom,
+            start_after,
+            limit,
+        } => to_binary(&contract().query_collections_by_volume(deps, denom, start_after, limit)?),
+        QueryMsg::RoyaltyPolicy { contract_address } => to_binary(
+            &contract().query_royalty_policy(deps, api.addr_validate(&contract_address)?)?,
+        ),
+        QueryMsg::MinOfferAmount { contract_address } => to_binary(
+            &contract().query_min_offer_amount(deps, api.addr_validate(&contract_address)?)?,
+        ),
+        QueryMsg::Paused {} => to_binary(
+            &contract()
+                .paused
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::SaleHooks {} => to_binary(&contract().sale_hooks.query_hooks(deps.storage)?),
+        QueryMsg::ListingHooks {} => {
+            to_binary(&contract().listing_hooks.query_hooks(deps.storage)?)
+        }
+        QueryMsg::RentalListing {
+            contract_address,
+            token_id,
+        } => to_binary(&contract().query_rental_listing(
+            deps,
+            api.addr_validate(&contract_address)?,
+            token_id,
+        )?),
+        QueryMsg::RentalListingsByContractAddress {
+            contract_address,
+            start_after,
+            limit,
+        } => to_binary(&contract().query_rental_listings_by_contract_address(
+            deps,
+            api.addr_validate(&contract_address)?,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ActiveRental {
+            contract_address,
+            token_id,
+        } => to_binary(&contract().query_active_rental(
+            deps,
+            env,
+            api.addr_validate(&contract_address)?,
+            token_id,
+        )?),
+        QueryMsg::ActiveRentalsByRenter {
+            renter,
+            start_after,
+            limit,
+        } => to_binary(&contract().query_active_rentals_by_renter(
+            deps,
+            env,
+            api.addr_validate(&renter)?,
+            start_after,
+            limit,
+        )?),
     }
 }
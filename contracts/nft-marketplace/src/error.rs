@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp, Uint128};
+use cw721::Expiration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,10 +19,53 @@ pub enum ContractError {
     #[error("Insufficient Funds")]
     InsufficientFunds {},
 
-    #[error("Custom Error val: {val:?}")]
-    CustomError { val: String },
+    #[error("Price {price} exceeds max price {max_price}")]
+    PriceExceedsMaxPrice { price: Uint128, max_price: Uint128 },
+
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+    #[error("Marketplace is not approved to transfer the nft")]
+    MissingApproval {},
+
+    #[error("Approval expiration {expires} cannot be compared with end time {end_time}")]
+    ApprovalExpirationMismatch {
+        expires: Expiration,
+        end_time: Expiration,
+    },
+
+    #[error("Invalid auction config")]
+    InvalidAuctionConfig {},
+
+    #[error("Owner cannot buy")]
+    CannotBuyOwnListing {},
+
+    #[error("Auction not started, starts at {start_time}")]
+    AuctionNotStarted { start_time: Expiration },
+
+    #[error("Auction ended at {end_time}, current time {block_time}")]
+    AuctionEnded {
+        end_time: Expiration,
+        block_time: Timestamp,
+    },
+
+    #[error("Offer is expired at {end_time}")]
+    OfferExpired { end_time: Expiration },
+
+    #[error("Offer amount mismatch: expected {expected}, actual {actual}")]
+    OfferAmountMismatch { expected: u128, actual: u128 },
+
+    #[error("Offer consideration is not a nft")]
+    ConsiderationNotNft {},
+
+    #[error("Collection offer is not supported")]
+    CollectionOfferNotSupported {},
+
+    #[error("Number of nfts {count} is greater than {max}")]
+    TooManyNfts { count: usize, max: usize },
+
+    #[error("Offer does not exist")]
+    OfferNotFound {},
+
     #[error("Offer item must be a nft")]
     OfferEmpty {},
 
@@ -43,6 +87,63 @@ pub enum ContractError {
     #[error("Invalid end time")]
     InvalidEndTime {},
 
+    #[error("Invalid start time")]
+    InvalidStartTime {},
+
+    #[error("Offer not started")]
+    OfferNotStarted {},
+
+    #[error("Offer is cancelled")]
+    OfferCancelled {},
+
+    #[error("Offer amount {amount} is lower than the minimum {min_amount}")]
+    OfferAmountTooLow { amount: u128, min_amount: u128 },
+
     #[error("VAura address not set")]
     VauraAddressNotSet {},
+
+    #[error("VAura cannot pay for denom {denom}")]
+    VauraDenomNotSupported { denom: String },
+
+    #[error("Invalid basis points: {bps}")]
+    InvalidBasisPoints { bps: u64 },
+
+    #[error("Collection {contract_address} does not support royalties")]
+    RoyaltiesNotSupported { contract_address: String },
+
+    #[error("Invalid royalty info of collection {contract_address}")]
+    InvalidRoyaltyInfo { contract_address: String },
+
+    #[error("Hook already registered")]
+    HookAlreadyRegistered {},
+
+    #[error("Hook not registered")]
+    HookNotRegistered {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Cannot migrate from contract {contract_name}")]
+    InvalidMigrationContract { contract_name: String },
+
+    #[error("Cannot migrate from version {stored_version} to older version {new_version}")]
+    CannotDowngrade {
+        stored_version: String,
+        new_version: String,
+    },
+
+    #[error("Trading is paused")]
+    TradingPaused {},
+
+    #[error("Invalid rental config")]
+    InvalidRentalConfig {},
+
+    #[error("Rental of {units} units is not between 1 and {max_units}")]
+    InvalidRentalDuration { units: u64, max_units: u64 },
+
+    #[error("Cannot rent your own NFT")]
+    CannotRentOwnNft {},
+
+    #[error("Nft is rented until {expires}")]
+    NftAlreadyRented { expires: Expiration },
 }
@@ -0,0 +1,378 @@
+// custom events emitted for the indexers, the chain prefixes their type with `wasm-`
+// times are encoded as `height:<block height>`, `time:<nanoseconds>` or `never`
+
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Coin, Event, StdError, StdResult, Timestamp, Uint128};
+use cw721::Expiration;
+
+// a typed event which can be built for a response and parsed back from a transaction
+pub trait MarketplaceEvent: Sized {
+    // the type of the event without the `wasm-` prefix
+    const EVENT_TYPE: &'static str;
+
+    fn to_event(&self) -> Event;
+
+    fn from_event(event: &Event) -> StdResult<Self>;
+}
+
+// how the price of a sale is split between the parties
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SaleSplit {
+    pub royalty: Option<(Addr, Uint128)>,
+    pub protocol_fee: Uint128,
+    pub referral: Option<(Addr, Uint128)>,
+    pub seller_amount: Uint128,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListEvent {
+    pub contract_address: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    pub price: Coin,
+    pub start_time: Option<Expiration>,
+    pub end_time: Option<Expiration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuyEvent {
+    pub contract_address: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub price: Coin,
+    pub split: SaleSplit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CancelEvent {
+    pub contract_address: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    pub cancelled_by: Addr,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferEvent {
+    pub contract_address: Addr,
+    pub token_id: String,
+    pub offerer: Addr,
+    pub price: Coin,
+    pub start_time: Option<Expiration>,
+    pub end_time: Expiration,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AcceptOfferEvent {
+    pub contract_address: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    pub offerer: Addr,
+    pub price: Coin,
+    pub split: SaleSplit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncrementCounterEvent {
+    pub user: Addr,
+    pub counter: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CancelOfferEvent {
+    pub contract_address: Addr,
+    pub token_id: String,
+    pub offerer: Addr,
+}
+
+impl MarketplaceEvent for ListEvent {
+    const EVENT_TYPE: &'static str = "list";
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::EVENT_TYPE)
+            .add_attribute("contract_address", &self.contract_address)
+            .add_attribute("token_id", &self.token_id)
+            .add_attribute("seller", &self.seller);
+        let event = add_price(event, &self.price);
+        let event = add_optional_time(event, "start_time", &self.start_time);
+        add_optional_time(event, "end_time", &self.end_time)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        let attributes = EventAttributes::new(event, Self::EVENT_TYPE)?;
+        Ok(ListEvent {
+            contract_address: attributes.addr("contract_address")?,
+            token_id: attributes.get("token_id")?,
+            seller: attributes.addr("seller")?,
+            price: attributes.price()?,
+            start_time: attributes.optional_time("start_time")?,
+            end_time: attributes.optional_time("end_time")?,
+        })
+    }
+}
+
+impl MarketplaceEvent for BuyEvent {
+    const EVENT_TYPE: &'static str = "buy";
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::EVENT_TYPE)
+            .add_attribute("contract_address", &self.contract_address)
+            .add_attribute("token_id", &self.token_id)
+            .add_attribute("seller", &self.seller)
+            .add_attribute("buyer", &self.buyer);
+        let event = add_price(event, &self.price);
+        add_split(event, &self.split)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        let attributes = EventAttributes::new(event, Self::EVENT_TYPE)?;
+        Ok(BuyEvent {
+            contract_address: attributes.addr("contract_address")?,
+            token_id: attributes.get("token_id")?,
+            seller: attributes.addr("seller")?,
+            buyer: attributes.addr("buyer")?,
+            price: attributes.price()?,
+            split: attributes.split()?,
+        })
+    }
+}
+
+impl MarketplaceEvent for CancelEvent {
+    const EVENT_TYPE: &'static str = "cancel";
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::EVENT_TYPE)
+            .add_attribute("contract_address", &self.contract_address)
+            .add_attribute("token_id", &self.token_id)
+            .add_attribute("seller", &self.seller)
+            .add_attribute("cancelled_by", &self.cancelled_by)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        let attributes = EventAttributes::new(event, Self::EVENT_TYPE)?;
+        Ok(CancelEvent {
+            contract_address: attributes.addr("contract_address")?,
+            token_id: attributes.get("token_id")?,
+            seller: attributes.addr("seller")?,
+            cancelled_by: attributes.addr("cancelled_by")?,
+        })
+    }
+}
+
+impl MarketplaceEvent for OfferEvent {
+    const EVENT_TYPE: &'static str = "offer";
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::EVENT_TYPE)
+            .add_attribute("contract_address", &self.contract_address)
+            .add_attribute("token_id", &self.token_id)
+            .add_attribute("offerer", &self.offerer);
+        let event = add_price(event, &self.price);
+        add_optional_time(event, "start_time", &self.start_time)
+            .add_attribute("end_time", time_to_string(&self.end_time))
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        let attributes = EventAttributes::new(event, Self::EVENT_TYPE)?;
+        Ok(OfferEvent {
+            contract_address: attributes.addr("contract_address")?,
+            token_id: attributes.get("token_id")?,
+            offerer: attributes.addr("offerer")?,
+            price: attributes.price()?,
+            start_time: attributes.optional_time("start_time")?,
+            end_time: parse_time(&attributes.get("end_time")?)?,
+        })
+    }
+}
+
+impl MarketplaceEvent for AcceptOfferEvent {
+    const EVENT_TYPE: &'static str = "accept-offer";
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::EVENT_TYPE)
+            .add_attribute("contract_address", &self.contract_address)
+            .add_attribute("token_id", &self.token_id)
+            .add_attribute("seller", &self.seller)
+            .add_attribute("offerer", &self.offerer);
+        let event = add_price(event, &self.price);
+        add_split(event, &self.split)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        let attributes = EventAttributes::new(event, Self::EVENT_TYPE)?;
+        Ok(AcceptOfferEvent {
+            contract_address: attributes.addr("contract_address")?,
+            token_id: attributes.get("token_id")?,
+            seller: attributes.addr("seller")?,
+            offerer: attributes.addr("offerer")?,
+            price: attributes.price()?,
+            split: attributes.split()?,
+        })
+    }
+}
+
+impl MarketplaceEvent for CancelOfferEvent {
+    const EVENT_TYPE: &'static str = "cancel-offer";
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::EVENT_TYPE)
+            .add_attribute("contract_address", &self.contract_address)
+            .add_attribute("token_id", &self.token_id)
+            .add_attribute("offerer", &self.offerer)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        let attributes = EventAttributes::new(event, Self::EVENT_TYPE)?;
+        Ok(CancelOfferEvent {
+            contract_address: attributes.addr("contract_address")?,
+            token_id: attributes.get("token_id")?,
+            offerer: attributes.addr("offerer")?,
+        })
+    }
+}
+
+impl MarketplaceEvent for IncrementCounterEvent {
+    const EVENT_TYPE: &'static str = "increment-counter";
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::EVENT_TYPE)
+            .add_attribute("user", &self.user)
+            .add_attribute("counter", self.counter.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        let attributes = EventAttributes::new(event, Self::EVENT_TYPE)?;
+        Ok(IncrementCounterEvent {
+            user: attributes.addr("user")?,
+            counter: attributes
+                .get("counter")?
+                .parse()
+                .map_err(|e: std::num::ParseIntError| StdError::parse_err("u64", e.to_string()))?,
+        })
+    }
+}
+
+fn add_price(event: Event, price: &Coin) -> Event {
+    event
+        .add_attribute("price_amount", price.amount)
+        .add_attribute("price_denom", &price.denom)
+}
+
+fn add_split(event: Event, split: &SaleSplit) -> Event {
+    let mut event = event;
+    match &split.royalty {
+        Some((recipient, amount)) => {
+            event = event
+                .add_attribute("royalty_recipient", recipient)
+                .add_attribute("royalty_amount", amount.to_string());
+        }
+        None => event = event.add_attribute("royalty_amount", Uint128::zero()),
+    }
+    event = event.add_attribute("protocol_fee", split.protocol_fee);
+    match &split.referral {
+        Some((referrer, amount)) => {
+            event = event
+                .add_attribute("referrer", referrer)
+                .add_attribute("referral_fee", amount.to_string());
+        }
+        None => event = event.add_attribute("referral_fee", Uint128::zero()),
+    }
+    event.add_attribute("seller_amount", split.seller_amount)
+}
+
+fn add_optional_time(event: Event, key: &str, time: &Option<Expiration>) -> Event {
+    match time {
+        Some(time) => event.add_attribute(key, time_to_string(time)),
+        None => event,
+    }
+}
+
+fn time_to_string(time: &Expiration) -> String {
+    match time {
+        Expiration::AtHeight(height) => format!("height:{}", height),
+        Expiration::AtTime(time) => format!("time:{}", time.nanos()),
+        Expiration::Never {} => "never".to_string(),
+    }
+}
+
+fn parse_time(value: &str) -> StdResult<Expiration> {
+    let parse_u64 = |v: &str| {
+        v.parse::<u64>()
+            .map_err(|e| StdError::parse_err("Expiration", e.to_string()))
+    };
+    match value.split_once(':') {
+        Some(("height", height)) => Ok(Expiration::AtHeight(parse_u64(height)?)),
+        Some(("time", nanos)) => Ok(Expiration::AtTime(Timestamp::from_nanos(parse_u64(nanos)?))),
+        _ if value == "never" => Ok(Expiration::Never {}),
+        _ => Err(StdError::parse_err("Expiration", value)),
+    }
+}
+
+// helper to read the attributes of an event emitted by the marketplace
+struct EventAttributes<'a>(&'a Event);
+
+impl<'a> EventAttributes<'a> {
+    fn new(event: &'a Event, event_type: &str) -> StdResult<Self> {
+        // the event type is prefixed when the event is read from a transaction
+        let ty = event.ty.strip_prefix("wasm-").unwrap_or(&event.ty);
+        if ty != event_type {
+            return Err(StdError::parse_err(
+                event_type,
+                format!("unexpected event type {}", event.ty),
+            ));
+        }
+        Ok(EventAttributes(event))
+    }
+
+    fn optional(&self, key: &str) -> Option<String> {
+        self.0
+            .attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.clone())
+    }
+
+    fn get(&self, key: &str) -> StdResult<String> {
+        self.optional(key)
+            .ok_or_else(|| StdError::not_found(format!("attribute {}", key)))
+    }
+
+    fn addr(&self, key: &str) -> StdResult<Addr> {
+        Ok(Addr::unchecked(self.get(key)?))
+    }
+
+    fn amount(&self, key: &str) -> StdResult<Uint128> {
+        Uint128::from_str(&self.get(key)?)
+    }
+
+    fn price(&self) -> StdResult<Coin> {
+        Ok(Coin {
+            denom: self.get("price_denom")?,
+            amount: self.amount("price_amount")?,
+        })
+    }
+
+    fn optional_time(&self, key: &str) -> StdResult<Option<Expiration>> {
+        self.optional(key)
+            .map(|value| parse_time(&value))
+            .transpose()
+    }
+
+    fn split(&self) -> StdResult<SaleSplit> {
+        let royalty = match self.optional("royalty_recipient") {
+            Some(recipient) => Some((Addr::unchecked(recipient), self.amount("royalty_amount")?)),
+            None => None,
+        };
+        let referral = match self.optional("referrer") {
+            Some(referrer) => Some((Addr::unchecked(referrer), self.amount("referral_fee")?)),
+            None => None,
+        };
+        Ok(SaleSplit {
+            royalty,
+            protocol_fee: self.amount("protocol_fee")?,
+            referral,
+            seller_amount: self.amount("seller_amount")?,
+        })
+    }
+}
@@ -1,23 +1,231 @@
+use crate::events::{
+    AcceptOfferEvent, BuyEvent, CancelEvent, CancelOfferEvent, IncrementCounterEvent, ListEvent,
+    MarketplaceEvent, OfferEvent, SaleSplit,
+};
+use crate::hooks::{LISTING_HOOK_REPLY_ID, SALE_HOOK_REPLY_ID};
+use crate::msg::{ListingHookAction, ListingHookMsg, SaleHookMsg};
 use crate::order_state::{
     consideration_item, offer_item, order_key, Asset, ItemType, OrderComponents, OrderType,
     PaymentAsset, CW20, NFT,
 };
 use crate::{
-    state::{listing_key, AuctionConfig, Listing, MarketplaceContract},
+    state::{
+        listing_key, ApprovalType, AuctionConfig, Listing, MarketplaceContract, RoyaltyPolicy,
+        MAX_BASIS_POINTS,
+    },
     ContractError,
 };
+use bidding_token::state::SUPPORTED_NATIVE;
 use cosmwasm_std::{
-    to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, QueryRequest, Response,
-    StdResult, Uint128, WasmMsg, WasmQuery,
+    attr, to_binary, Addr, Attribute, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
-use cw20::{AllowanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw20::{AllowanceResponse, BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
 use cw2981_royalties::{
-    msg::RoyaltiesInfoResponse, ExecuteMsg as Cw2981ExecuteMsg, QueryMsg as Cw2981QueryMsg,
+    msg::{CheckRoyaltiesResponse, RoyaltiesInfoResponse},
+    ExecuteMsg as Cw2981ExecuteMsg, MinterResponse, QueryMsg as Cw2981QueryMsg,
 };
 use cw721::{Cw721QueryMsg, Expiration as Cw721Expiration};
 
+// the maximum number of operators read from the nft contract in one query
+const OPERATOR_PAGE_LIMIT: u32 = 100;
+
+// check that the nft exists and is owned by the owner
+pub(crate) fn check_nft_owner(
+    deps: &DepsMut,
+    contract_address: &Addr,
+    token_id: &str,
+    owner: &Addr,
+) -> Result<(), ContractError> {
+    let owner_response: StdResult<cw721::OwnerOfResponse> = deps.querier.query_wasm_smart(
+        contract_address,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: token_id.to_string(),
+            include_expired: Some(false),
+        },
+    );
+    match owner_response {
+        Ok(response) if response.owner == *owner => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+// find the unexpired approval of the marketplace for the token, the token approval is preferred
+pub(crate) fn query_marketplace_approval(
+    deps: &DepsMut,
+    env: &Env,
+    contract_address: &Addr,
+    token_id: &str,
+    owner: &Addr,
+) -> Result<(ApprovalType, Cw721Expiration), ContractError> {
+    let marketplace = env.contract.address.to_string();
+
+    let approval_response: StdResult<cw721::ApprovalResponse> = deps.querier.query_wasm_smart(
+        contract_address,
+        &Cw721QueryMsg::Approval {
+            token_id: token_id.to_string(),
+            spender: marketplace.clone(),
+            include_expired: Some(false),
+        },
+    );
+    if let Ok(response) = approval_response {
+        return Ok((ApprovalType::Token, response.approval.expires));
+    }
+
+    // cw721 0.16 has no query for a single operator, so the operators of the owner are paged
+    let mut start_after = None;
+    loop {
+        let response: cw721::OperatorsResponse = deps
+            .querier
+            .query_wasm_smart(
+                contract_address,
+                &Cw721QueryMsg::AllOperators {
+                    owner: owner.to_string(),
+                    include_expired: Some(false),
+                    start_after: start_after.take(),
+                    limit: Some(OPERATOR_PAGE_LIMIT),
+                },
+            )
+            .map_err(|_| ContractError::MissingApproval {})?;
+
+        if let Some(operator) = response
+            .operators
+            .iter()
+            .find(|operator| operator.spender == marketplace)
+        {
+            return Ok((ApprovalType::Operator, operator.expires));
+        }
+        if response.operators.len() < OPERATOR_PAGE_LIMIT as usize {
+            return Err(ContractError::MissingApproval {});
+        }
+        start_after = response
+            .operators
+            .last()
+            .map(|operator| operator.spender.clone());
+    }
+}
+
+// set the end_time of the auction to the approval expiry when it would end later
+fn cap_end_time(
+    auction_config: AuctionConfig,
+    expires: Cw721Expiration,
+) -> Result<AuctionConfig, ContractError> {
+    if let Cw721Expiration::Never {} = expires {
+        return Ok(auction_config);
+    }
+    match auction_config {
+        AuctionConfig::FixedPrice {
+            price,
+            start_time,
+            end_time,
+        } => {
+            let end_time = match end_time {
+                None => expires,
+                Some(end_time) => match end_time.partial_cmp(&expires) {
+                    Some(std::cmp::Ordering::Greater) => expires,
+                    Some(_) => end_time,
+                    // a height cannot be compared with a time
+                    None => {
+                        return Err(ContractError::ApprovalExpirationMismatch { expires, end_time })
+                    }
+                },
+            };
+            Ok(AuctionConfig::FixedPrice {
+                price,
+                start_time,
+                end_time: Some(end_time),
+            })
+        }
+    }
+}
+
+// check that the price is paid with the funds, returns the surplus and the other coins to refund
+pub(crate) fn refund_surplus(funds: &[Coin], price: &Coin) -> Result<Vec<Coin>, ContractError> {
+    let paid = funds
+        .iter()
+        .find(|coin| coin.denom == price.denom)
+        .map(|coin| coin.amount)
+        .unwrap_or_default();
+    if paid < price.amount {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    Ok(funds
+        .iter()
+        .map(|coin| {
+            if coin.denom == price.denom {
+                Coin {
+                    denom: coin.denom.clone(),
+                    amount: coin.amount - price.amount,
+                }
+            } else {
+                coin.clone()
+            }
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect())
+}
+
+// the royalty and the fees are paid out of the price, so together they cannot exceed 100%
+fn check_total_bps(
+    max_royalty_bps: u64,
+    protocol_fee_bps: u64,
+    referral_fee_bps: u64,
+) -> Result<(), ContractError> {
+    let bps = max_royalty_bps
+        .saturating_add(protocol_fee_bps)
+        .saturating_add(referral_fee_bps);
+    if bps > MAX_BASIS_POINTS {
+        return Err(ContractError::InvalidBasisPoints { bps });
+    }
+    Ok(())
+}
+
+impl From<&SalePayment> for SaleSplit {
+    fn from(payment: &SalePayment) -> Self {
+        SaleSplit {
+            royalty: payment.royalty.clone(),
+            protocol_fee: payment.protocol_fee,
+            referral: payment.referral.clone(),
+            seller_amount: payment.seller_amount,
+        }
+    }
+}
+
+// the maximum number of offers cancelled in one message
+const MAX_CANCEL_OFFERS: usize = 50;
+
+// the split of the funds of a sale between the parties
+pub struct SalePayment {
+    pub messages: Vec<CosmosMsg>,
+    pub royalty: Option<(Addr, Uint128)>,
+    pub protocol_fee: Uint128,
+    pub referral: Option<(Addr, Uint128)>,
+    pub seller_amount: Uint128,
+}
+
+impl SalePayment {
+    pub fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![];
+        if let Some((creator, royalty_amount)) = &self.royalty {
+            attributes.push(attr("royalty_recipient", creator));
+            attributes.push(attr("royalty_amount", royalty_amount.to_string()));
+        }
+        attributes.push(attr("protocol_fee", self.protocol_fee.to_string()));
+        if let Some((referrer, referral_fee)) = &self.referral {
+            attributes.push(attr("referrer", referrer));
+            attributes.push(attr("referral_fee", referral_fee.to_string()));
+        }
+        attributes.push(attr("seller_amount", self.seller_amount.to_string()));
+        attributes
+    }
+}
+
 impl MarketplaceContract<'static> {
-    pub fn validate_auction_config(&self, auction_config: &AuctionConfig) -> bool {
+    pub fn validate_auction_config(
+        &self,
+        auction_config: &AuctionConfig,
+    ) -> Result<(), ContractError> {
         match auction_config {
             AuctionConfig::FixedPrice {
                 price,
@@ -27,16 +235,15 @@ impl MarketplaceContract<'static> {
                 if price.amount.is_zero() {
                     // since price is Uint128, it cannot be negative, we only
                     // need to check if it's zero
-                    return false;
+                    return Err(ContractError::InvalidAuctionConfig {});
                 }
                 // if start_time or end_time is not set, we don't need to check
-                if start_time.is_some()
-                    && end_time.is_some()
-                    && start_time.unwrap() >= end_time.unwrap()
-                {
-                    return false;
+                if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
+                    if start_time >= end_time {
+                        return Err(ContractError::InvalidStartTime {});
+                    }
                 }
-                true
+                Ok(())
             }
         }
     }
@@ -50,58 +257,21 @@ impl MarketplaceContract<'static> {
         token_id: String,
         auction_config: AuctionConfig,
     ) -> Result<Response, ContractError> {
+        // trading is stopped while the marketplace is paused
+        self.check_not_paused(deps.storage)?;
+
         // check if user is the owner of the token
-        let query_owner_msg = Cw721QueryMsg::OwnerOf {
-            token_id: token_id.clone(),
-            include_expired: Some(false),
-        };
-        let owner_response: StdResult<cw721::OwnerOfResponse> =
-            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr: contract_address.to_string(),
-                msg: to_binary(&query_owner_msg)?,
-            }));
-        match owner_response {
-            Ok(owner) => {
-                if owner.owner != info.sender {
-                    return Err(ContractError::Unauthorized {});
-                }
-            }
-            Err(_) => {
-                return Err(ContractError::Unauthorized {});
-            }
-        }
+        check_nft_owner(&deps, &contract_address, &token_id, &info.sender)?;
 
-        // check that user approves this contract to manage this token
-        // for now, we require never expired approval
-        let query_approval_msg = Cw721QueryMsg::Approval {
-            token_id: token_id.clone(),
-            spender: env.contract.address.to_string(),
-            include_expired: Some(true),
-        };
-        let approval_response: StdResult<cw721::ApprovalResponse> =
-            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr: contract_address.to_string(),
-                msg: to_binary(&query_approval_msg)?,
-            }));
-
-        // check if approval is never expired
-        match approval_response {
-            Ok(approval) => match approval.approval.expires {
-                Cw721Expiration::Never {} => {}
-                _ => return Err(ContractError::Unauthorized {}),
-            },
-            Err(_) => {
-                return Err(ContractError::CustomError {
-                    val: "Require never expired approval".to_string(),
-                });
-            }
-        }
+        // check that user approves this contract to manage this token,
+        // either for this token only or as an operator of all tokens
+        let (approval_type, expires) =
+            query_marketplace_approval(&deps, &env, &contract_address, &token_id, &info.sender)?;
 
-        if !self.validate_auction_config(&auction_config) {
-            return Err(ContractError::CustomError {
-                val: "Invalid auction config".to_string(),
-            });
-        }
+        // the listing cannot outlive the approval, so its end_time is capped at the expiry
+        let auction_config = cap_end_time(auction_config, expires)?;
+
+        self.validate_auction_config(&auction_config)?;
 
         // add a nft to listings
         let listing = Listing {
@@ -110,9 +280,17 @@ impl MarketplaceContract<'static> {
             auction_config,
             seller: info.sender,
             buyer: None,
+            approval_type,
         };
         let listing_key = listing_key(&contract_address, &token_id);
 
+        // the aggregates of the collection are updated with the new listing
+        let old_listing = self.listings.may_load(deps.storage, listing_key.clone())?;
+        if let Some(old_listing) = &old_listing {
+            self.remove_listing_stats(deps.storage, old_listing)?;
+        }
+        self.add_listing_stats(deps.storage, &listing)?;
+
         // we will override the listing if it already exists, so that we can update the auction config
         let new_listing = self.listings.update(
             deps.storage,
@@ -120,21 +298,40 @@ impl MarketplaceContract<'static> {
             |_old| -> Result<Listing, ContractError> { Ok(listing) },
         )?;
 
-        // println!("Listing: {:?}", _listing);
-        let auction_config_str = serde_json::to_string(&new_listing.auction_config);
-        match auction_config_str {
-            Ok(auction_config_str) => Ok(Response::new()
-                .add_attribute("method", "list_nft")
-                .add_attribute("contract_address", new_listing.contract_address)
-                .add_attribute("token_id", new_listing.token_id)
-                .add_attribute("auction_config", auction_config_str)
-                .add_attribute("seller", new_listing.seller.to_string())),
-            Err(_) => Err(ContractError::CustomError {
-                val: ("Auction Config Error".to_string()),
-            }),
-        }
+        let action = match old_listing {
+            Some(_) => ListingHookAction::Update,
+            None => ListingHookAction::Create,
+        };
+        let hook_msgs = self.listing_hook_msgs(deps.storage, action, &new_listing)?;
+
+        let event = match &new_listing.auction_config {
+            AuctionConfig::FixedPrice {
+                price,
+                start_time,
+                end_time,
+            } => ListEvent {
+                contract_address: new_listing.contract_address.clone(),
+                token_id: new_listing.token_id.clone(),
+                seller: new_listing.seller.clone(),
+                price: price.clone(),
+                start_time: *start_time,
+                end_time: *end_time,
+            },
+        };
+
+        let auction_config_str = serde_json::to_string(&new_listing.auction_config)
+            .map_err(|e| StdError::serialize_err("AuctionConfig", e))?;
+        Ok(Response::new()
+            .add_submessages(hook_msgs)
+            .add_event(event.to_event())
+            .add_attribute("method", "list_nft")
+            .add_attribute("contract_address", new_listing.contract_address)
+            .add_attribute("token_id", new_listing.token_id)
+            .add_attribute("auction_config", auction_config_str)
+            .add_attribute("seller", new_listing.seller.to_string()))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_buy(
         self,
         deps: DepsMut,
@@ -142,36 +339,51 @@ impl MarketplaceContract<'static> {
         info: MessageInfo,
         contract_address: Addr,
         token_id: String,
+        max_price: Option<Uint128>,
+        referrer: Option<Addr>,
+        pay_with_vaura: bool,
     ) -> Result<Response, ContractError> {
+        // trading is stopped while the marketplace is paused
+        self.check_not_paused(deps.storage)?;
+
         // get the listing
         let listing_key = listing_key(&contract_address, &token_id);
         let mut listing = self.listings.load(deps.storage, listing_key.clone())?;
 
         // check if buyer is the same as seller
         if info.sender == listing.seller {
-            return Err(ContractError::CustomError {
-                val: ("Owner cannot buy".to_string()),
-            });
+            return Err(ContractError::CannotBuyOwnListing {});
         }
 
         listing.buyer = Some(info.sender.clone());
 
         // remove the listing
         self.listings.remove(deps.storage, listing_key)?;
+        self.remove_listing_stats(deps.storage, &listing)?;
 
         match &listing.auction_config {
-            AuctionConfig::FixedPrice { .. } => {
-                self.process_buy_fixed_price(deps, env, info, &listing)
-            }
+            AuctionConfig::FixedPrice { .. } => self.process_buy_fixed_price(
+                deps,
+                env,
+                info,
+                &listing,
+                max_price,
+                referrer,
+                pay_with_vaura,
+            ),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn process_buy_fixed_price(
         self,
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
         listing: &Listing,
+        max_price: Option<Uint128>,
+        referrer: Option<Addr>,
+        pay_with_vaura: bool,
     ) -> Result<Response, ContractError> {
         match &listing.auction_config {
             AuctionConfig::FixedPrice {
@@ -180,63 +392,176 @@ impl MarketplaceContract<'static> {
                 end_time,
             } => {
                 // check if current block is after start_time
-                if start_time.is_some() && !start_time.unwrap().is_expired(&env.block) {
-                    return Err(ContractError::CustomError {
-                        val: ("Auction not started".to_string()),
-                    });
+                if let Some(start_time) = start_time {
+                    if !start_time.is_expired(&env.block) {
+                        return Err(ContractError::AuctionNotStarted {
+                            start_time: *start_time,
+                        });
+                    }
                 }
 
-                if end_time.is_some() && end_time.unwrap().is_expired(&env.block) {
-                    return Err(ContractError::CustomError {
-                        val: format!("Auction ended: {} {}", end_time.unwrap(), env.block.time),
-                    });
+                if let Some(end_time) = end_time {
+                    if end_time.is_expired(&env.block) {
+                        return Err(ContractError::AuctionEnded {
+                            end_time: *end_time,
+                            block_time: env.block.time,
+                        });
+                    }
                 }
 
-                // check if enough funds
-                if info.funds.is_empty() || info.funds[0] != *price {
-                    return Err(ContractError::InsufficientFunds {});
+                // the buyer can limit the price they are willing to pay
+                if let Some(max_price) = max_price {
+                    if price.amount > max_price {
+                        return Err(ContractError::PriceExceedsMaxPrice {
+                            price: price.amount,
+                            max_price,
+                        });
+                    }
                 }
 
+                // with vaura, all the funds sent with the message are refunded
+                let (vaura_msgs, refund_coins): (Vec<CosmosMsg>, Vec<Coin>) = if pay_with_vaura {
+                    let vaura_msgs = self.vaura_to_native_msgs(&deps, &env, &info.sender, price)?;
+                    (vaura_msgs, info.funds.clone())
+                } else {
+                    (vec![], refund_surplus(&info.funds, price)?)
+                };
+
                 // message to transfer nft to buyer
                 let transfer_nft_msg = WasmMsg::Execute {
                     contract_addr: listing.contract_address.to_string(),
                     msg: to_binary(&Cw2981ExecuteMsg::TransferNft {
-                        recipient: listing.buyer.clone().unwrap().into_string(),
+                        recipient: info.sender.to_string(),
                         token_id: listing.token_id.clone(),
                     })?,
                     funds: vec![],
                 };
-                let mut res = Response::new().add_message(transfer_nft_msg);
+                let mut res = Response::new()
+                    .add_message(transfer_nft_msg)
+                    .add_messages(vaura_msgs);
 
                 let payment = PaymentAsset::Native {
                     denom: price.denom.clone(),
                     amount: price.amount.into(),
                 };
 
-                let payment_messages = self.payment_with_royalty(
+                self.add_volume(
+                    deps.storage,
+                    &env.block,
+                    &listing.contract_address,
+                    &price.denom,
+                    price.amount,
+                )?;
+
+                let sale_payment = self.payment_with_royalty(
                     &deps,
                     &listing.contract_address,
                     &listing.token_id,
                     payment,
                     &info.sender,
                     &listing.seller,
-                );
+                    referrer.as_ref(),
+                )?;
+
+                res = res
+                    .add_messages(sale_payment.messages.clone())
+                    .add_attributes(sale_payment.attributes());
 
-                for payment_message in payment_messages {
-                    res = res.add_message(payment_message);
+                if !refund_coins.is_empty() {
+                    res = res.add_message(BankMsg::Send {
+                        to_address: info.sender.to_string(),
+                        amount: refund_coins,
+                    });
                 }
 
+                let hook_msgs = self.sale_hook_msgs(
+                    deps.storage,
+                    SaleHookMsg {
+                        contract_address: listing.contract_address.to_string(),
+                        token_id: listing.token_id.clone(),
+                        price: price.clone(),
+                        seller: listing.seller.to_string(),
+                        buyer: info.sender.to_string(),
+                    },
+                )?;
+
+                let event = BuyEvent {
+                    contract_address: listing.contract_address.clone(),
+                    token_id: listing.token_id.clone(),
+                    seller: listing.seller.clone(),
+                    buyer: info.sender.clone(),
+                    price: price.clone(),
+                    split: SaleSplit::from(&sale_payment),
+                };
+
                 res = res
+                    .add_submessages(hook_msgs)
+                    .add_event(event.to_event())
                     .add_attribute("method", "buy")
                     .add_attribute("contract_address", listing.contract_address.to_string())
                     .add_attribute("token_id", listing.token_id.to_string())
-                    .add_attribute("buyer", info.sender);
+                    .add_attribute("buyer", info.sender)
+                    .add_attribute("pay_with_vaura", pay_with_vaura.to_string());
 
                 Ok(res)
             }
         }
     }
 
+    // pull vaura from the owner and burn it, so the marketplace receives the native coins of the price
+    fn vaura_to_native_msgs(
+        &self,
+        deps: &DepsMut,
+        env: &Env,
+        owner: &Addr,
+        price: &Coin,
+    ) -> Result<Vec<CosmosMsg>, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        if config.vaura_address == Addr::unchecked("aura0") {
+            return Err(ContractError::VauraAddressNotSet {});
+        }
+
+        // vaura can only pay for the native denom it wraps
+        let supported_native =
+            SUPPORTED_NATIVE.query(&deps.querier, config.vaura_address.clone())?;
+        if supported_native.denom != price.denom {
+            return Err(ContractError::VauraDenomNotSupported {
+                denom: price.denom.clone(),
+            });
+        }
+
+        let balance_response: BalanceResponse = deps.querier.query_wasm_smart(
+            &config.vaura_address,
+            &Cw20QueryMsg::Balance {
+                address: owner.to_string(),
+            },
+        )?;
+        if balance_response.balance < price.amount {
+            return Err(ContractError::InsufficientBalance {});
+        }
+
+        Ok(vec![
+            WasmMsg::Execute {
+                contract_addr: config.vaura_address.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: owner.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount: price.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+            WasmMsg::Execute {
+                contract_addr: config.vaura_address.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: price.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        ])
+    }
+
     pub fn execute_cancel(
         self,
         deps: DepsMut,
@@ -256,8 +581,21 @@ impl MarketplaceContract<'static> {
 
         // we will remove the cancelled listing
         self.listings.remove(deps.storage, listing_key)?;
+        self.remove_listing_stats(deps.storage, &listing)?;
+
+        let hook_msgs =
+            self.listing_hook_msgs(deps.storage, ListingHookAction::Delete, &listing)?;
+
+        let event = CancelEvent {
+            contract_address: listing.contract_address.clone(),
+            token_id: listing.token_id.clone(),
+            seller: listing.seller.clone(),
+            cancelled_by: info.sender.clone(),
+        };
 
         Ok(Response::new()
+            .add_submessages(hook_msgs)
+            .add_event(event.to_event())
             .add_attribute("method", "cancel")
             .add_attribute("contract_address", contract_address)
             .add_attribute("token_id", token_id)
@@ -267,6 +605,7 @@ impl MarketplaceContract<'static> {
     // function to add new offer nft using ordering style
     // the 'offer' of offer_nft will contain the information of price
     // the 'consideration' of offer_nft will contain the information of nft
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_offer_nft(
         self,
         deps: DepsMut,
@@ -274,8 +613,12 @@ impl MarketplaceContract<'static> {
         info: MessageInfo,
         nft: NFT,
         funds_amount: u128,
+        start_time: Option<Cw721Expiration>,
         end_time: Cw721Expiration,
     ) -> Result<Response, ContractError> {
+        // trading is stopped while the marketplace is paused
+        self.check_not_paused(deps.storage)?;
+
         // load config
         let config = self.config.load(deps.storage)?;
         // check ig the vaura_address is set (the default value is equal to "aura0")
@@ -287,26 +630,27 @@ impl MarketplaceContract<'static> {
         if end_time.is_expired(&env.block) {
             return Err(ContractError::InvalidEndTime {});
         }
+
+        // the offer must start before it ends
+        if let Some(start_time) = start_time {
+            if start_time >= end_time {
+                return Err(ContractError::InvalidStartTime {});
+            }
+        }
         // ***********
         // OFFERING FUNDS
         // ***********
-        // load config
-        let config = self.config.load(deps.storage)?;
-
         let token_address = config.vaura_address;
         let amount = funds_amount;
 
         // check that the allowance of the cw20 offer token is enough
-        let allowance_response: AllowanceResponse = deps
-            .querier
-            .query_wasm_smart(
-                &token_address,
-                &Cw20QueryMsg::Allowance {
-                    owner: info.sender.to_string(),
-                    spender: env.contract.address.to_string(),
-                },
-            )
-            .unwrap();
+        let allowance_response: AllowanceResponse = deps.querier.query_wasm_smart(
+            &token_address,
+            &Cw20QueryMsg::Allowance {
+                owner: info.sender.to_string(),
+                spender: env.contract.address.to_string(),
+            },
+        )?;
 
         // check if the allowance is greater or equal the offer amount
         if allowance_response.allowance < Uint128::from(amount) {
@@ -314,6 +658,16 @@ impl MarketplaceContract<'static> {
         }
 
         let contract_address = nft.contract_address;
+
+        // check if the offer amount reaches the minimum offer amount of the collection
+        let min_amount = self
+            .min_offer_amounts
+            .may_load(deps.storage, contract_address.clone())?
+            .unwrap_or_default();
+        if amount < min_amount {
+            return Err(ContractError::OfferAmountTooLow { amount, min_amount });
+        }
+
         let token_id = nft.token_id;
         if let Some(token_id) = token_id {
             // query the owner of the nft to check if the nft exist
@@ -328,21 +682,29 @@ impl MarketplaceContract<'static> {
             match owner_response {
                 Ok(owner) => {
                     if owner.owner == info.sender {
-                        return Err(ContractError::CustomError {
-                            val: ("Cannot offer owned nft".to_string()),
-                        });
+                        return Err(ContractError::CannotOfferOwnNFT {});
                     }
                 }
                 Err(_) => {
-                    return Err(ContractError::CustomError {
-                        val: ("Nft not exist".to_string()),
-                    });
+                    return Err(ContractError::NftNotFound {});
                 }
             }
 
             // generate order key for order components based on user address, contract address and token id
             let order_key = order_key(&info.sender, &contract_address, &token_id);
 
+            let event = OfferEvent {
+                contract_address: contract_address.clone(),
+                token_id: token_id.clone(),
+                offerer: info.sender.clone(),
+                price: Coin {
+                    denom: token_address.to_string(),
+                    amount: Uint128::from(amount),
+                },
+                start_time,
+                end_time,
+            };
+
             // the offer item will contain the infomation of cw20 token
             let offer_item = offer_item(
                 &ItemType::CW20,
@@ -366,6 +728,9 @@ impl MarketplaceContract<'static> {
                 &info.sender,
             );
 
+            // the order is valid until the offerer increments their counter
+            let counter = self.counter(deps.storage, &info.sender)?;
+
             // generate order components
             let order_offer = OrderComponents {
                 order_type: OrderType::OFFER, // The type of offer must be OFFER
@@ -373,8 +738,9 @@ impl MarketplaceContract<'static> {
                 offerer: info.sender,
                 offer: [offer_item].to_vec(),
                 consideration: [consideration_item].to_vec(),
-                start_time: None,
+                start_time,
                 end_time: Some(end_time),
+                counter,
             };
 
             // we will override the order if it already exists
@@ -384,27 +750,29 @@ impl MarketplaceContract<'static> {
                 |_old| -> Result<OrderComponents, ContractError> { Ok(order_offer) },
             )?;
 
-            let offer_str = serde_json::to_string(&new_offer.offer);
-            let consideration_str = serde_json::to_string(&new_offer.consideration);
+            let offer_str = serde_json::to_string(&new_offer.offer)
+                .map_err(|e| StdError::serialize_err("OfferItem", e))?;
+            let consideration_str = serde_json::to_string(&new_offer.consideration)
+                .map_err(|e| StdError::serialize_err("ConsiderationItem", e))?;
 
             // return success
             Ok(Response::new()
+                .add_event(event.to_event())
                 .add_attribute("method", "create_offer")
                 .add_attribute("order_type", "OFFER")
                 .add_attribute("offerer", new_offer.offerer)
-                .add_attribute("offer", offer_str.unwrap())
-                .add_attribute("consideration", consideration_str.unwrap())
-                .add_attribute("end_time", new_offer.end_time.unwrap().to_string()))
+                .add_attribute("offer", offer_str)
+                .add_attribute("consideration", consideration_str)
+                .add_attribute("end_time", end_time.to_string()))
         } else {
             // if the token_id is not exist, then this order is offer for a collection of nft
             // we will handle this in the next version => return error for now
-            Err(ContractError::CustomError {
-                val: ("Collection offer is not supported".to_string()),
-            })
+            Err(ContractError::CollectionOfferNotSupported {})
         }
     }
 
     // function to accept offer nft using ordering style
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_accept_nft_offer(
         self,
         deps: DepsMut,
@@ -413,7 +781,11 @@ impl MarketplaceContract<'static> {
         offerer: Addr,
         nft: NFT,
         funds_amount: u128,
+        referrer: Option<Addr>,
     ) -> Result<Response, ContractError> {
+        // trading is stopped while the marketplace is paused
+        self.check_not_paused(deps.storage)?;
+
         let contract_address = nft.contract_address;
         let token_id = nft.token_id;
         // if the token_id is exist, then this order is offer for a specific nft
@@ -424,11 +796,23 @@ impl MarketplaceContract<'static> {
             // get order components
             let order_components = self.offers.load(deps.storage, order_key.clone())?;
 
+            // if the offerer incremented their counter after the offer, then it is cancelled
+            if !self.is_order_valid(deps.storage, &order_components)? {
+                return Err(ContractError::OfferCancelled {});
+            }
+
             // if the end time of the offer is expired, then return error
-            if order_components.end_time.unwrap().is_expired(&env.block) {
-                return Err(ContractError::CustomError {
-                    val: ("Offer is expired".to_string()),
-                });
+            if let Some(end_time) = order_components.end_time {
+                if end_time.is_expired(&env.block) {
+                    return Err(ContractError::OfferExpired { end_time });
+                }
+            }
+
+            // if the offer is not started yet, then return error
+            if let Some(start_time) = order_components.start_time {
+                if !start_time.is_expired(&env.block) {
+                    return Err(ContractError::OfferNotStarted {});
+                }
             }
             match &order_components.consideration[0].item {
                 // match if the consideration item is Nft
@@ -436,17 +820,21 @@ impl MarketplaceContract<'static> {
                     contract_address,
                     token_id,
                 }) => {
+                    let token_id = token_id
+                        .clone()
+                        .ok_or(ContractError::CollectionOfferNotSupported {})?;
+
                     // query the owner of the nft
                     let owner: cw721::OwnerOfResponse = deps
                         .querier
                         .query_wasm_smart(
                             contract_address,
                             &Cw721QueryMsg::OwnerOf {
-                                token_id: token_id.clone().unwrap(),
+                                token_id: token_id.clone(),
                                 include_expired: Some(false),
                             },
                         )
-                        .unwrap();
+                        .map_err(|_| ContractError::NftNotFound {})?;
 
                     // if the nft is not belong to the info.sender, then return error
                     if owner.owner != info.sender {
@@ -462,36 +850,44 @@ impl MarketplaceContract<'static> {
                     let payment_item = PaymentAsset::from(order_components.offer[0].item.clone());
 
                     // execute cw20 transfer msg from offerer to info.sender
-                    match &payment_item {
+                    let sale_payment = match &payment_item {
                         PaymentAsset::Cw20 {
-                            contract_address: _,
+                            contract_address: token_address,
                             amount,
                         } => {
                             if funds_amount != *amount {
-                                return Err(ContractError::CustomError {
-                                    val: ("Insufficient funds".to_string()),
+                                return Err(ContractError::OfferAmountMismatch {
+                                    expected: *amount,
+                                    actual: funds_amount,
                                 });
                             }
-                            let payment_messages = self.payment_with_royalty(
+                            self.add_volume(
+                                deps.storage,
+                                &env.block,
+                                contract_address,
+                                token_address.as_str(),
+                                Uint128::from(*amount),
+                            )?;
+                            let sale_payment = self.payment_with_royalty(
                                 &deps,
                                 contract_address,
-                                token_id.as_ref().unwrap(),
+                                &token_id,
                                 payment_item.clone(),
                                 &offerer,
                                 &info.sender,
-                            );
-
-                            // loop through all payment messages and add item to response to execute
-                            for payment_message in payment_messages {
-                                res = res.add_message(payment_message);
-                            }
+                                referrer.as_ref(),
+                            )?;
+
+                            // add all payment messages to response to execute
+                            res = res
+                                .add_messages(sale_payment.messages.clone())
+                                .add_attributes(sale_payment.attributes());
+                            sale_payment
                         }
                         _ => {
-                            return Err(ContractError::CustomError {
-                                val: ("Invalid Offer funding type".to_string()),
-                            });
+                            return Err(ContractError::OfferTokenTypeInvalid {});
                         }
-                    }
+                    };
 
                     // ***********************
                     // TRANSFER NFT TO OFFERER
@@ -501,7 +897,7 @@ impl MarketplaceContract<'static> {
                         contract_addr: contract_address.clone().to_string(),
                         msg: to_binary(&Cw2981ExecuteMsg::TransferNft {
                             recipient: order_components.offerer.clone().to_string(),
-                            token_id: token_id.clone().unwrap(),
+                            token_id: token_id.clone(),
                         })?,
                         funds: vec![],
                     };
@@ -512,25 +908,74 @@ impl MarketplaceContract<'static> {
                     // After the offer is accepted, we will delete the order
                     self.offers.remove(deps.storage, order_key)?;
 
-                    let listing_key = listing_key(contract_address, &token_id.clone().unwrap());
-                    self.listings.remove(deps.storage, listing_key)?;
+                    // the nft is no longer listed after the offer is accepted
+                    let listing_key = listing_key(contract_address, &token_id);
+                    if let Some(listing) =
+                        self.listings.may_load(deps.storage, listing_key.clone())?
+                    {
+                        self.listings.remove(deps.storage, listing_key)?;
+                        self.remove_listing_stats(deps.storage, &listing)?;
+
+                        let hook_msgs = self.listing_hook_msgs(
+                            deps.storage,
+                            ListingHookAction::Delete,
+                            &listing,
+                        )?;
+                        let event = CancelEvent {
+                            contract_address: listing.contract_address.clone(),
+                            token_id: listing.token_id.clone(),
+                            seller: listing.seller.clone(),
+                            cancelled_by: info.sender.clone(),
+                        };
+                        res = res.add_submessages(hook_msgs).add_event(event.to_event());
+                    }
+
+                    // notify the sale hooks, the price is paid with the offered cw20 token
+                    let (token_address, amount) = match payment_item {
+                        PaymentAsset::Cw20 {
+                            contract_address,
+                            amount,
+                        } => (contract_address.to_string(), amount),
+                        PaymentAsset::Native { denom, amount } => (denom, amount),
+                    };
+                    let price = Coin {
+                        denom: token_address,
+                        amount: Uint128::from(amount),
+                    };
+                    let hook_msgs = self.sale_hook_msgs(
+                        deps.storage,
+                        SaleHookMsg {
+                            contract_address: contract_address.to_string(),
+                            token_id: token_id.clone(),
+                            price: price.clone(),
+                            seller: info.sender.to_string(),
+                            buyer: order_components.offerer.to_string(),
+                        },
+                    )?;
+
+                    let event = AcceptOfferEvent {
+                        contract_address: contract_address.clone(),
+                        token_id: token_id.clone(),
+                        seller: info.sender.clone(),
+                        offerer: order_components.offerer.clone(),
+                        price,
+                        split: SaleSplit::from(&sale_payment),
+                    };
 
                     Ok(res
+                        .add_submessages(hook_msgs)
+                        .add_event(event.to_event())
                         .add_attribute("method", "execute_accept_nft_offer")
                         .add_attribute("owner", owner.owner)
                         .add_attribute("offerer", order_components.offerer)
                         .add_attribute("nft_contract_address", contract_address.to_string())
-                        .add_attribute("token_id", token_id.clone().unwrap()))
+                        .add_attribute("token_id", token_id.clone()))
                 }
                 // if the consideration item is not Nft, then return error
-                _ => Err(ContractError::CustomError {
-                    val: ("Consideration is not NFT".to_string()),
-                }),
+                _ => Err(ContractError::ConsiderationNotNft {}),
             }
         } else {
-            Err(ContractError::CustomError {
-                val: ("Collection offer is not supported".to_string()),
-            })
+            Err(ContractError::CollectionOfferNotSupported {})
         }
     }
 
@@ -542,34 +987,70 @@ impl MarketplaceContract<'static> {
         nfts: Vec<NFT>,
     ) -> Result<Response, ContractError> {
         // if the number of nfts is greater than 50, then return error
-        if nfts.len() > 50 {
-            return Err(ContractError::CustomError {
-                val: ("Number of NFTs is greater than 50".to_string()),
+        if nfts.len() > MAX_CANCEL_OFFERS {
+            return Err(ContractError::TooManyNfts {
+                count: nfts.len(),
+                max: MAX_CANCEL_OFFERS,
             });
         }
 
+        let mut events = vec![];
+
         // loop through all nfts
         for nft in nfts {
             // generate order key based on the sender address, nft.contract_address and nft.token_id
-            let order_key = order_key(&info.sender, &nft.contract_address, &nft.token_id.unwrap());
+            let token_id = nft
+                .token_id
+                .ok_or(ContractError::CollectionOfferNotSupported {})?;
+            let order_key = order_key(&info.sender, &nft.contract_address, &token_id);
 
             // check if the order exists
             if !self.offers.has(deps.storage, order_key.clone()) {
-                return Err(ContractError::CustomError {
-                    val: ("Offer does not exist".to_string()),
-                });
+                return Err(ContractError::OfferNotFound {});
             }
 
             // we will remove the cancelled offer
             self.offers.remove(deps.storage, order_key)?;
+
+            let event = CancelOfferEvent {
+                contract_address: nft.contract_address,
+                token_id,
+                offerer: info.sender.clone(),
+            };
+            events.push(event.to_event());
         }
 
         Ok(Response::new()
+            .add_events(events)
             .add_attribute("method", "cancel_all_offer")
             .add_attribute("user", info.sender.to_string())
             .add_attribute("cancelled_at", env.block.time.to_string()))
     }
 
+    // cancel all offers of the sender at once, the offers created before are no longer valid
+    pub fn execute_increment_counter(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let counter = self.counter(deps.storage, &info.sender)? + 1;
+        self.counters
+            .save(deps.storage, info.sender.clone(), &counter)?;
+
+        let event = IncrementCounterEvent {
+            user: info.sender.clone(),
+            counter,
+        };
+
+        Ok(Response::new()
+            .add_event(event.to_event())
+            .add_attribute("method", "increment_counter")
+            .add_attribute("user", info.sender.to_string())
+            .add_attribute("counter", counter.to_string())
+            .add_attribute("cancelled_at", env.block.time.to_string()))
+    }
+
     pub fn execute_edit_vaura_token(
         &self,
         deps: DepsMut,
@@ -597,7 +1078,242 @@ impl MarketplaceContract<'static> {
             .add_attribute("vaura_token_address", token_address))
     }
 
-    // function to process payment transfer with royalty
+    pub fn execute_add_sale_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        hook: String,
+    ) -> Result<Response, ContractError> {
+        self.check_owner(deps.storage, &info.sender)?;
+        self.sale_hooks
+            .add_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "add_sale_hook")
+            .add_attribute("hook", hook))
+    }
+
+    pub fn execute_remove_sale_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        hook: String,
+    ) -> Result<Response, ContractError> {
+        self.check_owner(deps.storage, &info.sender)?;
+        self.sale_hooks
+            .remove_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_sale_hook")
+            .add_attribute("hook", hook))
+    }
+
+    pub fn execute_add_listing_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        hook: String,
+    ) -> Result<Response, ContractError> {
+        self.check_owner(deps.storage, &info.sender)?;
+        self.listing_hooks
+            .add_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "add_listing_hook")
+            .add_attribute("hook", hook))
+    }
+
+    pub fn execute_remove_listing_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        hook: String,
+    ) -> Result<Response, ContractError> {
+        self.check_owner(deps.storage, &info.sender)?;
+        self.listing_hooks
+            .remove_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_listing_hook")
+            .add_attribute("hook", hook))
+    }
+
+    // check if the sender is the owner
+    fn check_owner(&self, storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+        if self.config.load(storage)?.owner != *sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
+
+    // check if trading is allowed
+    pub(crate) fn check_not_paused(&self, storage: &dyn Storage) -> Result<(), ContractError> {
+        if self.paused.may_load(storage)?.unwrap_or_default() {
+            return Err(ContractError::TradingPaused {});
+        }
+        Ok(())
+    }
+
+    pub fn set_fees(
+        &self,
+        storage: &mut dyn Storage,
+        protocol_fee_bps: u64,
+        referral_fee_bps: u64,
+    ) -> Result<(), ContractError> {
+        let mut conf = self.config.load(storage)?;
+        check_total_bps(conf.max_royalty_bps, protocol_fee_bps, referral_fee_bps)?;
+
+        conf.protocol_fee_bps = protocol_fee_bps;
+        conf.referral_fee_bps = referral_fee_bps;
+        self.config.save(storage, &conf)?;
+        Ok(())
+    }
+
+    // the submessages notifying the sale hooks
+    fn sale_hook_msgs(&self, storage: &dyn Storage, msg: SaleHookMsg) -> StdResult<Vec<SubMsg>> {
+        self.sale_hooks.prepare_hooks(storage, |hook| {
+            let execute = msg.clone().into_wasm_msg(hook.to_string())?;
+            Ok(SubMsg::reply_on_error(execute, SALE_HOOK_REPLY_ID))
+        })
+    }
+
+    // the submessages notifying the listing hooks
+    pub fn listing_hook_msgs(
+        &self,
+        storage: &dyn Storage,
+        action: ListingHookAction,
+        listing: &Listing,
+    ) -> StdResult<Vec<SubMsg>> {
+        let msg = ListingHookMsg {
+            action,
+            listing: listing.clone(),
+        };
+        self.listing_hooks.prepare_hooks(storage, |hook| {
+            let execute = msg.clone().into_wasm_msg(hook.to_string())?;
+            Ok(SubMsg::reply_on_error(execute, LISTING_HOOK_REPLY_ID))
+        })
+    }
+
+    pub fn execute_edit_referral_fee(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        referral_fee_bps: u64,
+    ) -> Result<Response, ContractError> {
+        self.check_owner(deps.storage, &info.sender)?;
+        let protocol_fee_bps = self.config.load(deps.storage)?.protocol_fee_bps;
+        self.set_fees(deps.storage, protocol_fee_bps, referral_fee_bps)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "edit_referral_fee")
+            .add_attribute("referral_fee_bps", referral_fee_bps.to_string()))
+    }
+
+    pub fn execute_edit_protocol_fee(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        protocol_fee_bps: u64,
+    ) -> Result<Response, ContractError> {
+        self.check_owner(deps.storage, &info.sender)?;
+        let referral_fee_bps = self.config.load(deps.storage)?.referral_fee_bps;
+        self.set_fees(deps.storage, protocol_fee_bps, referral_fee_bps)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "edit_protocol_fee")
+            .add_attribute("protocol_fee_bps", protocol_fee_bps.to_string()))
+    }
+
+    pub fn execute_edit_max_royalty(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        max_royalty_bps: u64,
+    ) -> Result<Response, ContractError> {
+        let mut conf = self.config.load(deps.storage)?;
+
+        // check if the sender is the owner
+        if conf.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        check_total_bps(
+            max_royalty_bps,
+            conf.protocol_fee_bps,
+            conf.referral_fee_bps,
+        )?;
+
+        conf.max_royalty_bps = max_royalty_bps;
+        self.config.save(deps.storage, &conf)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "edit_max_royalty")
+            .add_attribute("max_royalty_bps", max_royalty_bps.to_string()))
+    }
+
+    pub fn execute_set_royalty_policy(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        contract_address: Addr,
+        policy: RoyaltyPolicy,
+    ) -> Result<Response, ContractError> {
+        let conf = self.config.load(deps.storage)?;
+
+        // check if the sender is the owner
+        if conf.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        self.royalty_policies
+            .save(deps.storage, contract_address.clone(), &policy)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_royalty_policy")
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("policy", format!("{:?}", policy)))
+    }
+
+    pub fn execute_set_min_offer_amount(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        contract_address: Addr,
+        min_funds_amount: u128,
+    ) -> Result<Response, ContractError> {
+        let conf = self.config.load(deps.storage)?;
+
+        // the marketplace owner or the minter of the collection can set the minimum offer amount
+        if conf.owner != info.sender {
+            let minter: StdResult<MinterResponse> = deps
+                .querier
+                .query_wasm_smart(&contract_address, &Cw2981QueryMsg::Minter {});
+            match minter {
+                Ok(minter) if minter.minter == info.sender => {}
+                _ => return Err(ContractError::Unauthorized {}),
+            }
+        }
+
+        self.min_offer_amounts
+            .save(deps.storage, contract_address.clone(), &min_funds_amount)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_min_offer_amount")
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("min_funds_amount", min_funds_amount.to_string()))
+    }
+
+    // function to process payment transfer with royalty, protocol fee and referral fee
+    #[allow(clippy::too_many_arguments)]
     fn payment_with_royalty(
         &self,
         deps: &DepsMut,
@@ -606,9 +1322,9 @@ impl MarketplaceContract<'static> {
         token: PaymentAsset,
         sender: &Addr,
         receipient: &Addr,
-    ) -> Vec<CosmosMsg> {
-        // create empty vector of CosmosMsg
-        let mut res_messages: Vec<CosmosMsg> = vec![];
+        referrer: Option<&Addr>,
+    ) -> Result<SalePayment, ContractError> {
+        let config = self.config.load(deps.storage)?;
 
         // Extract information from token
         let (is_native, token_info, amount) = match token {
@@ -619,123 +1335,172 @@ impl MarketplaceContract<'static> {
             PaymentAsset::Native { denom, amount } => (true, denom, Uint128::from(amount)),
         };
 
-        // get cw2981 royalties info
-        let royalty_query_msg = Cw2981QueryMsg::Extension {
-            msg: cw2981_royalties::msg::Cw2981QueryMsg::RoyaltyInfo {
-                token_id: nft_id.into(),
-                sale_price: amount,
-            },
-        };
-
-        let royalty_info_rsp: Result<RoyaltiesInfoResponse, cosmwasm_std::StdError> =
-            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr: nft_contract_address.to_string(),
-                msg: to_binary(&royalty_query_msg).unwrap(),
-            }));
-
-        let (creator, royalty_amount): (Option<Addr>, Option<Uint128>) = match royalty_info_rsp {
-            Ok(RoyaltiesInfoResponse {
-                address,
-                royalty_amount,
-            }) => {
-                if address.is_empty() || royalty_amount == Uint128::zero() {
-                    (None, None)
+        // there is no royalty if creator is the receipient or royalty amount is 0
+        let royalty = self
+            .query_royalty(deps, nft_contract_address, nft_id, amount)?
+            .filter(|(creator, royalty_amount)| creator != receipient && !royalty_amount.is_zero());
+        let royalty_amount = royalty
+            .as_ref()
+            .map(|(_, royalty_amount)| *royalty_amount)
+            .unwrap_or_default();
+
+        let mut protocol_fee = amount.multiply_ratio(config.protocol_fee_bps, MAX_BASIS_POINTS);
+        let mut seller_amount = amount
+            .checked_sub(royalty_amount)
+            .and_then(|rest| rest.checked_sub(protocol_fee))
+            .map_err(StdError::from)?;
+
+        // the referral fee comes out of the protocol fee,
+        // or out of the seller proceeds if there is no protocol fee
+        let referral = match referrer {
+            Some(referrer) if config.referral_fee_bps > 0 => {
+                let mut referral_fee =
+                    amount.multiply_ratio(config.referral_fee_bps, MAX_BASIS_POINTS);
+                if config.protocol_fee_bps == 0 {
+                    seller_amount = seller_amount
+                        .checked_sub(referral_fee)
+                        .map_err(StdError::from)?;
                 } else {
-                    (
-                        Some(deps.api.addr_validate(&address).unwrap()),
-                        Some(royalty_amount),
-                    )
+                    referral_fee = referral_fee.min(protocol_fee);
+                    protocol_fee -= referral_fee;
                 }
+                Some((referrer.clone(), referral_fee))
             }
-            Err(_) => (None, None),
-        };
+            _ => None,
+        }
+        .filter(|(_, referral_fee)| !referral_fee.is_zero());
 
-        // there is no royalty, creator is the receipient, or royalty amount is 0
-        if creator.is_none()
-            || *creator.as_ref().unwrap() == *receipient
-            || royalty_amount.is_none()
-            || royalty_amount.unwrap().is_zero()
-        {
+        // create the message transferring funds from sender to a recipient
+        let transfer_msg = |recipient: &Addr, amount: Uint128| -> StdResult<CosmosMsg> {
             match &is_native {
                 false => {
-                    // execute cw20 transfer msg from info.sender to receipient
-                    let transfer_response = WasmMsg::Execute {
-                        contract_addr: deps.api.addr_validate(&token_info).unwrap().to_string(),
+                    // execute cw20 transfer msg from sender to recipient
+                    Ok(WasmMsg::Execute {
+                        contract_addr: token_info.clone(),
                         msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
                             owner: sender.to_string(),
-                            recipient: receipient.to_string(),
+                            recipient: recipient.to_string(),
                             amount,
-                        })
-                        .unwrap(),
+                        })?,
                         funds: vec![],
-                    };
-                    res_messages.push(transfer_response.into());
+                    }
+                    .into())
                 }
                 true => {
-                    // transfer all funds to receipient
-                    let transfer_response = BankMsg::Send {
-                        to_address: receipient.to_string(),
+                    // transfer native funds to recipient
+                    Ok(BankMsg::Send {
+                        to_address: recipient.to_string(),
                         amount: vec![Coin {
-                            denom: token_info,
+                            denom: token_info.clone(),
                             amount,
                         }],
-                    };
-                    res_messages.push(transfer_response.into());
+                    }
+                    .into())
                 }
             }
-        } else if let (Some(creator), Some(royalty_amount)) = (creator, royalty_amount) {
-            match &is_native {
-                false => {
-                    // execute cw20 transfer transfer royalty to creator
-                    let transfer_token_creator_response = WasmMsg::Execute {
-                        contract_addr: deps.api.addr_validate(&token_info).unwrap().to_string(),
-                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-                            owner: sender.to_string(),
-                            recipient: creator.to_string(),
-                            amount: royalty_amount,
-                        })
-                        .unwrap(),
-                        funds: vec![],
-                    };
-                    res_messages.push(transfer_token_creator_response.into());
+        };
 
-                    // execute cw20 transfer remaining funds to receipient
-                    let transfer_token_seller_msg = WasmMsg::Execute {
-                        contract_addr: deps.api.addr_validate(&token_info).unwrap().to_string(),
-                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-                            owner: sender.to_string(),
-                            recipient: receipient.to_string(),
-                            amount: amount - royalty_amount,
-                        })
-                        .unwrap(),
-                        funds: vec![],
-                    };
-                    res_messages.push(transfer_token_seller_msg.into());
-                }
-                true => {
-                    // transfer royalty to creator
-                    let transfer_token_creator_response = BankMsg::Send {
-                        to_address: creator.to_string(),
-                        amount: vec![Coin {
-                            denom: token_info.clone(),
-                            amount: royalty_amount,
-                        }],
-                    };
-                    res_messages.push(transfer_token_creator_response.into());
+        // the payments are sent in order: royalty, protocol fee, referral fee and seller proceeds
+        let mut messages: Vec<CosmosMsg> = vec![];
+        if let Some((creator, royalty_amount)) = &royalty {
+            messages.push(transfer_msg(creator, *royalty_amount)?);
+        }
+        if !protocol_fee.is_zero() {
+            messages.push(transfer_msg(&config.owner, protocol_fee)?);
+        }
+        if let Some((referrer, referral_fee)) = &referral {
+            messages.push(transfer_msg(referrer, *referral_fee)?);
+        }
+        if !seller_amount.is_zero() {
+            messages.push(transfer_msg(receipient, seller_amount)?);
+        }
 
-                    // transfer remaining funds to receipient
-                    let transfer_token_seller_msg = BankMsg::Send {
-                        to_address: receipient.to_string(),
-                        amount: vec![Coin {
-                            denom: token_info,
-                            amount: amount - royalty_amount,
-                        }],
-                    };
-                    res_messages.push(transfer_token_seller_msg.into());
-                }
+        Ok(SalePayment {
+            messages,
+            royalty,
+            protocol_fee,
+            referral,
+            seller_amount,
+        })
+    }
+
+    // get the royalty receiver and amount of a sale following the royalty policy of the collection
+    fn query_royalty(
+        &self,
+        deps: &DepsMut,
+        nft_contract_address: &Addr,
+        nft_id: &str,
+        sale_price: Uint128,
+    ) -> Result<Option<(Addr, Uint128)>, ContractError> {
+        let policy = self
+            .royalty_policies
+            .may_load(deps.storage, nft_contract_address.clone())?
+            .unwrap_or_default();
+
+        if policy == RoyaltyPolicy::Disabled {
+            return Ok(None);
+        }
+
+        // check if the collection implements royalties
+        let check_royalties_rsp: StdResult<CheckRoyaltiesResponse> = deps.querier.query_wasm_smart(
+            nft_contract_address,
+            &Cw2981QueryMsg::Extension {
+                msg: cw2981_royalties::msg::Cw2981QueryMsg::CheckRoyalties {},
+            },
+        );
+
+        let royalty_payments = match (check_royalties_rsp, &policy) {
+            (Ok(CheckRoyaltiesResponse { royalty_payments }), _) => royalty_payments,
+            // a collection without cw2981 support does not pay royalties
+            (Err(_), RoyaltyPolicy::Optional) => false,
+            (Err(_), _) => {
+                return Err(ContractError::RoyaltiesNotSupported {
+                    contract_address: nft_contract_address.to_string(),
+                })
             }
+        };
+
+        if !royalty_payments {
+            if policy == RoyaltyPolicy::Enforced {
+                return Err(ContractError::RoyaltiesNotSupported {
+                    contract_address: nft_contract_address.to_string(),
+                });
+            }
+            return Ok(None);
         }
 
-        res_messages
+        // get cw2981 royalties info, the sale must fail if the response is invalid
+        let invalid_royalty_info = || ContractError::InvalidRoyaltyInfo {
+            contract_address: nft_contract_address.to_string(),
+        };
+        let RoyaltiesInfoResponse {
+            address,
+            royalty_amount,
+        } = deps
+            .querier
+            .query_wasm_smart(
+                nft_contract_address,
+                &Cw2981QueryMsg::Extension {
+                    msg: cw2981_royalties::msg::Cw2981QueryMsg::RoyaltyInfo {
+                        token_id: nft_id.into(),
+                        sale_price,
+                    },
+                },
+            )
+            .map_err(|_| invalid_royalty_info())?;
+
+        if address.is_empty() || royalty_amount.is_zero() {
+            return Ok(None);
+        }
+        let creator = deps
+            .api
+            .addr_validate(&address)
+            .map_err(|_| invalid_royalty_info())?;
+
+        // the royalty is capped by the marketplace
+        let max_royalty_bps = self.config.load(deps.storage)?.max_royalty_bps;
+        let max_royalty_amount = sale_price.multiply_ratio(max_royalty_bps, MAX_BASIS_POINTS);
+
+        Ok(Some((creator, royalty_amount.min(max_royalty_amount))))
     }
 }
@@ -0,0 +1,59 @@
+use cosmwasm_std::{Addr, StdResult, Storage, SubMsg};
+use cw_storage_plus::Item;
+
+use crate::msg::HooksResponse;
+use crate::ContractError;
+
+// the hook submessages reply on error only, so a failing hook does not revert the trade
+pub const SALE_HOOK_REPLY_ID: u64 = 1;
+pub const LISTING_HOOK_REPLY_ID: u64 = 2;
+
+// a list of contracts notified by the marketplace, in the style of cw-controllers Hooks
+pub struct Hooks<'a>(Item<'a, Vec<Addr>>);
+
+impl<'a> Hooks<'a> {
+    pub const fn new(storage_key: &'a str) -> Self {
+        Hooks(Item::new(storage_key))
+    }
+
+    pub fn add_hook(&self, storage: &mut dyn Storage, hook: Addr) -> Result<(), ContractError> {
+        let mut hooks = self.0.may_load(storage)?.unwrap_or_default();
+        if hooks.contains(&hook) {
+            return Err(ContractError::HookAlreadyRegistered {});
+        }
+        hooks.push(hook);
+        Ok(self.0.save(storage, &hooks)?)
+    }
+
+    pub fn remove_hook(&self, storage: &mut dyn Storage, hook: Addr) -> Result<(), ContractError> {
+        let mut hooks = self.0.may_load(storage)?.unwrap_or_default();
+        match hooks.iter().position(|h| h == &hook) {
+            Some(index) => {
+                hooks.remove(index);
+                Ok(self.0.save(storage, &hooks)?)
+            }
+            None => Err(ContractError::HookNotRegistered {}),
+        }
+    }
+
+    // create a submessage for each hook
+    pub fn prepare_hooks<F: Fn(Addr) -> StdResult<SubMsg>>(
+        &self,
+        storage: &dyn Storage,
+        prep: F,
+    ) -> StdResult<Vec<SubMsg>> {
+        self.0
+            .may_load(storage)?
+            .unwrap_or_default()
+            .into_iter()
+            .map(prep)
+            .collect()
+    }
+
+    pub fn query_hooks(&self, storage: &dyn Storage) -> StdResult<HooksResponse> {
+        let hooks = self.0.may_load(storage)?.unwrap_or_default();
+        Ok(HooksResponse {
+            hooks: hooks.into_iter().map(String::from).collect(),
+        })
+    }
+}
@@ -0,0 +1,342 @@
+use crate::events::{
+    AcceptOfferEvent, BuyEvent, CancelEvent, CancelOfferEvent, IncrementCounterEvent, ListEvent,
+    MarketplaceEvent, OfferEvent, SaleSplit,
+};
+use crate::msg::ExecuteMsg;
+use crate::order_state::NFT;
+use crate::state::AuctionConfig;
+use crate::test_setup::env::{instantiate_contracts, NATIVE_DENOM, OWNER, USER_1, USER_2};
+
+use cosmwasm_std::{coin, Addr, Event, Uint128};
+use cw2981_royalties::{Metadata, MintMsg};
+use cw721::Expiration as Cw721Expiration;
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{AppResponse, Executor};
+
+const REFERRER: &str = "aura1000000000000000000000000000000referrer";
+
+// find the custom event of the marketplace in the response
+fn find_event<T: MarketplaceEvent>(response: &AppResponse) -> T {
+    let event: &Event = response
+        .events
+        .iter()
+        .find(|event| event.ty == format!("wasm-{}", T::EVENT_TYPE))
+        .unwrap();
+    T::from_event(event).unwrap()
+}
+
+#[test]
+fn trades_emit_typed_events() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+    let cw20_address = contracts[2].contract_addr.clone();
+
+    // the royalty of the collection is 20% paid to OWNER
+    let mint_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Mint(MintMsg {
+        token_id: "1".to_string(),
+        owner: USER_2.to_string(),
+        token_uri: None,
+        extension: Metadata::default(),
+    });
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&cw2981_address),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+    let approve_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Approve {
+        spender: marketplace_address.clone(),
+        token_id: "1".to_string(),
+        expires: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(USER_2),
+        Addr::unchecked(&cw2981_address),
+        &approve_msg,
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::EditMaxRoyalty {
+            max_royalty_bps: 2_000,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::EditProtocolFee {
+            protocol_fee_bps: 200,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::EditReferralFee {
+            referral_fee_bps: 100,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_2),
+            Addr::unchecked(&marketplace_address),
+            &ExecuteMsg::ListNft {
+                contract_address: cw2981_address.clone(),
+                token_id: "1".to_string(),
+                auction_config: AuctionConfig::FixedPrice {
+                    price: coin(1000, NATIVE_DENOM),
+                    start_time: None,
+                    end_time: Some(Cw721Expiration::AtHeight(1_000_000)),
+                },
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        find_event::<ListEvent>(&response),
+        ListEvent {
+            contract_address: Addr::unchecked(&cw2981_address),
+            token_id: "1".to_string(),
+            seller: Addr::unchecked(USER_2),
+            price: coin(1000, NATIVE_DENOM),
+            start_time: None,
+            end_time: Some(Cw721Expiration::AtHeight(1_000_000)),
+        }
+    );
+
+    // USER_1 offers for the nft then cancels the offer
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: USER_1.to_string(),
+            amount: vec![coin(2000, NATIVE_DENOM)],
+        },
+    ))
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&cw20_address),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: USER_1.to_string(),
+            amount: Uint128::from(500u128),
+        },
+        &[coin(500, NATIVE_DENOM)],
+    )
+    .unwrap();
+    let end_time = Cw721Expiration::AtTime(app.block_info().time.plus_seconds(1000));
+    let nft = NFT {
+        contract_address: Addr::unchecked(&cw2981_address),
+        token_id: Some("1".to_string()),
+    };
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(&marketplace_address),
+            &ExecuteMsg::OfferNft {
+                nft: nft.clone(),
+                funds_amount: 500,
+                start_time: None,
+                end_time,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        find_event::<OfferEvent>(&response),
+        OfferEvent {
+            contract_address: Addr::unchecked(&cw2981_address),
+            token_id: "1".to_string(),
+            offerer: Addr::unchecked(USER_1),
+            price: coin(500, &cw20_address),
+            start_time: None,
+            end_time,
+        }
+    );
+
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(&marketplace_address),
+            &ExecuteMsg::CancelOffer { nfts: vec![nft] },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        find_event::<CancelOfferEvent>(&response),
+        CancelOfferEvent {
+            contract_address: Addr::unchecked(&cw2981_address),
+            token_id: "1".to_string(),
+            offerer: Addr::unchecked(USER_1),
+        }
+    );
+
+    // USER_1 buys the listing through a referrer
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(&marketplace_address),
+            &ExecuteMsg::Buy {
+                contract_address: cw2981_address.clone(),
+                token_id: "1".to_string(),
+                max_price: None,
+                referrer: Some(REFERRER.to_string()),
+                pay_with_vaura: None,
+            },
+            &[coin(1000, NATIVE_DENOM)],
+        )
+        .unwrap();
+    assert_eq!(
+        find_event::<BuyEvent>(&response),
+        BuyEvent {
+            contract_address: Addr::unchecked(&cw2981_address),
+            token_id: "1".to_string(),
+            seller: Addr::unchecked(USER_2),
+            buyer: Addr::unchecked(USER_1),
+            price: coin(1000, NATIVE_DENOM),
+            split: SaleSplit {
+                royalty: Some((Addr::unchecked(OWNER), Uint128::from(200u128))),
+                protocol_fee: Uint128::from(10u128),
+                referral: Some((Addr::unchecked(REFERRER), Uint128::from(10u128))),
+                seller_amount: Uint128::from(780u128),
+            },
+        }
+    );
+}
+
+#[test]
+fn accepting_offer_and_incrementing_counter_emit_typed_events() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+    let cw20_address = contracts[2].contract_addr.clone();
+
+    let mint_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Mint(MintMsg {
+        token_id: "1".to_string(),
+        owner: USER_2.to_string(),
+        token_uri: None,
+        extension: Metadata::default(),
+    });
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&cw2981_address),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+    let approve_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Approve {
+        spender: marketplace_address.clone(),
+        token_id: "1".to_string(),
+        expires: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(USER_2),
+        Addr::unchecked(&cw2981_address),
+        &approve_msg,
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(USER_2),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::ListNft {
+            contract_address: cw2981_address.clone(),
+            token_id: "1".to_string(),
+            auction_config: AuctionConfig::FixedPrice {
+                price: coin(1000, NATIVE_DENOM),
+                start_time: None,
+                end_time: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // USER_1 offers for the listed nft
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: USER_1.to_string(),
+            amount: vec![coin(500, NATIVE_DENOM)],
+        },
+    ))
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&cw20_address),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: USER_1.to_string(),
+            amount: Uint128::from(500u128),
+        },
+        &[coin(500, NATIVE_DENOM)],
+    )
+    .unwrap();
+    let nft = NFT {
+        contract_address: Addr::unchecked(&cw2981_address),
+        token_id: Some("1".to_string()),
+    };
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::OfferNft {
+            nft: nft.clone(),
+            funds_amount: 500,
+            start_time: None,
+            end_time: Cw721Expiration::AtTime(app.block_info().time.plus_seconds(1000)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the listing is removed when USER_2 accepts the offer
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_2),
+            Addr::unchecked(&marketplace_address),
+            &ExecuteMsg::AcceptNftOffer {
+                offerer: USER_1.to_string(),
+                nft,
+                funds_amount: 500,
+                referrer: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        find_event::<AcceptOfferEvent>(&response).price,
+        coin(500, &cw20_address)
+    );
+    assert_eq!(
+        find_event::<CancelEvent>(&response),
+        CancelEvent {
+            contract_address: Addr::unchecked(&cw2981_address),
+            token_id: "1".to_string(),
+            seller: Addr::unchecked(USER_2),
+            cancelled_by: Addr::unchecked(USER_2),
+        }
+    );
+
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(&marketplace_address),
+            &ExecuteMsg::IncrementCounter {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        find_event::<IncrementCounterEvent>(&response),
+        IncrementCounterEvent {
+            user: Addr::unchecked(USER_1),
+            counter: 1,
+        }
+    );
+}
@@ -0,0 +1,296 @@
+use crate::msg::{
+    ExecuteMsg, HooksResponse, ListingHookAction, ListingHookMsg, QueryMsg, SaleHookMsg,
+};
+use crate::state::AuctionConfig;
+use crate::test_setup::env::{instantiate_contracts, NATIVE_DENOM, OWNER, USER_1};
+use crate::ContractError;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coin, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult,
+};
+use cw2981_royalties::{Metadata, MintMsg};
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+// the messages handled by the mock hook contract
+#[cw_serde]
+enum HookExecuteMsg {
+    SaleHook(SaleHookMsg),
+    ListingHook(ListingHookMsg),
+}
+
+#[cw_serde]
+struct ReceivedHooks {
+    sales: Vec<SaleHookMsg>,
+    listings: Vec<ListingHookMsg>,
+}
+
+const RECEIVED_HOOKS: Item<ReceivedHooks> = Item::new("received_hooks");
+
+fn hook_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    RECEIVED_HOOKS.save(
+        deps.storage,
+        &ReceivedHooks {
+            sales: vec![],
+            listings: vec![],
+        },
+    )?;
+    Ok(Response::new())
+}
+
+fn hook_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: HookExecuteMsg,
+) -> StdResult<Response> {
+    let mut received = RECEIVED_HOOKS.load(deps.storage)?;
+    match msg {
+        HookExecuteMsg::SaleHook(msg) => received.sales.push(msg),
+        HookExecuteMsg::ListingHook(msg) => received.listings.push(msg),
+    }
+    RECEIVED_HOOKS.save(deps.storage, &received)?;
+    Ok(Response::new())
+}
+
+fn failing_hook_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: HookExecuteMsg,
+) -> StdResult<Response> {
+    Err(StdError::generic_err("hook failed"))
+}
+
+fn hook_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_binary(&RECEIVED_HOOKS.load(deps.storage)?)
+}
+
+// instantiate a hook contract and register it as a sale hook and a listing hook
+fn register_hook(app: &mut App, marketplace_address: &str, failing: bool) -> Addr {
+    let code_id = if failing {
+        app.store_code(Box::new(ContractWrapper::new(
+            failing_hook_execute,
+            hook_instantiate,
+            hook_query,
+        )))
+    } else {
+        app.store_code(Box::new(ContractWrapper::new(
+            hook_execute,
+            hook_instantiate,
+            hook_query,
+        )))
+    };
+    let hook_address = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "hook",
+            None,
+        )
+        .unwrap();
+
+    for msg in [
+        ExecuteMsg::AddSaleHook {
+            hook: hook_address.to_string(),
+        },
+        ExecuteMsg::AddListingHook {
+            hook: hook_address.to_string(),
+        },
+    ] {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(marketplace_address),
+            &msg,
+            &[],
+        )
+        .unwrap();
+    }
+    hook_address
+}
+
+// mint a NFT to OWNER, approve marketplace and list it
+fn mint_and_list_nft(
+    app: &mut App,
+    token_id: &str,
+    price: u128,
+    cw2981_address: &str,
+    marketplace_address: &str,
+) {
+    let mint_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Mint(MintMsg {
+        token_id: token_id.to_string(),
+        owner: OWNER.to_string(),
+        token_uri: None,
+        extension: Metadata::default(),
+    });
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(cw2981_address),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+
+    let approve_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Approve {
+        spender: marketplace_address.to_string(),
+        token_id: token_id.to_string(),
+        expires: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(cw2981_address),
+        &approve_msg,
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(marketplace_address),
+        &ExecuteMsg::ListNft {
+            contract_address: cw2981_address.to_string(),
+            token_id: token_id.to_string(),
+            auction_config: AuctionConfig::FixedPrice {
+                price: coin(price, NATIVE_DENOM),
+                start_time: None,
+                end_time: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn only_owner_can_manage_hooks() {
+    let (mut app, contracts) = instantiate_contracts();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    let response = app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::AddSaleHook {
+            hook: USER_1.to_string(),
+        },
+        &[],
+    );
+    assert_eq!(
+        response.err().unwrap().source().unwrap().to_string(),
+        ContractError::Unauthorized {}.to_string()
+    );
+
+    let hook_address = register_hook(&mut app, &marketplace_address, false);
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(&marketplace_address, &QueryMsg::SaleHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![hook_address.to_string()]);
+
+    // a hook cannot be registered twice
+    let response = app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::AddListingHook {
+            hook: hook_address.to_string(),
+        },
+        &[],
+    );
+    assert_eq!(
+        response.err().unwrap().source().unwrap().to_string(),
+        ContractError::HookAlreadyRegistered {}.to_string()
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::RemoveListingHook {
+            hook: hook_address.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(&marketplace_address, &QueryMsg::ListingHooks {})
+        .unwrap();
+    assert!(hooks.hooks.is_empty());
+}
+
+#[test]
+fn hooks_are_notified_and_failing_hooks_do_not_revert_trades() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    // the failing hook is registered first
+    register_hook(&mut app, &marketplace_address, true);
+    let hook_address = register_hook(&mut app, &marketplace_address, false);
+
+    mint_and_list_nft(&mut app, "1", 100, &cw2981_address, &marketplace_address);
+    mint_and_list_nft(&mut app, "2", 200, &cw2981_address, &marketplace_address);
+
+    // USER_1 buys the first nft
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::Buy {
+            contract_address: cw2981_address.clone(),
+            token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
+        },
+        &[coin(100, NATIVE_DENOM)],
+    )
+    .unwrap();
+
+    // OWNER cancels the second listing
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::Cancel {
+            contract_address: cw2981_address.clone(),
+            token_id: "2".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let received: ReceivedHooks = app
+        .wrap()
+        .query_wasm_smart(hook_address, &Empty {})
+        .unwrap();
+    assert_eq!(
+        received.sales,
+        vec![SaleHookMsg {
+            contract_address: cw2981_address,
+            token_id: "1".to_string(),
+            price: coin(100, NATIVE_DENOM),
+            seller: OWNER.to_string(),
+            buyer: USER_1.to_string(),
+        }]
+    );
+    let actions: Vec<(ListingHookAction, String)> = received
+        .listings
+        .into_iter()
+        .map(|msg| (msg.action, msg.listing.token_id))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            (ListingHookAction::Create, "1".to_string()),
+            (ListingHookAction::Create, "2".to_string()),
+            (ListingHookAction::Delete, "2".to_string()),
+        ]
+    );
+}
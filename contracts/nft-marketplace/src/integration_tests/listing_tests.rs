@@ -1,7 +1,7 @@
 use crate::contract::*;
 use crate::msg::{ExecuteMsg, InstantiateMsg, ListingsResponse, QueryMsg};
 use crate::order_state::{OrderComponents, NFT};
-use crate::state::{contract, AuctionConfig, Config};
+use crate::state::{contract, AuctionConfig, Config, RoyaltyPolicy};
 use crate::test_setup::env::{instantiate_contracts, NATIVE_DENOM, NATIVE_DENOM_2, OWNER, USER_1};
 use crate::ContractError;
 
@@ -12,7 +12,7 @@ use cosmwasm_std::{
     WasmQuery,
 };
 use cw20::Expiration as Cw20Expiration;
-use cw2981_royalties::msg::{Cw2981QueryMsg, RoyaltiesInfoResponse};
+use cw2981_royalties::msg::{CheckRoyaltiesResponse, Cw2981QueryMsg, RoyaltiesInfoResponse};
 use cw2981_royalties::{ExecuteMsg as Cw2981ExecuteMsg, QueryMsg as Cw721QueryMsg};
 use cw721::{Approval, ApprovalResponse, Expiration as Cw721Expiration, OwnerOfResponse};
 
@@ -92,7 +92,16 @@ fn mock_deps() -> OwnedDeps<MemoryStorage, MockApi, MockQuerier> {
                                     }
                                 }
                                 Cw2981QueryMsg::CheckRoyalties {} => {
-                                    let result = ContractResult::Ok(to_binary(&true).unwrap());
+                                    let result = ContractResult::Ok(
+                                        to_binary(&CheckRoyaltiesResponse {
+                                            royalty_payments: true,
+                                        })
+                                        .unwrap(),
+                                    );
+                                    cosmwasm_std::SystemResult::Ok(result)
+                                }
+                                Cw2981QueryMsg::UserOf { .. } => {
+                                    let result = ContractResult::Err("Not Found".to_string());
                                     cosmwasm_std::SystemResult::Ok(result)
                                 }
                             }
@@ -265,6 +274,28 @@ mod listing {
         assert!(response.is_ok());
     }
 
+    #[test]
+    fn cannot_create_listing_ending_before_it_starts() {
+        let mut deps = mock_deps();
+
+        let response = create_listing(
+            deps.as_mut(),
+            "owner",
+            Addr::unchecked(MOCK_CW2981_ADDR),
+            "1",
+            Some(Cw721Expiration::AtTime(Timestamp::from_nanos(
+                1_600_000_002,
+            ))),
+            Some(Cw721Expiration::AtTime(Timestamp::from_nanos(
+                1_600_000_001,
+            ))),
+        );
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            ContractError::InvalidStartTime {}.to_string()
+        );
+    }
+
     #[test]
     fn cannot_update_listing_of_other() {
         let mut deps = mock_deps();
@@ -518,6 +549,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
 
         let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
@@ -556,6 +590,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
 
         let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
@@ -582,13 +619,16 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
         let mock_info_wrong_sender = mock_info("owner", &coins(100, "uaura"));
 
         let response = execute(deps.as_mut(), mock_env(), mock_info_wrong_sender, msg);
         match response {
             Ok(_) => panic!("Expected error"),
-            Err(ContractError::CustomError { .. }) => {}
+            Err(ContractError::CannotBuyOwnListing {}) => {}
             Err(e) => panic!("Unexpected error: {}", e),
         }
     }
@@ -611,6 +651,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
         let mock_info_buyer = mock_info("buyer", &coins(99, "uaura"));
 
@@ -641,6 +684,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
         let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
 
@@ -678,6 +724,93 @@ mod listing {
         );
     }
 
+    #[test]
+    fn buy_listing_refunds_surplus_and_other_coins() {
+        let mut deps = mock_deps();
+
+        create_listing(
+            deps.as_mut(),
+            "owner",
+            Addr::unchecked(MOCK_CW2981_ADDR),
+            "2",
+            None,
+            None,
+        )
+        .unwrap();
+
+        // buyer sends another denom first and more than the price
+        let msg = ExecuteMsg::Buy {
+            contract_address: MOCK_CW2981_ADDR.to_string(),
+            token_id: "2".to_string(),
+            max_price: Some(Uint128::from(150u128)),
+            referrer: None,
+            pay_with_vaura: None,
+        };
+        let mock_info_buyer = mock_info(
+            "buyer",
+            &[
+                cosmwasm_std::coin(5, "uother"),
+                cosmwasm_std::coin(120, "uaura"),
+            ],
+        );
+
+        let response = execute(deps.as_mut(), mock_env(), mock_info_buyer, msg).unwrap();
+        assert_eq!(3, response.messages.len());
+        assert_eq!(
+            response.messages[1],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner".to_string(),
+                amount: vec![cosmwasm_std::coin(100, "uaura")],
+            })),
+            "should transfer the price to owner"
+        );
+        assert_eq!(
+            response.messages[2],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "buyer".to_string(),
+                amount: vec![
+                    cosmwasm_std::coin(5, "uother"),
+                    cosmwasm_std::coin(20, "uaura")
+                ],
+            })),
+            "should refund the surplus to buyer"
+        );
+    }
+
+    #[test]
+    fn cannot_buy_listing_above_max_price() {
+        let mut deps = mock_deps();
+
+        create_listing(
+            deps.as_mut(),
+            "owner",
+            Addr::unchecked(MOCK_CW2981_ADDR),
+            "1",
+            None,
+            None,
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::Buy {
+            contract_address: MOCK_CW2981_ADDR.to_string(),
+            token_id: "1".to_string(),
+            max_price: Some(Uint128::from(99u128)),
+            referrer: None,
+            pay_with_vaura: None,
+        };
+        let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
+
+        let response = execute(deps.as_mut(), mock_env(), mock_info_buyer, msg);
+        match response {
+            Ok(_) => panic!("Expected error"),
+            Err(ContractError::PriceExceedsMaxPrice { price, max_price }) => {
+                assert_eq!(price, Uint128::from(100u128));
+                assert_eq!(max_price, Uint128::from(99u128));
+            }
+            Err(e) => panic!("Unexpected error: {}", e),
+        }
+    }
+
     #[test]
     fn cannot_buy_listing_before_start_time() {
         let mut deps = mock_deps();
@@ -698,6 +831,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
         let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
 
@@ -707,7 +843,7 @@ mod listing {
         println!("Response: {:?}", &response);
         match response {
             Ok(_) => panic!("Expected error"),
-            Err(ContractError::CustomError { .. }) => {}
+            Err(ContractError::AuctionNotStarted { .. }) => {}
             Err(e) => panic!("Unexpected error: {}", e),
         }
     }
@@ -732,6 +868,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
         let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
 
@@ -741,7 +880,7 @@ mod listing {
         println!("Response: {:?}", &response);
         match response {
             Ok(_) => panic!("Expected error"),
-            Err(ContractError::CustomError { .. }) => {}
+            Err(ContractError::AuctionEnded { .. }) => {}
             Err(e) => panic!("Unexpected error: {}", e),
         }
     }
@@ -764,6 +903,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "2".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
         let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
 
@@ -811,6 +953,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "2".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
         let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
 
@@ -858,6 +1003,9 @@ mod listing {
         let msg = ExecuteMsg::Buy {
             contract_address: MOCK_CW2981_ADDR.to_string(),
             token_id: "3".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
         };
         let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
 
@@ -886,136 +1034,461 @@ mod listing {
             "should transfer all funds to owner"
         );
     }
-}
-
-// fn create_offer(
-//     deps: DepsMut,
-//     sender: &str,
-//     contract_address: Addr,
-//     token_id: Option<String>,
-//     funds_amount: u128,
-//     end_time: Cw20Expiration,
-// ) -> Result<Response, ContractError> {
-//     let msg = ExecuteMsg::OfferNft {
-//         nft: NFT {
-//             contract_address,
-//             token_id,
-//         },
-//         funds_amount,
-//         end_time,
-//     };
-//     let info = mock_info(sender, &coins(1000, "uaura"));
-//     execute(deps, mock_env(), info, msg)
-// }
 
-// fn accept_offer(
-//     deps: DepsMut,
-//     sender: &str,
-//     offerer: &str,
-//     contract_address: Addr,
-//     token_id: Option<String>,
-// ) -> Result<Response, ContractError> {
-//     let msg = ExecuteMsg::AcceptNftOffer {
-//         offerer: offerer.to_string(),
-//         nft: NFT {
-//             contract_address,
-//             token_id,
-//         },
-//     };
-//     let info = mock_info(sender, &coins(1000, "uaura"));
-//     execute(deps, mock_env(), info, msg)
-// }
+    #[test]
+    fn royalty_is_capped_by_max_royalty() {
+        let mut deps = mock_deps();
 
-mod create_offer {
-    // use super::*;
+        // only the owner can edit the max royalty
+        let msg = ExecuteMsg::EditMaxRoyalty {
+            max_royalty_bps: 500,
+        };
+        let response = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg);
+        assert!(matches!(response, Err(ContractError::Unauthorized {})));
 
-    // // test offer a specific nft
-    // #[test]
-    // fn test_offer_nft() {
-    //     // prepare deps for test
-    //     let mut deps = mock_deps();
+        // max royalty cannot be greater than 100%
+        let msg = ExecuteMsg::EditMaxRoyalty {
+            max_royalty_bps: 10_001,
+        };
+        let response = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(
+            response,
+            Err(ContractError::InvalidBasisPoints { bps: 10_001 })
+        ));
+
+        // cap royalty at 5%
+        let msg = ExecuteMsg::EditMaxRoyalty {
+            max_royalty_bps: 500,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
 
-    //     // get block time from mock env
-    //     let block_time = mock_env().block.time;
+        create_listing(
+            deps.as_mut(),
+            "owner",
+            Addr::unchecked(MOCK_CW2981_ADDR),
+            "1",
+            None,
+            None,
+        )
+        .unwrap();
 
-    //     let response = create_offer(
-    //         deps.as_mut(),
-    //         MOCK_OFFER_NFT_OFFERER_1,
-    //         Addr::unchecked(MOCK_CW2981_ADDR),
-    //         Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
-    //         10000000,
-    //         Cw20Expiration::AtTime(block_time.plus_seconds(1000)),
-    //     );
-    //     println!("Response: {:?}", &response);
-    //     assert!(response.is_ok());
-    // }
+        let msg = ExecuteMsg::Buy {
+            contract_address: MOCK_CW2981_ADDR.to_string(),
+            token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
+        };
+        let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
 
-    // // test user can accept offer
-    // #[test]
-    // fn user_can_not_offer_invalid_nft() {
-    //     // prepare deps for test
-    //     let mut deps = mock_deps();
+        let response = execute(deps.as_mut(), mock_env(), mock_info_buyer, msg).unwrap();
+        assert_eq!(3, response.messages.len());
+        assert_eq!(
+            response.messages[1],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "creator".to_string(),
+                amount: vec![cosmwasm_std::coin(5, "uaura")],
+            })),
+            "should transfer capped royalty to creator"
+        );
+        assert_eq!(
+            response.messages[2],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner".to_string(),
+                amount: vec![cosmwasm_std::coin(95, "uaura")],
+            })),
+            "should transfer the rest to owner"
+        );
+    }
 
-    //     // get block time from mock env
-    //     let block_time = mock_env().block.time;
+    #[test]
+    fn protocol_and_referral_fees_are_paid_on_buy() {
+        let mut deps = mock_deps();
 
-    //     let response = create_offer(
-    //         deps.as_mut(),
-    //         MOCK_OFFER_NFT_OFFERER_1,
-    //         Addr::unchecked(MOCK_CW2981_ADDR),
-    //         Some(MOCK_OFFER_NFT_TOKEN_ID_INVALID.to_string()),
-    //         10000000,
-    //         Cw20Expiration::AtTime(block_time.plus_seconds(1000)),
-    //     );
-    //     assert_eq!(
-    //         response.err().unwrap().to_string(),
-    //         ContractError::CustomError {
-    //             val: "Nft not exist".to_string()
-    //         }
-    //         .to_string()
-    //     );
-    // }
+        // only the owner can edit the fees
+        let msg = ExecuteMsg::EditReferralFee {
+            referral_fee_bps: 100,
+        };
+        let response = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg);
+        assert!(matches!(response, Err(ContractError::Unauthorized {})));
+        let msg = ExecuteMsg::EditProtocolFee {
+            protocol_fee_bps: 300,
+        };
+        let response = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg);
+        assert!(matches!(response, Err(ContractError::Unauthorized {})));
 
-    // // cannot offer the owned nft
-    // #[test]
-    // fn cannot_offer_owned_nft() {
-    //     // prepare deps for test
-    //     let mut deps = mock_deps();
+        // the fees cannot be set while the max royalty is 100%
+        let msg = ExecuteMsg::EditProtocolFee {
+            protocol_fee_bps: 300,
+        };
+        let response = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(
+            response,
+            Err(ContractError::InvalidBasisPoints { bps: 10_300 })
+        ));
+
+        // max royalty is 10%, protocol fee is 3%, referral fee is 1%
+        let msg = ExecuteMsg::EditMaxRoyalty {
+            max_royalty_bps: 1_000,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        let msg = ExecuteMsg::EditProtocolFee {
+            protocol_fee_bps: 300,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
 
-    //     // get block time from mock env
-    //     let block_time = mock_env().block.time;
+        // the royalty and the fees cannot be greater than 100% in total
+        let msg = ExecuteMsg::EditReferralFee {
+            referral_fee_bps: 8_800,
+        };
+        let response = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(
+            response,
+            Err(ContractError::InvalidBasisPoints { bps: 10_100 })
+        ));
+
+        let msg = ExecuteMsg::EditReferralFee {
+            referral_fee_bps: 100,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
 
-    //     let response = create_offer(
-    //         deps.as_mut(),
-    //         MOCK_OFFER_NFT_OWNER,
-    //         Addr::unchecked(MOCK_CW2981_ADDR),
-    //         Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
-    //         10000000,
-    //         Cw20Expiration::AtTime(block_time.plus_seconds(1000)),
-    //     );
-    //     assert_eq!(
-    //         response.err().unwrap().to_string(),
-    //         ContractError::CustomError {
-    //             val: "Cannot offer owned nft".to_string()
-    //         }
-    //         .to_string()
-    //     );
-    // }
+        let msg = ExecuteMsg::EditMaxRoyalty {
+            max_royalty_bps: 9_700,
+        };
+        let response = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(
+            response,
+            Err(ContractError::InvalidBasisPoints { bps: 10_100 })
+        ));
 
-    // // cannot offer without token id
-    // #[test]
-    // fn cannot_offer_without_token_id() {
-    //     // prepare deps for test
-    //     let mut deps = mock_deps();
+        // token 2 has no royalty
+        create_listing(
+            deps.as_mut(),
+            "owner",
+            Addr::unchecked(MOCK_CW2981_ADDR),
+            "2",
+            None,
+            None,
+        )
+        .unwrap();
 
-    //     // get block time from mock env
-    //     let block_time = mock_env().block.time;
+        let msg = ExecuteMsg::Buy {
+            contract_address: MOCK_CW2981_ADDR.to_string(),
+            token_id: "2".to_string(),
+            max_price: None,
+            referrer: Some("referrer".to_string()),
+            pay_with_vaura: None,
+        };
+        let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
 
-    //     let response = create_offer(
-    //         deps.as_mut(),
-    //         MOCK_OFFER_NFT_OFFERER_1,
-    //         Addr::unchecked(MOCK_CW2981_ADDR),
-    //         None,
+        let response = execute(deps.as_mut(), mock_env(), mock_info_buyer, msg).unwrap();
+        assert_eq!(4, response.messages.len());
+        assert_eq!(
+            response.messages[1],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner".to_string(),
+                amount: vec![cosmwasm_std::coin(2, "uaura")],
+            })),
+            "should transfer protocol fee minus referral fee to marketplace owner"
+        );
+        assert_eq!(
+            response.messages[2],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "referrer".to_string(),
+                amount: vec![cosmwasm_std::coin(1, "uaura")],
+            })),
+            "should transfer referral fee to referrer"
+        );
+        assert_eq!(
+            response.messages[3],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner".to_string(),
+                amount: vec![cosmwasm_std::coin(97, "uaura")],
+            })),
+            "should transfer the rest to seller"
+        );
+        assert!(response
+            .attributes
+            .contains(&cosmwasm_std::attr("referral_fee", "1")));
+    }
+
+    #[test]
+    fn referral_fee_is_taken_from_seller_without_protocol_fee() {
+        let mut deps = mock_deps();
+
+        let msg = ExecuteMsg::EditMaxRoyalty {
+            max_royalty_bps: 1_000,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        let msg = ExecuteMsg::EditReferralFee {
+            referral_fee_bps: 200,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        create_listing(
+            deps.as_mut(),
+            "owner",
+            Addr::unchecked(MOCK_CW2981_ADDR),
+            "1",
+            None,
+            None,
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::Buy {
+            contract_address: MOCK_CW2981_ADDR.to_string(),
+            token_id: "1".to_string(),
+            max_price: None,
+            referrer: Some("referrer".to_string()),
+            pay_with_vaura: None,
+        };
+        let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
+
+        let response = execute(deps.as_mut(), mock_env(), mock_info_buyer, msg).unwrap();
+        assert_eq!(4, response.messages.len());
+        assert_eq!(
+            response.messages[1],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "creator".to_string(),
+                amount: vec![cosmwasm_std::coin(10, "uaura")],
+            })),
+            "should transfer royalty to creator"
+        );
+        assert_eq!(
+            response.messages[2],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "referrer".to_string(),
+                amount: vec![cosmwasm_std::coin(2, "uaura")],
+            })),
+            "should transfer referral fee to referrer"
+        );
+        assert_eq!(
+            response.messages[3],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner".to_string(),
+                amount: vec![cosmwasm_std::coin(88, "uaura")],
+            })),
+            "should transfer the rest to seller"
+        );
+    }
+
+    #[test]
+    fn no_royalty_when_policy_is_disabled() {
+        let mut deps = mock_deps();
+
+        let msg = ExecuteMsg::SetRoyaltyPolicy {
+            contract_address: MOCK_CW2981_ADDR.to_string(),
+            policy: RoyaltyPolicy::Disabled,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let policy: RoyaltyPolicy = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::RoyaltyPolicy {
+                    contract_address: MOCK_CW2981_ADDR.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(policy, RoyaltyPolicy::Disabled);
+
+        create_listing(
+            deps.as_mut(),
+            "owner",
+            Addr::unchecked(MOCK_CW2981_ADDR),
+            "1",
+            None,
+            None,
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::Buy {
+            contract_address: MOCK_CW2981_ADDR.to_string(),
+            token_id: "1".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
+        };
+        let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
+
+        let response = execute(deps.as_mut(), mock_env(), mock_info_buyer, msg).unwrap();
+        assert_eq!(2, response.messages.len());
+        assert_eq!(
+            response.messages[1],
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner".to_string(),
+                amount: vec![cosmwasm_std::coin(100, "uaura")],
+            })),
+            "should transfer all funds to owner"
+        );
+    }
+
+    #[test]
+    fn cannot_buy_when_royalty_info_is_invalid() {
+        let mut deps = mock_deps();
+
+        // the mock collection fails the RoyaltyInfo query of token 4
+        create_listing(
+            deps.as_mut(),
+            "owner",
+            Addr::unchecked(MOCK_CW2981_ADDR),
+            "4",
+            None,
+            None,
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::Buy {
+            contract_address: MOCK_CW2981_ADDR.to_string(),
+            token_id: "4".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
+        };
+        let mock_info_buyer = mock_info("buyer", &coins(100, "uaura"));
+
+        let response = execute(deps.as_mut(), mock_env(), mock_info_buyer, msg);
+        match response {
+            Ok(_) => panic!("Expected error"),
+            Err(ContractError::InvalidRoyaltyInfo { contract_address }) => {
+                assert_eq!(contract_address, MOCK_CW2981_ADDR);
+            }
+            Err(e) => panic!("Unexpected error: {}", e),
+        }
+    }
+}
+
+// fn create_offer(
+//     deps: DepsMut,
+//     sender: &str,
+//     contract_address: Addr,
+//     token_id: Option<String>,
+//     funds_amount: u128,
+//     end_time: Cw20Expiration,
+// ) -> Result<Response, ContractError> {
+//     let msg = ExecuteMsg::OfferNft {
+//         nft: NFT {
+//             contract_address,
+//             token_id,
+//         },
+//         funds_amount,
+//         end_time,
+//     };
+//     let info = mock_info(sender, &coins(1000, "uaura"));
+//     execute(deps, mock_env(), info, msg)
+// }
+
+// fn accept_offer(
+//     deps: DepsMut,
+//     sender: &str,
+//     offerer: &str,
+//     contract_address: Addr,
+//     token_id: Option<String>,
+// ) -> Result<Response, ContractError> {
+//     let msg = ExecuteMsg::AcceptNftOffer {
+//         offerer: offerer.to_string(),
+//         nft: NFT {
+//             contract_address,
+//             token_id,
+//         },
+//     };
+//     let info = mock_info(sender, &coins(1000, "uaura"));
+//     execute(deps, mock_env(), info, msg)
+// }
+
+mod create_offer {
+    // use super::*;
+
+    // // test offer a specific nft
+    // #[test]
+    // fn test_offer_nft() {
+    //     // prepare deps for test
+    //     let mut deps = mock_deps();
+
+    //     // get block time from mock env
+    //     let block_time = mock_env().block.time;
+
+    //     let response = create_offer(
+    //         deps.as_mut(),
+    //         MOCK_OFFER_NFT_OFFERER_1,
+    //         Addr::unchecked(MOCK_CW2981_ADDR),
+    //         Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
+    //         10000000,
+    //         Cw20Expiration::AtTime(block_time.plus_seconds(1000)),
+    //     );
+    //     println!("Response: {:?}", &response);
+    //     assert!(response.is_ok());
+    // }
+
+    // // test user can accept offer
+    // #[test]
+    // fn user_can_not_offer_invalid_nft() {
+    //     // prepare deps for test
+    //     let mut deps = mock_deps();
+
+    //     // get block time from mock env
+    //     let block_time = mock_env().block.time;
+
+    //     let response = create_offer(
+    //         deps.as_mut(),
+    //         MOCK_OFFER_NFT_OFFERER_1,
+    //         Addr::unchecked(MOCK_CW2981_ADDR),
+    //         Some(MOCK_OFFER_NFT_TOKEN_ID_INVALID.to_string()),
+    //         10000000,
+    //         Cw20Expiration::AtTime(block_time.plus_seconds(1000)),
+    //     );
+    //     assert_eq!(
+    //         response.err().unwrap().to_string(),
+    //         ContractError::CustomError {
+    //             val: "Nft not exist".to_string()
+    //         }
+    //         .to_string()
+    //     );
+    // }
+
+    // // cannot offer the owned nft
+    // #[test]
+    // fn cannot_offer_owned_nft() {
+    //     // prepare deps for test
+    //     let mut deps = mock_deps();
+
+    //     // get block time from mock env
+    //     let block_time = mock_env().block.time;
+
+    //     let response = create_offer(
+    //         deps.as_mut(),
+    //         MOCK_OFFER_NFT_OWNER,
+    //         Addr::unchecked(MOCK_CW2981_ADDR),
+    //         Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
+    //         10000000,
+    //         Cw20Expiration::AtTime(block_time.plus_seconds(1000)),
+    //     );
+    //     assert_eq!(
+    //         response.err().unwrap().to_string(),
+    //         ContractError::CustomError {
+    //             val: "Cannot offer owned nft".to_string()
+    //         }
+    //         .to_string()
+    //     );
+    // }
+
+    // // cannot offer without token id
+    // #[test]
+    // fn cannot_offer_without_token_id() {
+    //     // prepare deps for test
+    //     let mut deps = mock_deps();
+
+    //     // get block time from mock env
+    //     let block_time = mock_env().block.time;
+
+    //     let response = create_offer(
+    //         deps.as_mut(),
+    //         MOCK_OFFER_NFT_OFFERER_1,
+    //         Addr::unchecked(MOCK_CW2981_ADDR),
+    //         None,
     //         10000000,
     //         Cw20Expiration::AtTime(block_time.plus_seconds(1000)),
     //     );
@@ -1395,6 +1868,7 @@ mod accept_offer {
                 token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
             },
             funds_amount: MOCK_OFFER_CW20_PRICE,
+            start_time: None,
             end_time: Cw721Expiration::AtTime(app.block_info().time.plus_seconds(1000)),
         };
 
@@ -1449,6 +1923,7 @@ mod accept_offer {
                 token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
             },
             funds_amount: MOCK_OFFER_CW20_PRICE,
+            referrer: None,
         };
 
         // owner (OWNER) accepts offer
@@ -1571,6 +2046,7 @@ mod cancel_offer {
                 token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
             },
             funds_amount: MOCK_OFFER_CW20_PRICE,
+            start_time: None,
             end_time: Cw721Expiration::AtTime(app.block_info().time.plus_seconds(1000)),
         };
 
@@ -1628,3 +2104,450 @@ mod cancel_offer {
             .contains("OrderComponents not found"));
     }
 }
+
+mod buy_with_vaura {
+    use super::*;
+    use cw2981_royalties::{Metadata, MintMsg};
+    use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+
+    const MOCK_LISTING_PRICE: u128 = 1000;
+
+    // mint a NFT to OWNER and list it with a native price
+    fn mint_and_list_nft(
+        app: &mut cw_multi_test::App,
+        cw2981_address: &str,
+        marketplace_address: &str,
+    ) {
+        let mint_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Mint(MintMsg {
+            token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+            owner: OWNER.to_string(),
+            token_uri: None,
+            extension: Metadata::default(),
+        });
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(cw2981_address),
+            &mint_msg,
+            &[],
+        )
+        .unwrap();
+
+        let approve_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Approve {
+            spender: marketplace_address.to_string(),
+            token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+            expires: None,
+        };
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(cw2981_address),
+            &approve_msg,
+            &[],
+        )
+        .unwrap();
+
+        let list_nft_msg = ExecuteMsg::ListNft {
+            contract_address: cw2981_address.to_string(),
+            token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+            auction_config: AuctionConfig::FixedPrice {
+                price: Coin {
+                    denom: NATIVE_DENOM.to_string(),
+                    amount: Uint128::from(MOCK_LISTING_PRICE),
+                },
+                start_time: None,
+                end_time: None,
+            },
+        };
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(marketplace_address),
+            &list_nft_msg,
+            &[],
+        )
+        .unwrap();
+    }
+
+    // user can pay for a native listing with vaura
+    #[test]
+    fn user_can_buy_listing_with_vaura() {
+        // get integration test app and contracts
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+        let cw20_address = contracts[2].contract_addr.clone();
+
+        mint_and_list_nft(&mut app, &cw2981_address, &marketplace_address);
+
+        // USER_1 converts native token to vaura
+        app.sudo(cw_multi_test::SudoMsg::Bank(
+            cw_multi_test::BankSudo::Mint {
+                to_address: USER_1.to_string(),
+                amount: vec![Coin {
+                    amount: Uint128::from(MOCK_LISTING_PRICE),
+                    denom: NATIVE_DENOM.to_string(),
+                }],
+            },
+        ))
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(cw20_address.clone()),
+            &cw20::Cw20ExecuteMsg::Mint {
+                recipient: USER_1.to_string(),
+                amount: Uint128::from(MOCK_LISTING_PRICE),
+            },
+            &[Coin {
+                amount: Uint128::from(MOCK_LISTING_PRICE),
+                denom: NATIVE_DENOM.to_string(),
+            }],
+        )
+        .unwrap();
+
+        let owner_balance_before = app.wrap().query_balance(OWNER, NATIVE_DENOM).unwrap();
+
+        // USER_1 buys the listing with vaura
+        app.execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(marketplace_address.clone()),
+            &ExecuteMsg::Buy {
+                contract_address: cw2981_address.clone(),
+                token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                max_price: None,
+                referrer: None,
+                pay_with_vaura: Some(true),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // USER_1 owns the nft
+        let res: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                Addr::unchecked(cw2981_address),
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(res.owner, USER_1.to_string());
+
+        // the vaura of USER_1 is spent
+        let balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw20_address,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: USER_1.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::zero());
+
+        // the seller receives native token
+        let owner_balance_after = app.wrap().query_balance(OWNER, NATIVE_DENOM).unwrap();
+        assert_eq!(
+            owner_balance_after.amount - owner_balance_before.amount,
+            Uint128::from(MOCK_LISTING_PRICE)
+        );
+
+        // the marketplace keeps nothing
+        let marketplace_balance = app
+            .wrap()
+            .query_balance(marketplace_address, NATIVE_DENOM)
+            .unwrap();
+        assert_eq!(marketplace_balance.amount, Uint128::zero());
+    }
+
+    // user cannot buy with vaura if the balance is not enough
+    #[test]
+    fn user_cannot_buy_listing_without_enough_vaura() {
+        // get integration test app and contracts
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+
+        mint_and_list_nft(&mut app, &cw2981_address, &marketplace_address);
+
+        let response = app.execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(marketplace_address),
+            &ExecuteMsg::Buy {
+                contract_address: cw2981_address,
+                token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                max_price: None,
+                referrer: None,
+                pay_with_vaura: Some(true),
+            },
+            &[],
+        );
+        assert_eq!(
+            response.err().unwrap().source().unwrap().to_string(),
+            ContractError::InsufficientBalance {}.to_string()
+        );
+    }
+}
+
+mod listing_approval {
+    use super::*;
+    use crate::state::{ApprovalType, Listing};
+    use cw2981_royalties::{Metadata, MintMsg};
+    use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+
+    const MOCK_LISTING_PRICE: u128 = 1000;
+
+    fn mint_nft(app: &mut cw_multi_test::App, cw2981_address: &str) {
+        let mint_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Mint(MintMsg {
+            token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+            owner: OWNER.to_string(),
+            token_uri: None,
+            extension: Metadata::default(),
+        });
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(cw2981_address),
+            &mint_msg,
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn approve(
+        app: &mut cw_multi_test::App,
+        cw2981_address: &str,
+        msg: Cw721ExecuteMsg<Metadata, Metadata>,
+    ) {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(cw2981_address),
+            &msg,
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn list_nft(
+        app: &mut cw_multi_test::App,
+        cw2981_address: &str,
+        marketplace_address: &str,
+        end_time: Option<Cw721Expiration>,
+    ) -> anyhow::Result<cw_multi_test::AppResponse> {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(marketplace_address),
+            &ExecuteMsg::ListNft {
+                contract_address: cw2981_address.to_string(),
+                token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                auction_config: AuctionConfig::FixedPrice {
+                    price: Coin {
+                        denom: NATIVE_DENOM.to_string(),
+                        amount: Uint128::from(MOCK_LISTING_PRICE),
+                    },
+                    start_time: None,
+                    end_time,
+                },
+            },
+            &[],
+        )
+    }
+
+    fn query_listing(
+        app: &cw_multi_test::App,
+        cw2981_address: &str,
+        marketplace_address: &str,
+    ) -> Listing {
+        app.wrap()
+            .query_wasm_smart(
+                marketplace_address,
+                &QueryMsg::Listing {
+                    contract_address: cw2981_address.to_string(),
+                    token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    fn end_time(listing: &Listing) -> Option<Cw721Expiration> {
+        match listing.auction_config {
+            AuctionConfig::FixedPrice { end_time, .. } => end_time,
+        }
+    }
+
+    #[test]
+    fn cannot_list_without_approval() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+
+        mint_nft(&mut app, &cw2981_address);
+
+        let res = list_nft(&mut app, &cw2981_address, &marketplace_address, None);
+        assert_eq!(
+            res.unwrap_err().source().unwrap().to_string(),
+            ContractError::MissingApproval {}.to_string()
+        );
+    }
+
+    #[test]
+    fn can_list_and_sell_with_operator_approval() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+
+        mint_nft(&mut app, &cw2981_address);
+        approve(
+            &mut app,
+            &cw2981_address,
+            Cw721ExecuteMsg::ApproveAll {
+                operator: marketplace_address.clone(),
+                expires: None,
+            },
+        );
+
+        list_nft(&mut app, &cw2981_address, &marketplace_address, None).unwrap();
+
+        let listing = query_listing(&app, &cw2981_address, &marketplace_address);
+        assert_eq!(listing.approval_type, ApprovalType::Operator);
+        assert_eq!(end_time(&listing), None);
+
+        // the operator approval is enough to transfer the nft to the buyer
+        app.execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(marketplace_address),
+            &ExecuteMsg::Buy {
+                contract_address: cw2981_address.clone(),
+                token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                max_price: None,
+                referrer: None,
+                pay_with_vaura: None,
+            },
+            &[Coin {
+                denom: NATIVE_DENOM.to_string(),
+                amount: Uint128::from(MOCK_LISTING_PRICE),
+            }],
+        )
+        .unwrap();
+
+        let res: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw2981_address,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(res.owner, USER_1.to_string());
+    }
+
+    #[test]
+    fn token_approval_is_recorded() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+
+        mint_nft(&mut app, &cw2981_address);
+        approve(
+            &mut app,
+            &cw2981_address,
+            Cw721ExecuteMsg::Approve {
+                spender: marketplace_address.clone(),
+                token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                expires: None,
+            },
+        );
+
+        list_nft(&mut app, &cw2981_address, &marketplace_address, None).unwrap();
+
+        let listing = query_listing(&app, &cw2981_address, &marketplace_address);
+        assert_eq!(listing.approval_type, ApprovalType::Token);
+    }
+
+    #[test]
+    fn end_time_is_capped_at_approval_expiry() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+        let height = app.block_info().height;
+        let expires = Cw721Expiration::AtHeight(height + 100);
+
+        mint_nft(&mut app, &cw2981_address);
+        approve(
+            &mut app,
+            &cw2981_address,
+            Cw721ExecuteMsg::ApproveAll {
+                operator: marketplace_address.clone(),
+                expires: Some(expires),
+            },
+        );
+
+        // a listing without end_time ends when the approval expires
+        list_nft(&mut app, &cw2981_address, &marketplace_address, None).unwrap();
+        let listing = query_listing(&app, &cw2981_address, &marketplace_address);
+        assert_eq!(end_time(&listing), Some(expires));
+
+        // a later end_time is capped
+        list_nft(
+            &mut app,
+            &cw2981_address,
+            &marketplace_address,
+            Some(Cw721Expiration::AtHeight(height + 200)),
+        )
+        .unwrap();
+        let listing = query_listing(&app, &cw2981_address, &marketplace_address);
+        assert_eq!(end_time(&listing), Some(expires));
+
+        // an earlier end_time is kept
+        let earlier = Cw721Expiration::AtHeight(height + 50);
+        list_nft(
+            &mut app,
+            &cw2981_address,
+            &marketplace_address,
+            Some(earlier),
+        )
+        .unwrap();
+        let listing = query_listing(&app, &cw2981_address, &marketplace_address);
+        assert_eq!(end_time(&listing), Some(earlier));
+
+        // a time cannot be compared with the height of the approval
+        let end_time = Cw721Expiration::AtTime(app.block_info().time.plus_seconds(100));
+        let res = list_nft(
+            &mut app,
+            &cw2981_address,
+            &marketplace_address,
+            Some(end_time),
+        );
+        assert_eq!(
+            res.unwrap_err().source().unwrap().to_string(),
+            ContractError::ApprovalExpirationMismatch { expires, end_time }.to_string()
+        );
+    }
+
+    #[test]
+    fn cannot_list_with_expired_approval() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+
+        let height = app.block_info().height;
+
+        mint_nft(&mut app, &cw2981_address);
+        approve(
+            &mut app,
+            &cw2981_address,
+            Cw721ExecuteMsg::Approve {
+                spender: marketplace_address.clone(),
+                token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                expires: Some(Cw721Expiration::AtHeight(height + 1)),
+            },
+        );
+        app.update_block(|block| block.height += 1);
+
+        let res = list_nft(&mut app, &cw2981_address, &marketplace_address, None);
+        assert_eq!(
+            res.unwrap_err().source().unwrap().to_string(),
+            ContractError::MissingApproval {}.to_string()
+        );
+    }
+}
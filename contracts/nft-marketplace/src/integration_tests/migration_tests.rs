@@ -0,0 +1,243 @@
+use crate::contract::{migrate, query, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::migrations::MIGRATIONS;
+use crate::msg::{CollectionStatsResponse, MigrateMsg, QueryMsg};
+use crate::order_state::{
+    consideration_item, offer_item, order_key, Asset, ConsiderationItem, ItemType, OfferItem,
+    OrderComponents, OrderKey, OrderType, CW20, NFT,
+};
+use crate::state::{listing_key, AuctionConfig, Config, ListingKey, MAX_BASIS_POINTS};
+use crate::ContractError;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{coin, from_binary, Addr, OwnedDeps};
+use cw721::Expiration as Cw721Expiration;
+use cw_storage_plus::{Item, Map};
+
+const MOCK_CW2981_ADDR: &str = "cw2981_addr";
+const MOCK_CW20_ADDR: &str = "cw20_addr";
+
+// the config stored by 0.1.0
+#[cw_serde]
+struct ConfigV0_1_0 {
+    owner: Addr,
+}
+
+// the config stored by 0.1.1
+#[cw_serde]
+struct ConfigV0_1_1 {
+    owner: Addr,
+    vaura_address: Addr,
+}
+
+// the listing stored by 0.1.x
+#[cw_serde]
+#[allow(non_camel_case_types)]
+struct ListingV0_1_x {
+    contract_address: Addr,
+    token_id: String,
+    auction_config: AuctionConfig,
+    seller: Addr,
+    buyer: Option<Addr>,
+}
+
+// the order stored by 0.1.x
+#[cw_serde]
+#[allow(non_camel_case_types)]
+struct OrderComponentsV0_1_x {
+    order_type: OrderType,
+    order_id: OrderKey,
+    offerer: Addr,
+    offer: Vec<OfferItem>,
+    consideration: Vec<ConsiderationItem>,
+    start_time: Option<Cw721Expiration>,
+    end_time: Option<Cw721Expiration>,
+}
+
+// the listings and orders of the old versions are stored without the price indexes
+fn store_old_trades(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    let listings: Map<ListingKey, ListingV0_1_x> = Map::new("listings");
+    for (token_id, seller, price) in [
+        ("1", "seller1", 100),
+        ("2", "seller1", 50),
+        ("3", "seller2", 80),
+    ] {
+        let contract_address = Addr::unchecked(MOCK_CW2981_ADDR);
+        let token_id = token_id.to_string();
+        listings
+            .save(
+                &mut deps.storage,
+                listing_key(&contract_address, &token_id),
+                &ListingV0_1_x {
+                    contract_address: contract_address.clone(),
+                    token_id,
+                    auction_config: AuctionConfig::FixedPrice {
+                        price: coin(price, "uaura"),
+                        start_time: None,
+                        end_time: None,
+                    },
+                    seller: Addr::unchecked(seller),
+                    buyer: None,
+                },
+            )
+            .unwrap();
+    }
+
+    let orders: Map<OrderKey, OrderComponentsV0_1_x> = Map::new("orders");
+    let offerer = Addr::unchecked("offerer");
+    let contract_address = Addr::unchecked(MOCK_CW2981_ADDR);
+    let key = order_key(&offerer, &contract_address, "1");
+    orders
+        .save(
+            &mut deps.storage,
+            key.clone(),
+            &OrderComponentsV0_1_x {
+                order_type: OrderType::OFFER,
+                order_id: key,
+                offerer: offerer.clone(),
+                offer: vec![offer_item(
+                    &ItemType::CW20,
+                    &Asset::Cw20(CW20 {
+                        contract_address: Addr::unchecked(MOCK_CW20_ADDR),
+                        amount: 70,
+                    }),
+                    &0u128,
+                    &0u128,
+                )],
+                consideration: vec![consideration_item(
+                    &ItemType::CW721,
+                    &Asset::Nft(NFT {
+                        contract_address,
+                        token_id: Some("1".to_string()),
+                    }),
+                    &0u128,
+                    &0u128,
+                    &offerer,
+                )],
+                start_time: None,
+                end_time: Some(Cw721Expiration::Never {}),
+            },
+        )
+        .unwrap();
+}
+
+fn query_config(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Config {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap()
+}
+
+// the migrated storage has a complete config, price indexes and aggregates
+fn assert_migrated(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, vaura_address: &str) {
+    assert_eq!(
+        query_config(deps),
+        Config {
+            owner: Addr::unchecked("owner"),
+            vaura_address: Addr::unchecked(vaura_address),
+            max_royalty_bps: MAX_BASIS_POINTS,
+            protocol_fee_bps: 0,
+            referral_fee_bps: 0,
+        }
+    );
+
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(version.version, CONTRACT_VERSION);
+
+    let stats: CollectionStatsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CollectionStats {
+                contract_address: MOCK_CW2981_ADDR.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(stats.listed_count, 3);
+    assert_eq!(stats.unique_sellers, 2);
+    assert_eq!(stats.floor_prices, vec![coin(50, "uaura")]);
+
+    let best_offer: Option<OrderComponents> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::BestOffer {
+                contract_address: MOCK_CW2981_ADDR.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(best_offer.unwrap().offer_amount(), 70);
+}
+
+// the version gate skips every applied step, so the steps are called directly
+fn rerun_migration_steps(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    for (_, step) in MIGRATIONS {
+        step(&mut deps.storage).unwrap();
+    }
+}
+
+#[test]
+fn migrate_from_v0_1_0() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+    Item::new("config")
+        .save(
+            &mut deps.storage,
+            &ConfigV0_1_0 {
+                owner: Addr::unchecked("owner"),
+            },
+        )
+        .unwrap();
+    store_old_trades(&mut deps);
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_migrated(&deps, "aura0");
+
+    // running the steps again on the migrated storage changes nothing
+    rerun_migration_steps(&mut deps);
+    assert_migrated(&deps, "aura0");
+}
+
+#[test]
+fn migrate_from_v0_1_1() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.1").unwrap();
+    Item::new("config")
+        .save(
+            &mut deps.storage,
+            &ConfigV0_1_1 {
+                owner: Addr::unchecked("owner"),
+                vaura_address: Addr::unchecked("vaura"),
+            },
+        )
+        .unwrap();
+    store_old_trades(&mut deps);
+
+    // the vaura address is kept
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_migrated(&deps, "vaura");
+
+    rerun_migration_steps(&mut deps);
+    assert_migrated(&deps, "vaura");
+}
+
+#[test]
+fn cannot_migrate_to_older_version_or_other_contract() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+    let response = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+    assert!(matches!(
+        response,
+        Err(ContractError::CannotDowngrade { .. })
+    ));
+
+    cw2::set_contract_version(&mut deps.storage, "crates.io:other", "0.1.0").unwrap();
+    let response = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+    assert!(matches!(
+        response,
+        Err(ContractError::InvalidMigrationContract { .. })
+    ));
+}
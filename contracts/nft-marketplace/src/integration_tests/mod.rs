@@ -1,4 +1,10 @@
 #![cfg(test)]
+pub mod event_tests;
+pub mod hook_tests;
 #[cfg(test)]
 pub mod listing_tests;
+pub mod migration_tests;
 pub mod offer_tests;
+pub mod rental_tests;
+pub mod stats_tests;
+pub mod sudo_tests;
@@ -52,6 +52,7 @@ fn create_offer(
             token_id: Some(token_id.to_string()),
         },
         funds_amount: MOCK_OFFER_CW20_PRICE,
+        start_time: None,
         end_time: Cw721Expiration::AtTime(app.block_info().time.plus_seconds(1000)),
     };
 
@@ -202,6 +203,7 @@ mod accept_offer {
                 token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
             },
             funds_amount: MOCK_OFFER_CW20_PRICE,
+            referrer: None,
         };
 
         // owner (OWNER) accepts offer
@@ -320,6 +322,7 @@ mod accept_offer {
                 token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
             },
             funds_amount: MOCK_OFFER_CW20_PRICE,
+            referrer: None,
         };
         let res = app.execute_contract(
             Addr::unchecked(USER_1),
@@ -505,6 +508,7 @@ mod accept_offer {
                 token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
             },
             funds_amount: MOCK_OFFER_CW20_PRICE,
+            start_time: None,
             end_time: Cw721Expiration::AtTime(app.block_info().time.plus_seconds(1000)),
         };
 
@@ -559,6 +563,7 @@ mod accept_offer {
                 token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
             },
             funds_amount: MOCK_OFFER_CW20_PRICE,
+            referrer: None,
         };
 
         // owner (OWNER) accepts offer
@@ -611,3 +616,529 @@ mod accept_offer {
         assert_eq!(res.balance, Uint128::from(MOCK_OFFER_CW20_PRICE));
     }
 }
+
+mod create_offer {
+    use super::*;
+
+    use crate::ContractError;
+
+    // mint NFT to OWNER and vaura to USER_1, then approve marketplace to transfer NFT of OWNER
+    fn setup_offer(app: &mut App, cw2981_address: &str, marketplace_address: &str, cw20: &str) {
+        mint_nft(
+            app,
+            MOCK_OFFER_NFT_TOKEN_ID_1,
+            OWNER,
+            cw2981_address.to_string(),
+        );
+
+        app.execute_contract(
+            Addr::unchecked(USER_1.to_string()),
+            Addr::unchecked(cw20),
+            &cw20::Cw20ExecuteMsg::Mint {
+                recipient: USER_1.to_string(),
+                amount: Uint128::from(100000000u128),
+            },
+            &[Coin {
+                amount: Uint128::from(100000000u128),
+                denom: NATIVE_DENOM.to_string(),
+            }],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(cw2981_address),
+            &cw721::Cw721ExecuteMsg::ApproveAll {
+                operator: marketplace_address.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn accept_offer_msg(cw2981_address: &str) -> ExecuteMsg {
+        ExecuteMsg::AcceptNftOffer {
+            offerer: USER_1.to_string(),
+            nft: NFT {
+                contract_address: Addr::unchecked(cw2981_address),
+                token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
+            },
+            funds_amount: MOCK_OFFER_CW20_PRICE,
+            referrer: None,
+        }
+    }
+
+    #[test]
+    fn cannot_accept_offer_before_start_time() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+        let cw20_address = contracts[2].contract_addr.clone();
+
+        setup_offer(
+            &mut app,
+            &cw2981_address,
+            &marketplace_address,
+            &cw20_address,
+        );
+
+        // start time must be before end time
+        let now = app.block_info().time;
+        let offer_nft_msg = ExecuteMsg::OfferNft {
+            nft: NFT {
+                contract_address: Addr::unchecked(&cw2981_address),
+                token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
+            },
+            funds_amount: MOCK_OFFER_CW20_PRICE,
+            start_time: Some(Cw721Expiration::AtTime(now.plus_seconds(1000))),
+            end_time: Cw721Expiration::AtTime(now.plus_seconds(1000)),
+        };
+        let err = app
+            .execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(&marketplace_address),
+                &offer_nft_msg,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            ContractError::InvalidStartTime {}.to_string()
+        );
+
+        // USER_1 offers NFT, the offer starts in 100 seconds
+        let offer_nft_msg = ExecuteMsg::OfferNft {
+            nft: NFT {
+                contract_address: Addr::unchecked(&cw2981_address),
+                token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
+            },
+            funds_amount: MOCK_OFFER_CW20_PRICE,
+            start_time: Some(Cw721Expiration::AtTime(now.plus_seconds(100))),
+            end_time: Cw721Expiration::AtTime(now.plus_seconds(1000)),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(&marketplace_address),
+            &offer_nft_msg,
+            &[],
+        )
+        .unwrap();
+
+        // OWNER cannot accept the offer before start time
+        let err = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                Addr::unchecked(&marketplace_address),
+                &accept_offer_msg(&cw2981_address),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            ContractError::OfferNotStarted {}.to_string()
+        );
+
+        // OWNER can accept the offer after start time
+        app.update_block(|block| block.time = block.time.plus_seconds(100));
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(&marketplace_address),
+            &accept_offer_msg(&cw2981_address),
+            &[],
+        )
+        .unwrap();
+
+        let res: cw721::OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                Addr::unchecked(cw2981_address),
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(res.owner, USER_1.to_string(), "Invalid owner");
+    }
+
+    #[test]
+    fn cannot_offer_below_min_offer_amount() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+        let cw20_address = contracts[2].contract_addr.clone();
+
+        setup_offer(
+            &mut app,
+            &cw2981_address,
+            &marketplace_address,
+            &cw20_address,
+        );
+
+        let set_min_offer_msg = ExecuteMsg::SetMinOfferAmount {
+            contract_address: cw2981_address.clone(),
+            min_funds_amount: MOCK_OFFER_CW20_PRICE + 1,
+        };
+
+        // USER_1 is neither the marketplace owner nor the minter of the collection
+        let err = app
+            .execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(&marketplace_address),
+                &set_min_offer_msg,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            ContractError::Unauthorized {}.to_string()
+        );
+
+        // OWNER is the minter of the collection
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(&marketplace_address),
+            &set_min_offer_msg,
+            &[],
+        )
+        .unwrap();
+
+        let min_offer_amount: u128 = app
+            .wrap()
+            .query_wasm_smart(
+                Addr::unchecked(&marketplace_address),
+                &QueryMsg::MinOfferAmount {
+                    contract_address: cw2981_address.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(min_offer_amount, MOCK_OFFER_CW20_PRICE + 1);
+
+        let err = create_offer(
+            &mut app,
+            MOCK_OFFER_NFT_TOKEN_ID_1,
+            USER_1,
+            cw2981_address,
+            marketplace_address,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            ContractError::OfferAmountTooLow {
+                amount: MOCK_OFFER_CW20_PRICE,
+                min_amount: MOCK_OFFER_CW20_PRICE + 1,
+            }
+            .to_string()
+        );
+    }
+}
+
+mod increment_counter {
+    use super::*;
+
+    use crate::order_state::OrderComponents;
+    use crate::ContractError;
+    use cosmwasm_std::StdResult;
+
+    #[test]
+    fn increment_counter_cancels_all_offers() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+
+        mint_nft(
+            &mut app,
+            MOCK_OFFER_NFT_TOKEN_ID_1,
+            OWNER,
+            cw2981_address.clone(),
+        );
+        mint_nft(
+            &mut app,
+            MOCK_OFFER_NFT_TOKEN_ID_2,
+            OWNER,
+            cw2981_address.clone(),
+        );
+
+        // USER_1 offers both NFTs
+        for token_id in [MOCK_OFFER_NFT_TOKEN_ID_1, MOCK_OFFER_NFT_TOKEN_ID_2] {
+            create_offer(
+                &mut app,
+                token_id,
+                USER_1,
+                cw2981_address.clone(),
+                marketplace_address.clone(),
+            )
+            .unwrap();
+        }
+
+        // USER_1 cancels all offers
+        app.execute_contract(
+            Addr::unchecked(USER_1),
+            Addr::unchecked(&marketplace_address),
+            &ExecuteMsg::IncrementCounter {},
+            &[],
+        )
+        .unwrap();
+
+        let counter: u64 = app
+            .wrap()
+            .query_wasm_smart(
+                Addr::unchecked(&marketplace_address),
+                &QueryMsg::Counter {
+                    user: USER_1.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(counter, 1);
+
+        // the offers are no longer returned
+        let res: OffersResponse = app
+            .wrap()
+            .query_wasm_smart(
+                Addr::unchecked(&marketplace_address),
+                &QueryMsg::UserOffers {
+                    offerer: USER_1.to_string(),
+                    start_after_nft: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(res.offers.is_empty());
+
+        let res: StdResult<OrderComponents> = app.wrap().query_wasm_smart(
+            Addr::unchecked(&marketplace_address),
+            &QueryMsg::Offer {
+                contract_address: cw2981_address.clone(),
+                token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                offerer: USER_1.to_string(),
+            },
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("OrderComponents not found"));
+
+        // OWNER cannot accept the cancelled offer
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(&cw2981_address),
+            &cw721::Cw721ExecuteMsg::ApproveAll {
+                operator: marketplace_address.clone(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let accept_offer_msg = ExecuteMsg::AcceptNftOffer {
+            offerer: USER_1.to_string(),
+            nft: NFT {
+                contract_address: Addr::unchecked(&cw2981_address),
+                token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
+            },
+            funds_amount: MOCK_OFFER_CW20_PRICE,
+            referrer: None,
+        };
+        let err = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                Addr::unchecked(&marketplace_address),
+                &accept_offer_msg,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            ContractError::OfferCancelled {}.to_string()
+        );
+
+        // a new offer of USER_1 uses the new counter
+        create_offer(
+            &mut app,
+            MOCK_OFFER_NFT_TOKEN_ID_1,
+            USER_1,
+            cw2981_address.clone(),
+            marketplace_address.clone(),
+        )
+        .unwrap();
+
+        let res: OffersResponse = app
+            .wrap()
+            .query_wasm_smart(
+                Addr::unchecked(&marketplace_address),
+                &QueryMsg::NftOffers {
+                    contract_address: cw2981_address,
+                    token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                    start_after_offerer: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(res.offers.len(), 1);
+        assert_eq!(res.offers[0].counter, 1);
+    }
+}
+
+mod offer_queries {
+    use super::*;
+
+    use crate::order_state::OrderComponents;
+    use cosmwasm_std::StdResult;
+
+    const USER_3: &str = "aura1000000000000000000000000000000000user3";
+
+    fn offer_with_amount(
+        app: &mut App,
+        offerer: &str,
+        funds_amount: u128,
+        duration: u64,
+        cw2981_address: &str,
+        marketplace_address: &str,
+    ) {
+        let offer_nft_msg = ExecuteMsg::OfferNft {
+            nft: NFT {
+                contract_address: Addr::unchecked(cw2981_address),
+                token_id: Some(MOCK_OFFER_NFT_TOKEN_ID_1.to_string()),
+            },
+            funds_amount,
+            start_time: None,
+            end_time: Cw721Expiration::AtTime(app.block_info().time.plus_seconds(duration)),
+        };
+        app.execute_contract(
+            Addr::unchecked(offerer),
+            Addr::unchecked(marketplace_address),
+            &offer_nft_msg,
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn query_offers_sorted_by_price() {
+        let (mut app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+
+        mint_nft(
+            &mut app,
+            MOCK_OFFER_NFT_TOKEN_ID_1,
+            OWNER,
+            cw2981_address.clone(),
+        );
+
+        // the highest offer of USER_2 expires first
+        offer_with_amount(
+            &mut app,
+            USER_1,
+            10000000,
+            1000,
+            &cw2981_address,
+            &marketplace_address,
+        );
+        offer_with_amount(
+            &mut app,
+            USER_2,
+            30000000,
+            100,
+            &cw2981_address,
+            &marketplace_address,
+        );
+        offer_with_amount(
+            &mut app,
+            USER_3,
+            20000000,
+            1000,
+            &cw2981_address,
+            &marketplace_address,
+        );
+
+        let query_offers = |app: &App, start_after: Option<(u128, String)>, include_expired| {
+            let res: OffersResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    Addr::unchecked(&marketplace_address),
+                    &QueryMsg::NftOffersByPrice {
+                        contract_address: cw2981_address.clone(),
+                        token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                        start_after,
+                        limit: Some(2),
+                        include_expired,
+                    },
+                )
+                .unwrap();
+            res.offers
+                .iter()
+                .map(|offer| (offer.offerer.to_string(), offer.offer_amount()))
+                .collect::<Vec<_>>()
+        };
+        let query_best_offer = |app: &App| {
+            let res: Option<OrderComponents> = app
+                .wrap()
+                .query_wasm_smart(
+                    Addr::unchecked(&marketplace_address),
+                    &QueryMsg::BestOffer {
+                        contract_address: cw2981_address.clone(),
+                        token_id: MOCK_OFFER_NFT_TOKEN_ID_1.to_string(),
+                    },
+                )
+                .unwrap();
+            res.map(|offer| offer.offerer.to_string())
+        };
+
+        assert_eq!(
+            query_offers(&app, None, None),
+            vec![
+                (USER_2.to_string(), 30000000),
+                (USER_3.to_string(), 20000000)
+            ]
+        );
+        assert_eq!(
+            query_offers(&app, Some((20000000, USER_3.to_string())), None),
+            vec![(USER_1.to_string(), 10000000)]
+        );
+        assert_eq!(query_best_offer(&app), Some(USER_2.to_string()));
+
+        // the offer of USER_2 expires
+        app.update_block(|block| block.time = block.time.plus_seconds(200));
+
+        assert_eq!(
+            query_offers(&app, None, None),
+            vec![
+                (USER_3.to_string(), 20000000),
+                (USER_1.to_string(), 10000000)
+            ]
+        );
+        assert_eq!(
+            query_offers(&app, None, Some(true)),
+            vec![
+                (USER_2.to_string(), 30000000),
+                (USER_3.to_string(), 20000000)
+            ]
+        );
+        assert_eq!(query_best_offer(&app), Some(USER_3.to_string()));
+    }
+
+    #[test]
+    fn cannot_query_user_offers_after_nft_without_token_id() {
+        let (app, contracts) = instantiate_contracts();
+        let cw2981_address = contracts[0].contract_addr.clone();
+        let marketplace_address = contracts[1].contract_addr.clone();
+
+        let res: StdResult<OffersResponse> = app.wrap().query_wasm_smart(
+            Addr::unchecked(&marketplace_address),
+            &QueryMsg::UserOffers {
+                offerer: USER_1.to_string(),
+                start_after_nft: Some(NFT {
+                    contract_address: Addr::unchecked(&cw2981_address),
+                    token_id: None,
+                }),
+                limit: None,
+            },
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("start_after_nft must have a token_id"));
+    }
+}
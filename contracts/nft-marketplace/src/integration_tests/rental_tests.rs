@@ -0,0 +1,447 @@
+use crate::msg::{ExecuteMsg, QueryMsg, RentalsResponse};
+use crate::rental_state::{Rental, RentalListing};
+use crate::test_setup::env::{instantiate_contracts, NATIVE_DENOM, OWNER, USER_1, USER_2};
+use crate::ContractError;
+
+use cosmwasm_std::{coin, Addr, Coin};
+use cw2981_royalties::msg::{Cw2981QueryMsg, Cw4907ExecuteMsg, UserOfResponse};
+use cw2981_royalties::{
+    ExecuteMsg as Cw2981ExecuteMsg, Metadata, MintMsg, QueryMsg as Cw2981QueryMsgWrapper,
+};
+use cw721::{Expiration as Cw721Expiration, OwnerOfResponse};
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{App, AppResponse, Executor};
+
+const TOKEN_ID: &str = "1";
+const PRICE_PER_UNIT: u128 = 100;
+const UNIT_SECONDS: u64 = 3600;
+const MAX_UNITS: u64 = 24;
+
+// mint a NFT to OWNER, approve marketplace to transfer it and list it for rent
+fn mint_and_list_rental(app: &mut App, cw2981_address: &str, marketplace_address: &str) {
+    let mint_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Mint(MintMsg {
+        token_id: TOKEN_ID.to_string(),
+        owner: OWNER.to_string(),
+        token_uri: None,
+        extension: Metadata::default(),
+    });
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(cw2981_address),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+
+    let approve_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Approve {
+        spender: marketplace_address.to_string(),
+        token_id: TOKEN_ID.to_string(),
+        expires: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(cw2981_address),
+        &approve_msg,
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(marketplace_address),
+        &ExecuteMsg::ListRental {
+            contract_address: cw2981_address.to_string(),
+            token_id: TOKEN_ID.to_string(),
+            price_per_unit: coin(PRICE_PER_UNIT, NATIVE_DENOM),
+            unit_seconds: UNIT_SECONDS,
+            max_units: MAX_UNITS,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn rent(
+    app: &mut App,
+    renter: &str,
+    units: u64,
+    funds: &[Coin],
+    cw2981_address: &str,
+    marketplace_address: &str,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(renter),
+        Addr::unchecked(marketplace_address),
+        &ExecuteMsg::Rent {
+            contract_address: cw2981_address.to_string(),
+            token_id: TOKEN_ID.to_string(),
+            units,
+        },
+        funds,
+    )
+}
+
+fn query_user_of(app: &App, cw2981_address: &str) -> UserOfResponse {
+    app.wrap()
+        .query_wasm_smart(
+            cw2981_address,
+            &Cw2981QueryMsgWrapper::Extension {
+                msg: Cw2981QueryMsg::UserOf {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            },
+        )
+        .unwrap()
+}
+
+fn query_active_rental(
+    app: &App,
+    cw2981_address: &str,
+    marketplace_address: &str,
+) -> Option<Rental> {
+    app.wrap()
+        .query_wasm_smart(
+            marketplace_address,
+            &QueryMsg::ActiveRental {
+                contract_address: cw2981_address.to_string(),
+                token_id: TOKEN_ID.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+fn query_active_rentals_by_renter(
+    app: &App,
+    renter: &str,
+    marketplace_address: &str,
+) -> Vec<Rental> {
+    let res: RentalsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            marketplace_address,
+            &QueryMsg::ActiveRentalsByRenter {
+                renter: renter.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    res.rentals
+}
+
+#[test]
+fn renter_becomes_user_of_nft() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    mint_and_list_rental(&mut app, &cw2981_address, &marketplace_address);
+
+    let rental_listing: RentalListing = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace_address,
+            &QueryMsg::RentalListing {
+                contract_address: cw2981_address.clone(),
+                token_id: TOKEN_ID.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(rental_listing.lender, Addr::unchecked(OWNER));
+    assert_eq!(rental_listing.max_units, MAX_UNITS);
+
+    let owner_balance_before = app.wrap().query_balance(OWNER, NATIVE_DENOM).unwrap();
+    let renter_balance_before = app.wrap().query_balance(USER_1, NATIVE_DENOM).unwrap();
+
+    // USER_1 rents the nft for 2 units and sends more than the price
+    rent(
+        &mut app,
+        USER_1,
+        2,
+        &[coin(250, NATIVE_DENOM)],
+        &cw2981_address,
+        &marketplace_address,
+    )
+    .unwrap();
+
+    // the lender receives the price and the surplus is refunded
+    let owner_balance_after = app.wrap().query_balance(OWNER, NATIVE_DENOM).unwrap();
+    let renter_balance_after = app.wrap().query_balance(USER_1, NATIVE_DENOM).unwrap();
+    assert_eq!(
+        (owner_balance_after.amount - owner_balance_before.amount).u128(),
+        2 * PRICE_PER_UNIT
+    );
+    assert_eq!(
+        (renter_balance_before.amount - renter_balance_after.amount).u128(),
+        2 * PRICE_PER_UNIT
+    );
+
+    // USER_1 is the user until the rental expires, OWNER keeps the ownership
+    let expires = Cw721Expiration::AtTime(app.block_info().time.plus_seconds(2 * UNIT_SECONDS));
+    assert_eq!(
+        query_user_of(&app, &cw2981_address),
+        UserOfResponse {
+            user: Some(USER_1.to_string()),
+            expires: Some(expires),
+        }
+    );
+    let owner: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &cw2981_address,
+            &Cw2981QueryMsgWrapper::OwnerOf {
+                token_id: TOKEN_ID.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, OWNER.to_string());
+
+    let rental = query_active_rental(&app, &cw2981_address, &marketplace_address).unwrap();
+    assert_eq!(rental.renter, Addr::unchecked(USER_1));
+    assert_eq!(rental.price, coin(2 * PRICE_PER_UNIT, NATIVE_DENOM));
+    assert_eq!(rental.expires, expires);
+    assert_eq!(
+        query_active_rentals_by_renter(&app, USER_1, &marketplace_address),
+        vec![rental]
+    );
+
+    // the nft cannot be rented again before the rental expires
+    let res = rent(
+        &mut app,
+        USER_2,
+        1,
+        &[coin(PRICE_PER_UNIT, NATIVE_DENOM)],
+        &cw2981_address,
+        &marketplace_address,
+    );
+    assert_eq!(
+        res.unwrap_err().source().unwrap().to_string(),
+        ContractError::NftAlreadyRented { expires }.to_string()
+    );
+
+    // after the rental expires, the nft has no user and can be rented again
+    app.update_block(|block| block.time = block.time.plus_seconds(2 * UNIT_SECONDS));
+    assert_eq!(query_user_of(&app, &cw2981_address).user, None);
+    assert_eq!(
+        query_active_rental(&app, &cw2981_address, &marketplace_address),
+        None
+    );
+    assert!(query_active_rentals_by_renter(&app, USER_1, &marketplace_address).is_empty());
+
+    rent(
+        &mut app,
+        USER_2,
+        1,
+        &[coin(PRICE_PER_UNIT, NATIVE_DENOM)],
+        &cw2981_address,
+        &marketplace_address,
+    )
+    .unwrap();
+    assert_eq!(
+        query_user_of(&app, &cw2981_address).user,
+        Some(USER_2.to_string())
+    );
+}
+
+#[test]
+fn cannot_rent_with_invalid_duration_or_funds() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    mint_and_list_rental(&mut app, &cw2981_address, &marketplace_address);
+
+    for units in [0, MAX_UNITS + 1] {
+        let res = rent(
+            &mut app,
+            USER_1,
+            units,
+            &[coin(PRICE_PER_UNIT * 30, NATIVE_DENOM)],
+            &cw2981_address,
+            &marketplace_address,
+        );
+        assert_eq!(
+            res.unwrap_err().source().unwrap().to_string(),
+            ContractError::InvalidRentalDuration {
+                units,
+                max_units: MAX_UNITS
+            }
+            .to_string()
+        );
+    }
+
+    let res = rent(
+        &mut app,
+        USER_1,
+        2,
+        &[coin(PRICE_PER_UNIT, NATIVE_DENOM)],
+        &cw2981_address,
+        &marketplace_address,
+    );
+    assert_eq!(
+        res.unwrap_err().source().unwrap().to_string(),
+        ContractError::InsufficientFunds {}.to_string()
+    );
+
+    // the lender cannot rent their own nft
+    let res = rent(
+        &mut app,
+        OWNER,
+        1,
+        &[coin(PRICE_PER_UNIT, NATIVE_DENOM)],
+        &cw2981_address,
+        &marketplace_address,
+    );
+    assert_eq!(
+        res.unwrap_err().source().unwrap().to_string(),
+        ContractError::CannotRentOwnNft {}.to_string()
+    );
+}
+
+#[test]
+fn only_lender_can_cancel_rental_listing() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    mint_and_list_rental(&mut app, &cw2981_address, &marketplace_address);
+
+    let cancel_msg = ExecuteMsg::CancelRental {
+        contract_address: cw2981_address.clone(),
+        token_id: TOKEN_ID.to_string(),
+    };
+    let res = app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&marketplace_address),
+        &cancel_msg,
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().source().unwrap().to_string(),
+        ContractError::Unauthorized {}.to_string()
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &cancel_msg,
+        &[],
+    )
+    .unwrap();
+
+    // the nft cannot be rented after the listing is cancelled
+    let res = rent(
+        &mut app,
+        USER_1,
+        1,
+        &[coin(PRICE_PER_UNIT, NATIVE_DENOM)],
+        &cw2981_address,
+        &marketplace_address,
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn cannot_list_rental_with_invalid_config() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    mint_and_list_rental(&mut app, &cw2981_address, &marketplace_address);
+
+    // the longest rentals of the last two configs end after the largest timestamp
+    for (price_per_unit, unit_seconds, max_units) in [
+        (0, 3600, 1),
+        (100, 0, 1),
+        (100, 3600, 0),
+        (100, u64::MAX, 2),
+        (100, u64::MAX / 1_000_000_000, 1),
+    ] {
+        let res = app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(&marketplace_address),
+            &ExecuteMsg::ListRental {
+                contract_address: cw2981_address.clone(),
+                token_id: TOKEN_ID.to_string(),
+                price_per_unit: coin(price_per_unit, NATIVE_DENOM),
+                unit_seconds,
+                max_units,
+            },
+            &[],
+        );
+        assert_eq!(
+            res.unwrap_err().source().unwrap().to_string(),
+            ContractError::InvalidRentalConfig {}.to_string()
+        );
+    }
+}
+
+#[test]
+fn lender_cannot_end_rental_before_it_expires() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    mint_and_list_rental(&mut app, &cw2981_address, &marketplace_address);
+    rent(
+        &mut app,
+        USER_1,
+        1,
+        &[coin(PRICE_PER_UNIT, NATIVE_DENOM)],
+        &cw2981_address,
+        &marketplace_address,
+    )
+    .unwrap();
+
+    // the lender cannot revoke the user set by the marketplace
+    let revoke_msg = Cw2981ExecuteMsg::Extension {
+        msg: Cw4907ExecuteMsg::SetUser {
+            token_id: TOKEN_ID.to_string(),
+            user: None,
+            expires: Cw721Expiration::Never {},
+        },
+    };
+    let res = app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&cw2981_address),
+        &revoke_msg,
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().source().unwrap().to_string(),
+        "Generic error: Cannot change the user of a token before it expires"
+    );
+
+    // nor transfer the nft to remove its user
+    let transfer_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::TransferNft {
+        recipient: USER_2.to_string(),
+        token_id: TOKEN_ID.to_string(),
+    };
+    let res = app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&cw2981_address),
+        &transfer_msg,
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().source().unwrap().to_string(),
+        "Generic error: Cannot transfer a token before its user expires"
+    );
+    assert_eq!(
+        query_user_of(&app, &cw2981_address).user,
+        Some(USER_1.to_string())
+    );
+    assert!(query_active_rental(&app, &cw2981_address, &marketplace_address).is_some());
+
+    // the lender can transfer the nft after the rental expires
+    app.update_block(|block| block.time = block.time.plus_seconds(UNIT_SECONDS));
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&cw2981_address),
+        &transfer_msg,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_user_of(&app, &cw2981_address).user, None);
+}
@@ -0,0 +1,265 @@
+use crate::msg::{CollectionStatsResponse, CollectionVolumesResponse, ExecuteMsg, QueryMsg};
+use crate::order_state::NFT;
+use crate::state::AuctionConfig;
+
+use crate::test_setup::env::{instantiate_contracts, NATIVE_DENOM, OWNER, USER_1, USER_2};
+
+use cosmwasm_std::{coin, Addr, Coin, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw2981_royalties::{Metadata, MintMsg};
+use cw721::Expiration as Cw721Expiration;
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+
+const MOCK_OFFER_CW20_PRICE: u128 = 10000000;
+
+// mint a NFT to owner and approve marketplace to transfer it
+fn mint_and_approve_nft(
+    app: &mut App,
+    token_id: &str,
+    owner: &str,
+    cw2981_address: &str,
+    marketplace_address: &str,
+) {
+    let mint_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Mint(MintMsg {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: Metadata::default(),
+    });
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(cw2981_address),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+
+    let approve_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Approve {
+        spender: marketplace_address.to_string(),
+        token_id: token_id.to_string(),
+        expires: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(owner),
+        Addr::unchecked(cw2981_address),
+        &approve_msg,
+        &[],
+    )
+    .unwrap();
+}
+
+fn list_nft(
+    app: &mut App,
+    token_id: &str,
+    seller: &str,
+    price: u128,
+    cw2981_address: &str,
+    marketplace_address: &str,
+) {
+    let list_nft_msg = ExecuteMsg::ListNft {
+        contract_address: cw2981_address.to_string(),
+        token_id: token_id.to_string(),
+        auction_config: AuctionConfig::FixedPrice {
+            price: coin(price, NATIVE_DENOM),
+            start_time: None,
+            end_time: None,
+        },
+    };
+    app.execute_contract(
+        Addr::unchecked(seller),
+        Addr::unchecked(marketplace_address),
+        &list_nft_msg,
+        &[],
+    )
+    .unwrap();
+}
+
+fn query_stats(
+    app: &App,
+    cw2981_address: &str,
+    marketplace_address: &str,
+) -> CollectionStatsResponse {
+    app.wrap()
+        .query_wasm_smart(
+            Addr::unchecked(marketplace_address),
+            &QueryMsg::CollectionStats {
+                contract_address: cw2981_address.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn collection_stats_follow_trades() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+    let cw20_address = contracts[2].contract_addr.clone();
+
+    mint_and_approve_nft(&mut app, "1", OWNER, &cw2981_address, &marketplace_address);
+    mint_and_approve_nft(&mut app, "2", OWNER, &cw2981_address, &marketplace_address);
+    mint_and_approve_nft(&mut app, "3", USER_2, &cw2981_address, &marketplace_address);
+
+    list_nft(
+        &mut app,
+        "1",
+        OWNER,
+        200,
+        &cw2981_address,
+        &marketplace_address,
+    );
+    list_nft(
+        &mut app,
+        "2",
+        OWNER,
+        100,
+        &cw2981_address,
+        &marketplace_address,
+    );
+    list_nft(
+        &mut app,
+        "3",
+        USER_2,
+        300,
+        &cw2981_address,
+        &marketplace_address,
+    );
+
+    let stats = query_stats(&app, &cw2981_address, &marketplace_address);
+    assert_eq!(stats.listed_count, 3);
+    assert_eq!(stats.unique_sellers, 2);
+    assert_eq!(stats.floor_prices, vec![coin(100, NATIVE_DENOM)]);
+    assert!(stats.volumes.is_empty());
+
+    // updating a listing does not change the counts
+    list_nft(
+        &mut app,
+        "1",
+        OWNER,
+        250,
+        &cw2981_address,
+        &marketplace_address,
+    );
+    let stats = query_stats(&app, &cw2981_address, &marketplace_address);
+    assert_eq!(stats.listed_count, 3);
+    assert_eq!(stats.unique_sellers, 2);
+
+    // USER_1 buys the floor
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::Buy {
+            contract_address: cw2981_address.clone(),
+            token_id: "2".to_string(),
+            max_price: None,
+            referrer: None,
+            pay_with_vaura: None,
+        },
+        &[coin(100, NATIVE_DENOM)],
+    )
+    .unwrap();
+
+    let stats = query_stats(&app, &cw2981_address, &marketplace_address);
+    assert_eq!(stats.listed_count, 2);
+    assert_eq!(stats.unique_sellers, 2);
+    assert_eq!(stats.floor_prices, vec![coin(250, NATIVE_DENOM)]);
+    assert_eq!(stats.volumes.len(), 1);
+    assert_eq!(stats.volumes[0].volume_24h, Uint128::from(100u128));
+
+    // OWNER cancels the last listing
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::Cancel {
+            contract_address: cw2981_address.clone(),
+            token_id: "1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let stats = query_stats(&app, &cw2981_address, &marketplace_address);
+    assert_eq!(stats.listed_count, 1);
+    assert_eq!(stats.unique_sellers, 1);
+    assert_eq!(stats.floor_prices, vec![coin(300, NATIVE_DENOM)]);
+
+    // a day later, USER_2 accepts the offer of USER_1 on the listed NFT
+    app.update_block(|block| block.time = block.time.plus_seconds(25 * 3600));
+
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&cw20_address),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: USER_1.to_string(),
+            amount: Uint128::from(MOCK_OFFER_CW20_PRICE),
+        },
+        &[Coin {
+            amount: Uint128::from(MOCK_OFFER_CW20_PRICE),
+            denom: NATIVE_DENOM.to_string(),
+        }],
+    )
+    .unwrap();
+
+    let nft = NFT {
+        contract_address: Addr::unchecked(&cw2981_address),
+        token_id: Some("3".to_string()),
+    };
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::OfferNft {
+            nft: nft.clone(),
+            funds_amount: MOCK_OFFER_CW20_PRICE,
+            start_time: None,
+            end_time: Cw721Expiration::AtTime(app.block_info().time.plus_seconds(1000)),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(USER_2),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::AcceptNftOffer {
+            offerer: USER_1.to_string(),
+            nft,
+            funds_amount: MOCK_OFFER_CW20_PRICE,
+            referrer: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let stats = query_stats(&app, &cw2981_address, &marketplace_address);
+    assert_eq!(stats.listed_count, 0);
+    assert_eq!(stats.unique_sellers, 0);
+    assert!(stats.floor_prices.is_empty());
+    assert_eq!(stats.volumes.len(), 2);
+    for volume in stats.volumes {
+        if volume.denom == NATIVE_DENOM {
+            assert_eq!(volume.volume_24h, Uint128::zero());
+            assert_eq!(volume.volume_7d, Uint128::from(100u128));
+            assert_eq!(volume.volume_all_time, Uint128::from(100u128));
+        } else {
+            assert_eq!(volume.denom, cw20_address);
+            assert_eq!(volume.volume_24h, Uint128::from(MOCK_OFFER_CW20_PRICE));
+            assert_eq!(volume.volume_all_time, Uint128::from(MOCK_OFFER_CW20_PRICE));
+        }
+    }
+
+    // collections are ranked by volume
+    let res: CollectionVolumesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            Addr::unchecked(&marketplace_address),
+            &QueryMsg::CollectionsByVolume {
+                denom: NATIVE_DENOM.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.volumes.len(), 1);
+    assert_eq!(res.volumes[0].contract_address, cw2981_address);
+    assert_eq!(res.volumes[0].amount, Uint128::from(100u128));
+}
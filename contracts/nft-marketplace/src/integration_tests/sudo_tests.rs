@@ -0,0 +1,245 @@
+use crate::msg::{ExecuteMsg, QueryMsg, SudoMsg};
+use crate::order_state::{OrderComponents, NFT};
+use crate::state::{AuctionConfig, Config, Listing};
+use crate::test_setup::env::{instantiate_contracts, NATIVE_DENOM, OWNER, USER_1, USER_2};
+use crate::ContractError;
+
+use cosmwasm_std::{coin, Addr, StdResult, Uint128};
+use cw2981_royalties::{Metadata, MintMsg};
+use cw721::Expiration as Cw721Expiration;
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{App, AppResponse, Executor};
+
+// mint a NFT to OWNER and approve marketplace to transfer it
+fn mint_and_approve_nft(
+    app: &mut App,
+    token_id: &str,
+    cw2981_address: &str,
+    marketplace_address: &str,
+) {
+    let mint_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Mint(MintMsg {
+        token_id: token_id.to_string(),
+        owner: OWNER.to_string(),
+        token_uri: None,
+        extension: Metadata::default(),
+    });
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(cw2981_address),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+
+    let approve_msg: Cw721ExecuteMsg<Metadata, Metadata> = Cw721ExecuteMsg::Approve {
+        spender: marketplace_address.to_string(),
+        token_id: token_id.to_string(),
+        expires: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(cw2981_address),
+        &approve_msg,
+        &[],
+    )
+    .unwrap();
+}
+
+fn list_nft(
+    app: &mut App,
+    token_id: &str,
+    cw2981_address: &str,
+    marketplace_address: &str,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(marketplace_address),
+        &ExecuteMsg::ListNft {
+            contract_address: cw2981_address.to_string(),
+            token_id: token_id.to_string(),
+            auction_config: AuctionConfig::FixedPrice {
+                price: coin(100, NATIVE_DENOM),
+                start_time: None,
+                end_time: None,
+            },
+        },
+        &[],
+    )
+}
+
+#[test]
+fn governance_can_update_owner_and_fees() {
+    let (mut app, contracts) = instantiate_contracts();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    // the royalty and the fees cannot exceed 100% in total
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::EditMaxRoyalty {
+            max_royalty_bps: 1_000,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.wasm_sudo(
+        Addr::unchecked(&marketplace_address),
+        &SudoMsg::UpdateOwner {
+            owner: USER_2.to_string(),
+        },
+    )
+    .unwrap();
+    app.wasm_sudo(
+        Addr::unchecked(&marketplace_address),
+        &SudoMsg::SetFees {
+            protocol_fee_bps: 200,
+            referral_fee_bps: 50,
+        },
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&marketplace_address, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.owner, Addr::unchecked(USER_2));
+    assert_eq!(config.protocol_fee_bps, 200);
+    assert_eq!(config.referral_fee_bps, 50);
+
+    // the fees are still validated
+    let response = app.wasm_sudo(
+        Addr::unchecked(&marketplace_address),
+        &SudoMsg::SetFees {
+            protocol_fee_bps: 9_000,
+            referral_fee_bps: 1,
+        },
+    );
+    assert_eq!(
+        response.unwrap_err().to_string(),
+        ContractError::InvalidBasisPoints { bps: 10_001 }.to_string()
+    );
+
+    // the old owner lost its permissions
+    let response = app.execute_contract(
+        Addr::unchecked(OWNER),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::EditProtocolFee {
+            protocol_fee_bps: 0,
+        },
+        &[],
+    );
+    assert_eq!(
+        response.err().unwrap().source().unwrap().to_string(),
+        ContractError::Unauthorized {}.to_string()
+    );
+}
+
+#[test]
+fn governance_can_pause_trading() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+
+    mint_and_approve_nft(&mut app, "1", &cw2981_address, &marketplace_address);
+
+    app.wasm_sudo(Addr::unchecked(&marketplace_address), &SudoMsg::Pause {})
+        .unwrap();
+    let paused: bool = app
+        .wrap()
+        .query_wasm_smart(&marketplace_address, &QueryMsg::Paused {})
+        .unwrap();
+    assert!(paused);
+
+    let response = list_nft(&mut app, "1", &cw2981_address, &marketplace_address);
+    assert_eq!(
+        response.err().unwrap().source().unwrap().to_string(),
+        ContractError::TradingPaused {}.to_string()
+    );
+
+    app.wasm_sudo(Addr::unchecked(&marketplace_address), &SudoMsg::Unpause {})
+        .unwrap();
+    list_nft(&mut app, "1", &cw2981_address, &marketplace_address).unwrap();
+}
+
+#[test]
+fn governance_can_remove_listings_and_offers() {
+    let (mut app, contracts) = instantiate_contracts();
+    let cw2981_address = contracts[0].contract_addr.clone();
+    let marketplace_address = contracts[1].contract_addr.clone();
+    let cw20_address = contracts[2].contract_addr.clone();
+
+    mint_and_approve_nft(&mut app, "1", &cw2981_address, &marketplace_address);
+    list_nft(&mut app, "1", &cw2981_address, &marketplace_address).unwrap();
+
+    // USER_1 converts native token to vaura and offers for the nft
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: USER_1.to_string(),
+            amount: vec![coin(1000, NATIVE_DENOM)],
+        },
+    ))
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&cw20_address),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: USER_1.to_string(),
+            amount: Uint128::from(1000u128),
+        },
+        &[coin(1000, NATIVE_DENOM)],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(USER_1),
+        Addr::unchecked(&marketplace_address),
+        &ExecuteMsg::OfferNft {
+            nft: NFT {
+                contract_address: Addr::unchecked(&cw2981_address),
+                token_id: Some("1".to_string()),
+            },
+            funds_amount: 1000,
+            start_time: None,
+            end_time: Cw721Expiration::AtTime(app.block_info().time.plus_seconds(1000)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.wasm_sudo(
+        Addr::unchecked(&marketplace_address),
+        &SudoMsg::RemoveListing {
+            contract_address: cw2981_address.clone(),
+            token_id: "1".to_string(),
+        },
+    )
+    .unwrap();
+    app.wasm_sudo(
+        Addr::unchecked(&marketplace_address),
+        &SudoMsg::RemoveOffer {
+            offerer: USER_1.to_string(),
+            contract_address: cw2981_address.clone(),
+            token_id: "1".to_string(),
+        },
+    )
+    .unwrap();
+
+    let listing: StdResult<Listing> = app.wrap().query_wasm_smart(
+        &marketplace_address,
+        &QueryMsg::Listing {
+            contract_address: cw2981_address.clone(),
+            token_id: "1".to_string(),
+        },
+    );
+    assert!(listing.is_err());
+
+    let offer: StdResult<OrderComponents> = app.wrap().query_wasm_smart(
+        &marketplace_address,
+        &QueryMsg::Offer {
+            contract_address: cw2981_address,
+            token_id: "1".to_string(),
+            offerer: USER_1.to_string(),
+        },
+    );
+    assert!(offer.is_err());
+}
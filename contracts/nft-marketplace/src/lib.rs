@@ -1,12 +1,19 @@
 pub mod contract;
 pub mod error;
+pub mod events;
 pub mod execute;
+pub mod hooks;
+pub mod migrations;
 pub mod msg;
 pub mod query;
+pub mod rental;
 pub mod state;
+pub mod sudo;
 pub mod test_setup;
 
 pub mod order_state;
+pub mod rental_state;
+pub mod stats_state;
 
 pub use crate::error::ContractError;
 
@@ -0,0 +1,145 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, DepsMut, Order, StdError, StdResult, Storage};
+use cw_storage_plus::Item;
+use semver::Version;
+
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::state::{contract, Config, MAX_BASIS_POINTS};
+use crate::ContractError;
+
+// a migration step brings the storage to the version it is registered with
+// every step must do nothing when it is already applied
+type MigrationStep = fn(&mut dyn Storage) -> Result<(), ContractError>;
+
+// the ordered chain of migration steps, a version can register several steps
+pub(crate) const MIGRATIONS: &[(&str, MigrationStep)] = &[
+    ("0.1.1", migrate_to_v0_1_1),
+    ("0.2.0", migrate_to_v0_2_0),
+    ("0.2.0", backfill_offer_indexes),
+];
+
+// the config as stored by any version, the fields added later are optional
+#[cw_serde]
+struct StoredConfig {
+    owner: Addr,
+    vaura_address: Option<Addr>,
+    max_royalty_bps: Option<u64>,
+    protocol_fee_bps: Option<u64>,
+    referral_fee_bps: Option<u64>,
+}
+
+const STORED_CONFIG: Item<StoredConfig> = Item::new("config");
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    version
+        .parse()
+        .map_err(|e: semver::Error| StdError::parse_err("Version", e.to_string()))
+}
+
+// run the migration steps newer than the stored version, returns the stored version
+pub fn migrate_storage(deps: DepsMut) -> Result<String, ContractError> {
+    let stored = cw2::get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigrationContract {
+            contract_name: stored.contract,
+        });
+    }
+
+    let stored_version = parse_version(&stored.version)?;
+    let new_version = parse_version(CONTRACT_VERSION)?;
+    if stored_version > new_version {
+        return Err(ContractError::CannotDowngrade {
+            stored_version: stored.version,
+            new_version: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    for (version, step) in MIGRATIONS {
+        if parse_version(version)? > stored_version {
+            step(deps.storage)?;
+        }
+    }
+
+    Ok(stored.version)
+}
+
+// 0.1.0 stored only the owner in the config
+fn migrate_to_v0_1_1(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let mut config = STORED_CONFIG.load(storage)?;
+
+    // the default value of vaura_address is equal to "aura0" and MUST BE SET before offer nft
+    if config.vaura_address.is_none() {
+        config.vaura_address = Some(Addr::unchecked("aura0"));
+        STORED_CONFIG.save(storage, &config)?;
+    }
+    Ok(())
+}
+
+// 0.2.0 added royalty and fee settings, listing price indexes and collection aggregates
+fn migrate_to_v0_2_0(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let stored = STORED_CONFIG.load(storage)?;
+    let config = Config {
+        owner: stored.owner,
+        vaura_address: stored
+            .vaura_address
+            .unwrap_or_else(|| Addr::unchecked("aura0")),
+        max_royalty_bps: stored.max_royalty_bps.unwrap_or(MAX_BASIS_POINTS),
+        protocol_fee_bps: stored.protocol_fee_bps.unwrap_or_default(),
+        referral_fee_bps: stored.referral_fee_bps.unwrap_or_default(),
+    };
+    let contract = contract();
+    contract.config.save(storage, &config)?;
+
+    // saving the listings and orders again writes their missing index entries
+    let listings = contract
+        .listings
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, listing) in &listings {
+        contract.listings.save(storage, key.clone(), listing)?;
+    }
+
+    // the aggregates are rebuilt from the active listings
+    let collections = contract
+        .stats
+        .collection_stats
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in collections {
+        contract.stats.collection_stats.remove(storage, key);
+    }
+    let listed_counts = contract
+        .stats
+        .listed_counts
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in listed_counts {
+        contract.stats.listed_counts.remove(storage, key);
+    }
+    let seller_listing_counts = contract
+        .stats
+        .seller_listing_counts
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in seller_listing_counts {
+        contract.stats.seller_listing_counts.remove(storage, key);
+    }
+    for (_, listing) in &listings {
+        contract.add_listing_stats(storage, listing)?;
+    }
+
+    Ok(())
+}
+
+// 0.2.0 also indexed the offers by price, saving the orders again writes their missing
+// nft_prices entries used by the best offer queries
+fn backfill_offer_indexes(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let offers = contract().offers;
+    let orders = offers
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, order) in orders {
+        offers.save(storage, key, &order)?;
+    }
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{to_binary, Addr, Coin, StdResult, Uint128, WasmMsg};
 use cw721::Expiration;
 
 use crate::{
     order_state::{OrderComponents, NFT},
-    state::{AuctionConfig, Listing},
+    rental_state::{Rental, RentalListing},
+    state::{AuctionConfig, Listing, RoyaltyPolicy},
+    stats_state::Volume,
 };
 
 #[cw_serde]
@@ -21,19 +23,26 @@ pub enum ExecuteMsg {
         auction_config: AuctionConfig,
     },
     // Buy a listed NFT
+    // funds above the price are refunded, max_price protects against a listing price change
+    // the referrer receives the referral fee of the sale
+    // with pay_with_vaura, the price is paid with the vaura balance of the buyer
     Buy {
         contract_address: String,
         token_id: String,
+        max_price: Option<Uint128>,
+        referrer: Option<String>,
+        pay_with_vaura: Option<bool>,
     },
     // Cancel a listed NFT
     Cancel {
         contract_address: String,
         token_id: String,
     },
-    // Offer a Nft
+    // Offer a Nft, the offer can only be accepted after start_time
     OfferNft {
         nft: NFT,
         funds_amount: u128,
+        start_time: Option<Expiration>,
         end_time: Expiration,
     },
     // Accept a Nft offer
@@ -41,20 +50,105 @@ pub enum ExecuteMsg {
         offerer: String,
         nft: NFT,
         funds_amount: u128,
+        referrer: Option<String>,
     },
     // Cancel offer of User
     CancelOffer {
         nfts: Vec<NFT>,
     },
+    // Cancel all offers of User by incrementing their counter
+    IncrementCounter {},
     // edit contract address of vaura token
     EditVauraToken {
         token_address: String,
     },
+    // edit the share of the price paid to the referrer of a sale, in basis points
+    // the max royalty, the protocol fee and the referral fee cannot exceed 100% in total
+    EditReferralFee {
+        referral_fee_bps: u64,
+    },
+    // edit the share of the price paid to the marketplace owner on a sale, in basis points
+    EditProtocolFee {
+        protocol_fee_bps: u64,
+    },
+    // edit the maximum royalty paid on a sale, in basis points
+    EditMaxRoyalty {
+        max_royalty_bps: u64,
+    },
+    // set how royalties of a collection are handled
+    SetRoyaltyPolicy {
+        contract_address: String,
+        policy: RoyaltyPolicy,
+    },
+    // set the minimum amount of an offer for a collection
+    SetMinOfferAmount {
+        contract_address: String,
+        min_funds_amount: u128,
+    },
+    // register a contract notified after each sale
+    AddSaleHook {
+        hook: String,
+    },
+    RemoveSaleHook {
+        hook: String,
+    },
+    // register a contract notified after each listing and cancellation
+    AddListingHook {
+        hook: String,
+    },
+    RemoveListingHook {
+        hook: String,
+    },
+    // List a NFT for rent, the renter pays price_per_unit for each unit_seconds of use
+    // and becomes the cw4907 user of the NFT, the ownership stays with the lender
+    ListRental {
+        contract_address: String,
+        token_id: String,
+        price_per_unit: Coin,
+        unit_seconds: u64,
+        max_units: u64,
+    },
+    // Cancel a rental listing, the current rental is not affected
+    CancelRental {
+        contract_address: String,
+        token_id: String,
+    },
+    // Rent a listed NFT for a number of time units
+    // funds above the price are refunded
+    Rent {
+        contract_address: String,
+        token_id: String,
+        units: u64,
+    },
 }
 
 #[cw_serde]
 pub struct MigrateMsg {}
 
+// messages executed by governance, e.g. if the owner key is lost
+#[cw_serde]
+pub enum SudoMsg {
+    UpdateOwner {
+        owner: String,
+    },
+    // stop listing, buying, offering and accepting offers
+    Pause {},
+    Unpause {},
+    SetFees {
+        protocol_fee_bps: u64,
+        referral_fee_bps: u64,
+    },
+    RemoveListing {
+        contract_address: String,
+        token_id: String,
+    },
+    RemoveOffer {
+        offerer: String,
+        contract_address: String,
+        token_id: String,
+    },
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -89,6 +183,22 @@ pub enum QueryMsg {
         start_after_offerer: Option<String>,
         limit: Option<u32>,
     },
+    // get offers of a nft sorted by the offered amount, highest first
+    // expired offers are left out unless include_expired is true
+    #[returns(OffersResponse)]
+    NftOffersByPrice {
+        contract_address: String,
+        token_id: String,
+        start_after: Option<(u128, String)>, // (amount, offerer) of the last offer of the previous page
+        limit: Option<u32>,
+        include_expired: Option<bool>,
+    },
+    // get the highest offer of a nft which can be accepted now
+    #[returns(Option<OrderComponents>)]
+    BestOffer {
+        contract_address: String,
+        token_id: String,
+    },
     // get all offers of a user
     #[returns(OffersResponse)]
     UserOffers {
@@ -96,6 +206,60 @@ pub enum QueryMsg {
         start_after_nft: Option<NFT>,
         limit: Option<u32>,
     },
+    // get the current counter of a user
+    #[returns(u64)]
+    Counter { user: String },
+    // get the floor price, listings, sellers and volume of a collection
+    #[returns(CollectionStatsResponse)]
+    CollectionStats { contract_address: String },
+    // get collections sorted by their all-time volume in a denom, highest first
+    #[returns(CollectionVolumesResponse)]
+    CollectionsByVolume {
+        denom: String,
+        start_after: Option<(u128, String)>, // (volume, contract_address) of the last collection of the previous page
+        limit: Option<u32>,
+    },
+    // get the royalty policy of a collection
+    #[returns(RoyaltyPolicy)]
+    RoyaltyPolicy { contract_address: String },
+    // get the minimum amount of an offer for a collection
+    #[returns(u128)]
+    MinOfferAmount { contract_address: String },
+    // check if trading is paused
+    #[returns(bool)]
+    Paused {},
+    // get the contracts notified after each sale
+    #[returns(HooksResponse)]
+    SaleHooks {},
+    // get the contracts notified after each listing and cancellation
+    #[returns(HooksResponse)]
+    ListingHooks {},
+    // get the rental listing of a nft
+    #[returns(RentalListing)]
+    RentalListing {
+        contract_address: String,
+        token_id: String,
+    },
+    // get the rental listings of a collection
+    #[returns(RentalListingsResponse)]
+    RentalListingsByContractAddress {
+        contract_address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // get the active rental of a nft
+    #[returns(Option<Rental>)]
+    ActiveRental {
+        contract_address: String,
+        token_id: String,
+    },
+    // get the active rentals of a renter
+    #[returns(RentalsResponse)]
+    ActiveRentalsByRenter {
+        renter: String,
+        start_after: Option<(String, String)>, // (contract_address, token_id) of the last rental of the previous page
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -103,6 +267,16 @@ pub struct ListingsResponse {
     pub listings: Vec<Listing>,
 }
 
+#[cw_serde]
+pub struct RentalListingsResponse {
+    pub rental_listings: Vec<RentalListing>,
+}
+
+#[cw_serde]
+pub struct RentalsResponse {
+    pub rentals: Vec<Rental>,
+}
+
 #[cw_serde]
 pub struct ValidateResponse {
     pub valid: bool,
@@ -112,3 +286,87 @@ pub struct ValidateResponse {
 pub struct OffersResponse {
     pub offers: Vec<OrderComponents>,
 }
+
+#[cw_serde]
+pub struct CollectionVolume {
+    pub denom: String,
+    pub volume_24h: Uint128,
+    pub volume_7d: Uint128,
+    pub volume_all_time: Uint128,
+}
+
+#[cw_serde]
+pub struct CollectionStatsResponse {
+    pub contract_address: Addr,
+    pub floor_prices: Vec<Coin>, // the lowest price of active listings in each denom
+    pub listed_count: u64, // the listings not cancelled or sold yet, including the expired ones
+    pub unique_sellers: u64, // the sellers of these listings
+    pub volumes: Vec<CollectionVolume>,
+}
+
+#[cw_serde]
+pub struct CollectionVolumesResponse {
+    pub volumes: Vec<Volume>,
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<String>,
+}
+
+// message sent to the sale hooks after a listing is bought or an offer is accepted
+// the denom of the price is the native denom or the address of the cw20 token
+#[cw_serde]
+pub struct SaleHookMsg {
+    pub contract_address: String,
+    pub token_id: String,
+    pub price: Coin,
+    pub seller: String,
+    pub buyer: String,
+}
+
+impl SaleHookMsg {
+    pub fn into_wasm_msg(self, contract_addr: String) -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&SaleHookExecuteMsg::SaleHook(self))?,
+            funds: vec![],
+        })
+    }
+}
+
+// the hook contracts must handle this message
+#[cw_serde]
+pub enum SaleHookExecuteMsg {
+    SaleHook(SaleHookMsg),
+}
+
+#[cw_serde]
+pub enum ListingHookAction {
+    Create,
+    Update,
+    Delete,
+}
+
+// message sent to the listing hooks after a listing is created, updated or cancelled
+#[cw_serde]
+pub struct ListingHookMsg {
+    pub action: ListingHookAction,
+    pub listing: Listing,
+}
+
+impl ListingHookMsg {
+    pub fn into_wasm_msg(self, contract_addr: String) -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&ListingHookExecuteMsg::ListingHook(self))?,
+            funds: vec![],
+        })
+    }
+}
+
+// the hook contracts must handle this message
+#[cw_serde]
+pub enum ListingHookExecuteMsg {
+    ListingHook(ListingHookMsg),
+}
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, BlockInfo};
 use cw721::Expiration;
 use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
 
@@ -146,17 +146,52 @@ pub struct OrderComponents {
     pub consideration: Vec<ConsiderationItem>,
     pub start_time: Option<Expiration>,
     pub end_time: Option<Expiration>,
+    // the counter of the offerer when the order was created,
+    // the order is cancelled when the offerer increments the counter
+    #[serde(default)]
+    pub counter: u64,
+}
+
+impl OrderComponents {
+    // the amount of funds offered by the order
+    pub fn offer_amount(&self) -> u128 {
+        match self.offer.first().map(|offer_item| &offer_item.item) {
+            Some(Asset::Cw20(CW20 { amount, .. })) | Some(Asset::Native(NATIVE { amount, .. })) => {
+                *amount
+            }
+            _ => 0,
+        }
+    }
+
+    // expired is when an order has passed the end_time
+    pub fn is_expired(&self, block_info: &BlockInfo) -> bool {
+        match self.end_time {
+            Some(end_time) => end_time.is_expired(block_info),
+            None => false,
+        }
+    }
+
+    // active is when an order has passed the start_time and is not expired
+    pub fn is_active(&self, block_info: &BlockInfo) -> bool {
+        let started = match self.start_time {
+            Some(start_time) => start_time.is_expired(block_info),
+            None => true,
+        };
+        started && !self.is_expired(block_info)
+    }
 }
 
 pub struct OfferIndexes<'a> {
     pub users: MultiIndex<'a, User, OrderComponents, OrderKey>,
     pub nfts: MultiIndex<'a, (Addr, String), OrderComponents, OrderKey>,
+    // offers of a nft sorted by the offered amount
+    pub nft_prices: MultiIndex<'a, (Addr, String, u128), OrderComponents, OrderKey>,
 }
 
 impl<'a> IndexList<OrderComponents> for OfferIndexes<'a> {
     // this method returns a list of all indexes
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<OrderComponents>> + '_> {
-        let v: Vec<&dyn Index<OrderComponents>> = vec![&self.users, &self.nfts];
+        let v: Vec<&dyn Index<OrderComponents>> = vec![&self.users, &self.nfts, &self.nft_prices];
         Box::new(v.into_iter())
     }
 }
@@ -165,7 +200,7 @@ impl<'a> IndexList<OrderComponents> for OfferIndexes<'a> {
 pub fn orders<'a>() -> IndexedMap<'a, OrderKey, OrderComponents, OfferIndexes<'a>> {
     let indexes = OfferIndexes {
         users: MultiIndex::new(
-            |_pk: &[u8], l: &OrderComponents| (l.order_id.0.clone()),
+            |_pk: &[u8], l: &OrderComponents| l.order_id.0.clone(),
             "orders",
             "orders__user_address",
         ),
@@ -174,6 +209,13 @@ pub fn orders<'a>() -> IndexedMap<'a, OrderKey, OrderComponents, OfferIndexes<'a
             "orders",
             "orders__nft_identifier",
         ),
+        nft_prices: MultiIndex::new(
+            |_pk: &[u8], l: &OrderComponents| {
+                (l.order_id.1.clone(), l.order_id.2.clone(), l.offer_amount())
+            },
+            "orders",
+            "orders__nft_price",
+        ),
     };
     IndexedMap::new("orders", indexes)
 }
@@ -1,12 +1,22 @@
-use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cosmwasm_std::{Addr, Deps, Env, Order, StdError, StdResult, Uint128};
+use cw2981_royalties::msg::{Cw2981QueryMsg as Cw2981QueryExtension, UserOfResponse};
+use cw2981_royalties::QueryMsg as Cw2981QueryMsg;
 use cw_storage_plus::Bound;
 
 use crate::{
-    msg::{ListingsResponse, OffersResponse},
+    msg::{
+        CollectionStatsResponse, CollectionVolume, CollectionVolumesResponse, ListingsResponse,
+        OffersResponse, RentalListingsResponse, RentalsResponse,
+    },
     order_state::{order_key, OrderComponents, OrderKey, NFT},
-    state::{listing_key, Listing, ListingKey, MarketplaceContract},
+    rental_state::{Rental, RentalListing},
+    state::{listing_key, Listing, ListingKey, MarketplaceContract, RoyaltyPolicy},
+    stats_state::{VolumeKey, VOLUME_BUCKET_SECONDS},
 };
 
+// the maximum number of offers read to find the best offer of a nft
+const BEST_OFFER_SCAN_LIMIT: usize = 100;
+
 impl MarketplaceContract<'static> {
     pub fn query_listing(
         self,
@@ -49,7 +59,13 @@ impl MarketplaceContract<'static> {
         offerer: Addr,
     ) -> StdResult<OrderComponents> {
         let order_key = order_key(&offerer, &contract_address, &token_id);
-        self.offers.load(deps.storage, order_key)
+        let order = self.offers.load(deps.storage, order_key)?;
+
+        // an order cancelled by incrementing the counter is treated as not existing
+        if !self.is_order_valid(deps.storage, &order)? {
+            return Err(StdError::not_found("OrderComponents"));
+        }
+        Ok(order)
     }
 
     // query all offers of a specific nft
@@ -63,14 +79,16 @@ impl MarketplaceContract<'static> {
     ) -> StdResult<OffersResponse> {
         let limit = limit.unwrap_or(30).min(30) as usize;
 
-        let start: Option<Bound<OrderKey>> = start_after_offerer.map(|offerer| {
-            let order_key = order_key(
-                &deps.api.addr_validate(&offerer).unwrap(),
-                &contract_address,
-                &token_id,
-            );
-            Bound::exclusive(order_key)
-        });
+        let start: Option<Bound<OrderKey>> = start_after_offerer
+            .map(|offerer| -> StdResult<Bound<OrderKey>> {
+                let order_key = order_key(
+                    &deps.api.addr_validate(&offerer)?,
+                    &contract_address,
+                    &token_id,
+                );
+                Ok(Bound::exclusive(order_key))
+            })
+            .transpose()?;
 
         // load offers
         let offers = self
@@ -80,6 +98,10 @@ impl MarketplaceContract<'static> {
             .prefix((contract_address, token_id))
             .range(deps.storage, start, None, Order::Descending)
             .map(|item| item.map(|(_, order)| order))
+            .filter(|item| match item {
+                Ok(order) => self.is_order_valid(deps.storage, order).unwrap_or(false),
+                Err(_) => true,
+            })
             .take(limit)
             .collect::<StdResult<Vec<_>>>()?;
 
@@ -87,6 +109,78 @@ impl MarketplaceContract<'static> {
         Ok(OffersResponse { offers })
     }
 
+    // query offers of a specific nft sorted by the offered amount in descending order
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_nft_offers_by_price(
+        self,
+        deps: Deps,
+        env: Env,
+        contract_address: Addr,
+        token_id: String,
+        start_after: Option<(u128, String)>,
+        limit: Option<u32>,
+        include_expired: bool,
+    ) -> StdResult<OffersResponse> {
+        let limit = limit.unwrap_or(30).min(30) as usize;
+
+        // the offers are iterated from the highest amount, so the previous page is the upper bound
+        let end: Option<Bound<(u128, OrderKey)>> = match start_after {
+            Some((amount, offerer)) => {
+                let offerer = deps.api.addr_validate(&offerer)?;
+                Some(Bound::exclusive((
+                    amount,
+                    order_key(&offerer, &contract_address, &token_id),
+                )))
+            }
+            None => None,
+        };
+
+        let offers = self
+            .offers
+            .idx
+            .nft_prices
+            .sub_prefix((contract_address, token_id))
+            .range(deps.storage, None, end, Order::Descending)
+            .map(|item| item.map(|(_, order)| order))
+            .filter(|item| match item {
+                Ok(order) => {
+                    self.is_order_valid(deps.storage, order).unwrap_or(false)
+                        && (include_expired || !order.is_expired(&env.block))
+                }
+                Err(_) => true,
+            })
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(OffersResponse { offers })
+    }
+
+    // query the highest offer of a specific nft which can be accepted now
+    // only the BEST_OFFER_SCAN_LIMIT highest offers are read, None if all of them are inactive
+    pub fn query_best_offer(
+        self,
+        deps: Deps,
+        env: Env,
+        contract_address: Addr,
+        token_id: String,
+    ) -> StdResult<Option<OrderComponents>> {
+        self.offers
+            .idx
+            .nft_prices
+            .sub_prefix((contract_address, token_id))
+            .range(deps.storage, None, None, Order::Descending)
+            .take(BEST_OFFER_SCAN_LIMIT)
+            .map(|item| item.map(|(_, order)| order))
+            .find(|item| match item {
+                Ok(order) => {
+                    self.is_order_valid(deps.storage, order).unwrap_or(false)
+                        && order.is_active(&env.block)
+                }
+                Err(_) => true,
+            })
+            .transpose()
+    }
+
     // query all offers of a specific user
     pub fn query_user_offers(
         self,
@@ -97,10 +191,18 @@ impl MarketplaceContract<'static> {
     ) -> StdResult<OffersResponse> {
         let limit = limit.unwrap_or(30).min(30) as usize;
 
-        let start: Option<Bound<OrderKey>> = start_after_nft.map(|nft| {
-            let order_key = order_key(&offerer, &nft.contract_address, &nft.token_id.unwrap());
-            Bound::exclusive(order_key)
-        });
+        let start: Option<Bound<OrderKey>> = start_after_nft
+            .map(|nft| {
+                let token_id = nft
+                    .token_id
+                    .ok_or_else(|| StdError::generic_err("start_after_nft must have a token_id"))?;
+                Ok::<_, StdError>(Bound::exclusive(order_key(
+                    &offerer,
+                    &nft.contract_address,
+                    &token_id,
+                )))
+            })
+            .transpose()?;
 
         // load offers
         let offers = self
@@ -110,10 +212,244 @@ impl MarketplaceContract<'static> {
             .prefix(offerer)
             .range(deps.storage, start, None, Order::Descending)
             .map(|item| item.map(|(_, order)| order))
+            .filter(|item| match item {
+                Ok(order) => self.is_order_valid(deps.storage, order).unwrap_or(false),
+                Err(_) => true,
+            })
             .take(limit)
             .collect::<StdResult<Vec<_>>>()?;
 
         // return offers
         Ok(OffersResponse { offers })
     }
+
+    pub fn query_rental_listing(
+        self,
+        deps: Deps,
+        contract_address: Addr,
+        token_id: String,
+    ) -> StdResult<RentalListing> {
+        let listing_key = listing_key(&contract_address, &token_id);
+        self.rental_listings.load(deps.storage, listing_key)
+    }
+
+    pub fn query_rental_listings_by_contract_address(
+        self,
+        deps: Deps,
+        contract_address: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<RentalListingsResponse> {
+        let limit = limit.unwrap_or(30).min(30) as usize;
+        let start: Option<Bound<ListingKey>> =
+            start_after.map(|token_id| Bound::exclusive(listing_key(&contract_address, &token_id)));
+        let rental_listings = self
+            .rental_listings
+            .idx
+            .contract_address
+            .prefix(contract_address)
+            .range(deps.storage, start, None, Order::Ascending)
+            .map(|item| item.map(|(_, rental_listing)| rental_listing))
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(RentalListingsResponse { rental_listings })
+    }
+
+    // query the rental of a nft, None if it is not rented or the rental is expired
+    pub fn query_active_rental(
+        self,
+        deps: Deps,
+        env: Env,
+        contract_address: Addr,
+        token_id: String,
+    ) -> StdResult<Option<Rental>> {
+        let listing_key = listing_key(&contract_address, &token_id);
+        let rental = match self.rentals.may_load(deps.storage, listing_key)? {
+            Some(rental) if rental.is_active(&env.block) => rental,
+            _ => return Ok(None),
+        };
+
+        // the user of the nft is removed when it is transferred, so the nft contract is the source
+        let user_of: StdResult<UserOfResponse> = deps.querier.query_wasm_smart(
+            &contract_address,
+            &Cw2981QueryMsg::Extension {
+                msg: Cw2981QueryExtension::UserOf { token_id },
+            },
+        );
+        match user_of {
+            Ok(response) if response.user.as_deref() == Some(rental.renter.as_str()) => {
+                Ok(Some(rental))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // query the rentals of a renter which are not expired
+    pub fn query_active_rentals_by_renter(
+        self,
+        deps: Deps,
+        env: Env,
+        renter: Addr,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<RentalsResponse> {
+        let limit = limit.unwrap_or(30).min(30) as usize;
+        let start: Option<Bound<ListingKey>> = start_after
+            .map(
+                |(contract_address, token_id)| -> StdResult<Bound<ListingKey>> {
+                    Ok(Bound::exclusive(listing_key(
+                        &deps.api.addr_validate(&contract_address)?,
+                        &token_id,
+                    )))
+                },
+            )
+            .transpose()?;
+
+        let rentals = self
+            .rentals
+            .idx
+            .renter
+            .prefix(renter)
+            .range(deps.storage, start, None, Order::Ascending)
+            .map(|item| item.map(|(_, rental)| rental))
+            .filter(|item| match item {
+                Ok(rental) => rental.is_active(&env.block),
+                Err(_) => true,
+            })
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(RentalsResponse { rentals })
+    }
+
+    // query the royalty policy of a collection, collections without a policy use the default one
+    pub fn query_royalty_policy(
+        self,
+        deps: Deps,
+        contract_address: Addr,
+    ) -> StdResult<RoyaltyPolicy> {
+        Ok(self
+            .royalty_policies
+            .may_load(deps.storage, contract_address)?
+            .unwrap_or_default())
+    }
+
+    // query the minimum amount of an offer for a collection, 0 if it is not set
+    pub fn query_min_offer_amount(self, deps: Deps, contract_address: Addr) -> StdResult<u128> {
+        Ok(self
+            .min_offer_amounts
+            .may_load(deps.storage, contract_address)?
+            .unwrap_or_default())
+    }
+
+    // query the running aggregates of a collection
+    pub fn query_collection_stats(
+        self,
+        deps: Deps,
+        env: Env,
+        contract_address: Addr,
+    ) -> StdResult<CollectionStatsResponse> {
+        let collection_stats = self
+            .stats
+            .collection_stats
+            .may_load(deps.storage, contract_address.clone())?
+            .unwrap_or_default();
+
+        // the floor price in each denom is the first active listing in the price index
+        let denoms = self
+            .stats
+            .listed_counts
+            .prefix(contract_address.clone())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut floor_prices = vec![];
+        for denom in denoms {
+            let floor_listing = self
+                .listings
+                .idx
+                .prices
+                .sub_prefix((contract_address.clone(), denom))
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, listing)| listing))
+                .find(|item| match item {
+                    Ok(listing) => !listing.is_expired(&env.block),
+                    Err(_) => true,
+                })
+                .transpose()?;
+            if let Some(listing) = floor_listing {
+                floor_prices.push(listing.price().clone());
+            }
+        }
+
+        // the volume of the last 24 hours and 7 days are summed from the buckets
+        let current_bucket = env.block.time.seconds() / VOLUME_BUCKET_SECONDS;
+        let sum_volume = |denom: &str, buckets: u64| -> StdResult<Uint128> {
+            self.stats
+                .bucket_volumes
+                .prefix((contract_address.clone(), denom.to_string()))
+                .range(
+                    deps.storage,
+                    Some(Bound::inclusive(current_bucket.saturating_sub(buckets - 1))),
+                    None,
+                    Order::Ascending,
+                )
+                .try_fold(Uint128::zero(), |total, item| Ok(total + item?.1))
+        };
+        let volumes = self
+            .stats
+            .volumes
+            .prefix(contract_address.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (denom, volume) = item?;
+                Ok(CollectionVolume {
+                    volume_24h: sum_volume(&denom, 24)?,
+                    volume_7d: sum_volume(&denom, 24 * 7)?,
+                    volume_all_time: volume.amount,
+                    denom,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(CollectionStatsResponse {
+            contract_address,
+            floor_prices,
+            listed_count: collection_stats.listed_count,
+            unique_sellers: collection_stats.unique_sellers,
+            volumes,
+        })
+    }
+
+    // query collections sorted by their all-time volume in a denom in descending order
+    pub fn query_collections_by_volume(
+        self,
+        deps: Deps,
+        denom: String,
+        start_after: Option<(u128, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<CollectionVolumesResponse> {
+        let limit = limit.unwrap_or(30).min(30) as usize;
+
+        // the volumes are iterated from the highest amount, so the previous page is the upper bound
+        let end: Option<Bound<(u128, VolumeKey)>> = match start_after {
+            Some((amount, contract_address)) => Some(Bound::exclusive((
+                amount,
+                (deps.api.addr_validate(&contract_address)?, denom.clone()),
+            ))),
+            None => None,
+        };
+
+        let volumes = self
+            .stats
+            .volumes
+            .idx
+            .denom_amount
+            .sub_prefix(denom)
+            .range(deps.storage, None, end, Order::Descending)
+            .map(|item| item.map(|(_, volume)| volume))
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(CollectionVolumesResponse { volumes })
+    }
 }
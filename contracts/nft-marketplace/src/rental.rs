@@ -0,0 +1,208 @@
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, StdError, Timestamp,
+    Uint128, WasmMsg,
+};
+use cw2981_royalties::{msg::Cw4907ExecuteMsg, ExecuteMsg as Cw2981ExecuteMsg};
+use cw721::Expiration;
+
+use crate::execute::{check_nft_owner, query_marketplace_approval, refund_surplus};
+use crate::rental_state::{Rental, RentalListing};
+use crate::state::{listing_key, MarketplaceContract};
+use crate::ContractError;
+
+// the end of a rental of units starting at time, None when it cannot be represented
+fn rental_end(time: Timestamp, unit_seconds: u64, units: u64) -> Option<Timestamp> {
+    units
+        .checked_mul(unit_seconds)?
+        .checked_mul(1_000_000_000)
+        .and_then(|nanos| time.nanos().checked_add(nanos))
+        .map(Timestamp::from_nanos)
+}
+
+// rentals of nfts implementing the cw4907 extension of cw2981-royalties
+impl MarketplaceContract<'static> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_list_rental(
+        self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract_address: Addr,
+        token_id: String,
+        price_per_unit: Coin,
+        unit_seconds: u64,
+        max_units: u64,
+    ) -> Result<Response, ContractError> {
+        // trading is stopped while the marketplace is paused
+        self.check_not_paused(deps.storage)?;
+
+        check_nft_owner(&deps, &contract_address, &token_id, &info.sender)?;
+
+        // the marketplace sets the user of the nft, so it must be allowed to transfer it
+        query_marketplace_approval(&deps, &env, &contract_address, &token_id, &info.sender)?;
+
+        // the longest rental must end at a representable time
+        if price_per_unit.amount.is_zero()
+            || unit_seconds == 0
+            || max_units == 0
+            || rental_end(env.block.time, unit_seconds, max_units).is_none()
+        {
+            return Err(ContractError::InvalidRentalConfig {});
+        }
+
+        let rental_listing = RentalListing {
+            contract_address: contract_address.clone(),
+            token_id: token_id.clone(),
+            lender: info.sender,
+            price_per_unit,
+            unit_seconds,
+            max_units,
+        };
+        // we will override the rental listing if it already exists
+        self.rental_listings.save(
+            deps.storage,
+            listing_key(&contract_address, &token_id),
+            &rental_listing,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("method", "list_rental")
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("token_id", token_id)
+            .add_attribute("lender", rental_listing.lender)
+            .add_attribute("price_per_unit", rental_listing.price_per_unit.to_string())
+            .add_attribute("unit_seconds", unit_seconds.to_string())
+            .add_attribute("max_units", max_units.to_string()))
+    }
+
+    pub fn execute_cancel_rental(
+        self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        contract_address: Addr,
+        token_id: String,
+    ) -> Result<Response, ContractError> {
+        let listing_key = listing_key(&contract_address, &token_id);
+        let rental_listing = self
+            .rental_listings
+            .load(deps.storage, listing_key.clone())?;
+
+        // only the lender can cancel the rental listing
+        if rental_listing.lender != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.rental_listings.remove(deps.storage, listing_key)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "cancel_rental")
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("token_id", token_id)
+            .add_attribute("lender", rental_listing.lender))
+    }
+
+    pub fn execute_rent(
+        self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract_address: Addr,
+        token_id: String,
+        units: u64,
+    ) -> Result<Response, ContractError> {
+        // trading is stopped while the marketplace is paused
+        self.check_not_paused(deps.storage)?;
+
+        let listing_key = listing_key(&contract_address, &token_id);
+        let rental_listing = self
+            .rental_listings
+            .load(deps.storage, listing_key.clone())?;
+
+        if info.sender == rental_listing.lender {
+            return Err(ContractError::CannotRentOwnNft {});
+        }
+        if units == 0 || units > rental_listing.max_units {
+            return Err(ContractError::InvalidRentalDuration {
+                units,
+                max_units: rental_listing.max_units,
+            });
+        }
+
+        // a nft has one user at a time
+        if let Some(rental) = self.rentals.may_load(deps.storage, listing_key.clone())? {
+            if rental.is_active(&env.block) {
+                return Err(ContractError::NftAlreadyRented {
+                    expires: rental.expires,
+                });
+            }
+        }
+
+        // the lender may have transferred the nft after listing it
+        check_nft_owner(&deps, &contract_address, &token_id, &rental_listing.lender)
+            .map_err(|_| ContractError::ListingNotActive {})?;
+
+        let price = Coin {
+            denom: rental_listing.price_per_unit.denom.clone(),
+            amount: rental_listing
+                .price_per_unit
+                .amount
+                .checked_mul(Uint128::from(units))
+                .map_err(StdError::from)?,
+        };
+
+        let refund_coins = refund_surplus(&info.funds, &price)?;
+
+        let expires = Expiration::AtTime(
+            rental_end(env.block.time, rental_listing.unit_seconds, units).ok_or(
+                ContractError::InvalidRentalDuration {
+                    units,
+                    max_units: rental_listing.max_units,
+                },
+            )?,
+        );
+        let rental = Rental {
+            contract_address: contract_address.clone(),
+            token_id: token_id.clone(),
+            lender: rental_listing.lender.clone(),
+            renter: info.sender.clone(),
+            price: price.clone(),
+            expires,
+        };
+        self.rentals.save(deps.storage, listing_key, &rental)?;
+
+        // the renter becomes the user of the nft until the rental expires
+        let set_user_msg = WasmMsg::Execute {
+            contract_addr: contract_address.to_string(),
+            msg: to_binary(&Cw2981ExecuteMsg::Extension {
+                msg: Cw4907ExecuteMsg::SetUser {
+                    token_id: token_id.clone(),
+                    user: Some(info.sender.to_string()),
+                    expires,
+                },
+            })?,
+            funds: vec![],
+        };
+
+        let mut res = Response::new()
+            .add_message(set_user_msg)
+            .add_message(BankMsg::Send {
+                to_address: rental_listing.lender.to_string(),
+                amount: vec![price.clone()],
+            });
+        if !refund_coins.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: refund_coins,
+            });
+        }
+
+        Ok(res
+            .add_attribute("method", "rent")
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("token_id", token_id)
+            .add_attribute("lender", rental_listing.lender)
+            .add_attribute("renter", info.sender)
+            .add_attribute("price", price.to_string())
+            .add_attribute("expires", expires.to_string()))
+    }
+}
@@ -0,0 +1,86 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Coin};
+use cw721::Expiration;
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+
+use crate::state::ListingKey;
+
+// a nft offered for rent, the lender keeps the ownership and the renter becomes its cw4907 user
+#[cw_serde]
+pub struct RentalListing {
+    pub contract_address: Addr,
+    pub token_id: String,
+    pub lender: Addr,
+    pub price_per_unit: Coin, // the price of one time unit
+    pub unit_seconds: u64,    // the length of a time unit
+    pub max_units: u64,       // the longest rental, in time units
+}
+
+// a rental of a nft, only the latest rental of each nft is kept
+#[cw_serde]
+pub struct Rental {
+    pub contract_address: Addr,
+    pub token_id: String,
+    pub lender: Addr,
+    pub renter: Addr,
+    pub price: Coin, // the total price paid by the renter
+    pub expires: Expiration,
+}
+
+impl Rental {
+    // a rental is active until it expires
+    pub fn is_active(&self, block_info: &BlockInfo) -> bool {
+        !self.expires.is_expired(block_info)
+    }
+}
+
+// rental listings can be indexed by contract_address
+pub struct RentalListingIndexes<'a> {
+    pub contract_address: MultiIndex<'a, Addr, RentalListing, ListingKey>,
+}
+
+impl<'a> IndexList<RentalListing> for RentalListingIndexes<'a> {
+    // this method returns a list of all indexes
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<RentalListing>> + '_> {
+        let v: Vec<&dyn Index<RentalListing>> = vec![&self.contract_address];
+        Box::new(v.into_iter())
+    }
+}
+
+// helper function create a IndexedMap for rental listings
+pub fn rental_listings<'a>() -> IndexedMap<'a, ListingKey, RentalListing, RentalListingIndexes<'a>>
+{
+    let indexes = RentalListingIndexes {
+        contract_address: MultiIndex::new(
+            |_pk: &[u8], l: &RentalListing| l.contract_address.clone(),
+            "rental_listings",
+            "rental_listings__contract_address",
+        ),
+    };
+    IndexedMap::new("rental_listings", indexes)
+}
+
+// rentals can be indexed by renter
+pub struct RentalIndexes<'a> {
+    pub renter: MultiIndex<'a, Addr, Rental, ListingKey>,
+}
+
+impl<'a> IndexList<Rental> for RentalIndexes<'a> {
+    // this method returns a list of all indexes
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Rental>> + '_> {
+        let v: Vec<&dyn Index<Rental>> = vec![&self.renter];
+        Box::new(v.into_iter())
+    }
+}
+
+// helper function create a IndexedMap for rentals
+pub fn rentals<'a>() -> IndexedMap<'a, ListingKey, Rental, RentalIndexes<'a>> {
+    let indexes = RentalIndexes {
+        renter: MultiIndex::new(
+            |_pk: &[u8], r: &Rental| r.renter.clone(),
+            "rentals",
+            "rentals__renter",
+        ),
+    };
+    IndexedMap::new("rentals", indexes)
+}
@@ -1,9 +1,14 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, BlockInfo, Coin};
+use cosmwasm_std::{Addr, BlockInfo, Coin, StdResult, Storage};
 use cw721::Expiration;
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, UniqueIndex};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 
+use crate::hooks::Hooks;
 use crate::order_state::{orders, OfferIndexes, OrderComponents, OrderKey};
+use crate::rental_state::{
+    rental_listings, rentals, Rental, RentalIndexes, RentalListing, RentalListingIndexes,
+};
+use crate::stats_state::StatsStorage;
 
 #[cw_serde]
 pub enum AuctionConfig {
@@ -16,6 +21,15 @@ pub enum AuctionConfig {
 
 pub type TokenId = String;
 
+// how the seller allowed the marketplace to transfer the nft
+#[cw_serde]
+#[derive(Default)]
+pub enum ApprovalType {
+    #[default]
+    Token, // approval of the single token
+    Operator, // operator approval of all tokens of the seller
+}
+
 #[cw_serde]
 pub struct Listing {
     pub contract_address: Addr,        // contract contains the NFT
@@ -23,9 +37,18 @@ pub struct Listing {
     pub auction_config: AuctionConfig, // config of the auction, should be validated by the auction contract when created
     pub seller: Addr,
     pub buyer: Option<Addr>, // buyer, will be initialized to None
+    #[serde(default)]
+    pub approval_type: ApprovalType, // older listings were approved per token
 }
 
 impl Listing {
+    // the price of the listing
+    pub fn price(&self) -> &Coin {
+        match &self.auction_config {
+            AuctionConfig::FixedPrice { price, .. } => price,
+        }
+    }
+
     // expired is when a listing has passed the end_time
     pub fn is_expired(&self, block_info: &BlockInfo) -> bool {
         match self.auction_config {
@@ -48,12 +71,14 @@ pub fn listing_key(contract_address: &Addr, token_id: &TokenId) -> ListingKey {
 // contract_address can point to multiple listings
 pub struct ListingIndexes<'a> {
     pub contract_address: MultiIndex<'a, Addr, Listing, ListingKey>,
+    // listings of a collection sorted by the price in each denom
+    pub prices: MultiIndex<'a, (Addr, String, u128), Listing, ListingKey>,
 }
 
 impl<'a> IndexList<Listing> for ListingIndexes<'a> {
     // this method returns a list of all indexes
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Listing>> + '_> {
-        let v: Vec<&dyn Index<Listing>> = vec![&self.contract_address];
+        let v: Vec<&dyn Index<Listing>> = vec![&self.contract_address, &self.prices];
         Box::new(v.into_iter())
     }
 }
@@ -62,24 +87,48 @@ impl<'a> IndexList<Listing> for ListingIndexes<'a> {
 pub fn listings<'a>() -> IndexedMap<'a, ListingKey, Listing, ListingIndexes<'a>> {
     let indexes = ListingIndexes {
         contract_address: MultiIndex::new(
-            |_pk: &[u8], l: &Listing| (l.contract_address.clone()),
+            |_pk: &[u8], l: &Listing| l.contract_address.clone(),
             "listings",
             "listings__contract_address",
         ),
+        prices: MultiIndex::new(
+            |_pk: &[u8], l: &Listing| {
+                (
+                    l.contract_address.clone(),
+                    l.price().denom.clone(),
+                    l.price().amount.u128(),
+                )
+            },
+            "listings",
+            "listings__price",
+        ),
     };
     IndexedMap::new("listings", indexes)
 }
 
+// royalties and fees are expressed in basis points, 10_000 is 100%
+pub const MAX_BASIS_POINTS: u64 = 10_000;
+
 #[cw_serde]
 pub struct Config {
     pub owner: Addr,
     pub vaura_address: Addr,
+    pub max_royalty_bps: u64, // the royalty paid on a sale is capped at this share of the price
+    pub protocol_fee_bps: u64, // share of the price paid to the owner on a sale
+    pub referral_fee_bps: u64, // share of the price paid to the referrer of a sale
 }
 
-// we use this struct in the migration
+// how the marketplace handles royalties of a collection
 #[cw_serde]
-pub struct ConfigOld {
-    pub owner: Addr,
+#[derive(Default)]
+pub enum RoyaltyPolicy {
+    // the collection must implement cw2981 and royalties are always paid
+    Enforced,
+    // royalties are paid if the collection reports them through CheckRoyalties
+    #[default]
+    Optional,
+    // royalties are never paid
+    Disabled,
 }
 
 // Auction Contract
@@ -127,6 +176,15 @@ pub struct MarketplaceContract<'a> {
         IndexedMap<'a, AuctionContractKey, AuctionContract, AuctionContractIndexes<'a>>,
 
     pub offers: IndexedMap<'a, OrderKey, OrderComponents, OfferIndexes<'a>>,
+    pub royalty_policies: Map<'a, Addr, RoyaltyPolicy>,
+    pub min_offer_amounts: Map<'a, Addr, u128>,
+    pub counters: Map<'a, Addr, u64>,
+    pub stats: StatsStorage<'a>,
+    pub sale_hooks: Hooks<'a>,
+    pub listing_hooks: Hooks<'a>,
+    pub paused: Item<'a, bool>,
+    pub rental_listings: IndexedMap<'a, ListingKey, RentalListing, RentalListingIndexes<'a>>,
+    pub rentals: IndexedMap<'a, ListingKey, Rental, RentalIndexes<'a>>,
 }
 
 // impl default for MarketplaceContract
@@ -138,10 +196,38 @@ impl Default for MarketplaceContract<'static> {
             auction_contracts: auction_contracts(),
 
             offers: orders(),
+            royalty_policies: Map::new("royalty_policies"),
+            min_offer_amounts: Map::new("min_offer_amounts"),
+            counters: Map::new("counters"),
+            stats: StatsStorage::default(),
+            sale_hooks: Hooks::new("sale_hooks"),
+            listing_hooks: Hooks::new("listing_hooks"),
+            paused: Item::new("paused"),
+            rental_listings: rental_listings(),
+            rentals: rentals(),
         }
     }
 }
 
+impl<'a> MarketplaceContract<'a> {
+    // the current counter of a user, orders created with an older counter are cancelled
+    pub fn counter(&self, storage: &dyn Storage, user: &Addr) -> StdResult<u64> {
+        Ok(self
+            .counters
+            .may_load(storage, user.clone())?
+            .unwrap_or_default())
+    }
+
+    // an order is valid only if it was created with the current counter of the offerer
+    pub fn is_order_valid(
+        &self,
+        storage: &dyn Storage,
+        order: &OrderComponents,
+    ) -> StdResult<bool> {
+        Ok(order.counter == self.counter(storage, &order.offerer)?)
+    }
+}
+
 // public the default MarketplaceContract
 pub fn contract() -> MarketplaceContract<'static> {
     MarketplaceContract::default()
@@ -0,0 +1,195 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, StdResult, Storage, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Map, MultiIndex};
+
+use crate::state::{Listing, MarketplaceContract};
+
+// the volume of a collection is recorded in buckets of one hour
+pub const VOLUME_BUCKET_SECONDS: u64 = 3600;
+
+// the denom of a payment is the native denom or the address of the cw20 token
+pub type Denom = String;
+
+// VolumeKey is unique for each collection and denom
+pub type VolumeKey = (Addr, Denom);
+
+#[cw_serde]
+#[derive(Default)]
+pub struct CollectionStats {
+    pub listed_count: u64, // number of listings of the collection, including the expired ones
+    pub unique_sellers: u64, // number of sellers having at least one listing in the collection
+}
+
+#[cw_serde]
+pub struct Volume {
+    pub contract_address: Addr,
+    pub denom: Denom,
+    pub amount: Uint128, // all-time volume of the collection in this denom
+}
+
+// volumes can be indexed by denom and amount to rank collections
+pub struct VolumeIndexes<'a> {
+    pub denom_amount: MultiIndex<'a, (Denom, u128), Volume, VolumeKey>,
+}
+
+impl<'a> IndexList<Volume> for VolumeIndexes<'a> {
+    // this method returns a list of all indexes
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Volume>> + '_> {
+        let v: Vec<&dyn Index<Volume>> = vec![&self.denom_amount];
+        Box::new(v.into_iter())
+    }
+}
+
+// helper function create a IndexedMap for volumes
+pub fn volumes<'a>() -> IndexedMap<'a, VolumeKey, Volume, VolumeIndexes<'a>> {
+    let indexes = VolumeIndexes {
+        denom_amount: MultiIndex::new(
+            |_pk: &[u8], v: &Volume| (v.denom.clone(), v.amount.u128()),
+            "volumes",
+            "volumes__denom_amount",
+        ),
+    };
+    IndexedMap::new("volumes", indexes)
+}
+
+// storage of the running aggregates of each collection
+pub struct StatsStorage<'a> {
+    pub collection_stats: Map<'a, Addr, CollectionStats>,
+    // number of listings of a collection in each denom, including the expired ones
+    pub listed_counts: Map<'a, (Addr, Denom), u64>,
+    // number of listings of a seller in a collection, including the expired ones
+    pub seller_listing_counts: Map<'a, (Addr, Addr), u64>,
+    pub volumes: IndexedMap<'a, VolumeKey, Volume, VolumeIndexes<'a>>,
+    // volume of a collection in a denom during each bucket
+    pub bucket_volumes: Map<'a, (Addr, Denom, u64), Uint128>,
+}
+
+impl Default for StatsStorage<'static> {
+    fn default() -> Self {
+        StatsStorage {
+            collection_stats: Map::new("collection_stats"),
+            listed_counts: Map::new("listed_counts"),
+            seller_listing_counts: Map::new("seller_listing_counts"),
+            volumes: volumes(),
+            bucket_volumes: Map::new("bucket_volumes"),
+        }
+    }
+}
+
+impl<'a> MarketplaceContract<'a> {
+    // update the aggregates of the collection when a listing is created
+    pub fn add_listing_stats(&self, storage: &mut dyn Storage, listing: &Listing) -> StdResult<()> {
+        let contract_address = listing.contract_address.clone();
+        let mut collection_stats = self
+            .stats
+            .collection_stats
+            .may_load(storage, contract_address.clone())?
+            .unwrap_or_default();
+        collection_stats.listed_count += 1;
+
+        self.stats.listed_counts.update(
+            storage,
+            (contract_address.clone(), listing.price().denom.clone()),
+            |count| -> StdResult<u64> { Ok(count.unwrap_or_default() + 1) },
+        )?;
+
+        let seller_count = self.stats.seller_listing_counts.update(
+            storage,
+            (contract_address.clone(), listing.seller.clone()),
+            |count| -> StdResult<u64> { Ok(count.unwrap_or_default() + 1) },
+        )?;
+        if seller_count == 1 {
+            collection_stats.unique_sellers += 1;
+        }
+
+        self.stats
+            .collection_stats
+            .save(storage, contract_address, &collection_stats)
+    }
+
+    // update the aggregates of the collection when a listing is removed
+    pub fn remove_listing_stats(
+        &self,
+        storage: &mut dyn Storage,
+        listing: &Listing,
+    ) -> StdResult<()> {
+        // every stored listing is counted, the aggregates are rebuilt from them when migrating to 0.2.0
+        let contract_address = listing.contract_address.clone();
+        let mut collection_stats = self
+            .stats
+            .collection_stats
+            .may_load(storage, contract_address.clone())?
+            .unwrap_or_default();
+        collection_stats.listed_count = collection_stats.listed_count.saturating_sub(1);
+
+        let denom_key = (contract_address.clone(), listing.price().denom.clone());
+        match self
+            .stats
+            .listed_counts
+            .may_load(storage, denom_key.clone())?
+        {
+            Some(count) if count > 1 => {
+                self.stats
+                    .listed_counts
+                    .save(storage, denom_key, &(count - 1))?
+            }
+            _ => self.stats.listed_counts.remove(storage, denom_key),
+        }
+
+        let seller_key = (contract_address.clone(), listing.seller.clone());
+        match self
+            .stats
+            .seller_listing_counts
+            .may_load(storage, seller_key.clone())?
+        {
+            Some(count) if count > 1 => {
+                self.stats
+                    .seller_listing_counts
+                    .save(storage, seller_key, &(count - 1))?
+            }
+            Some(_) => {
+                self.stats.seller_listing_counts.remove(storage, seller_key);
+                collection_stats.unique_sellers = collection_stats.unique_sellers.saturating_sub(1);
+            }
+            None => {}
+        }
+
+        self.stats
+            .collection_stats
+            .save(storage, contract_address, &collection_stats)
+    }
+
+    // add the amount of a sale to the volume of the collection
+    pub fn add_volume(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        contract_address: &Addr,
+        denom: &str,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        let volume_key = (contract_address.clone(), denom.to_string());
+        let volume = match self.stats.volumes.may_load(storage, volume_key.clone())? {
+            Some(volume) => Volume {
+                amount: volume.amount + amount,
+                ..volume
+            },
+            None => Volume {
+                contract_address: contract_address.clone(),
+                denom: denom.to_string(),
+                amount,
+            },
+        };
+        self.stats.volumes.save(storage, volume_key, &volume)?;
+
+        let bucket = block.time.seconds() / VOLUME_BUCKET_SECONDS;
+        self.stats.bucket_volumes.update(
+            storage,
+            (contract_address.clone(), denom.to_string(), bucket),
+            |bucket_volume| -> StdResult<Uint128> {
+                Ok(bucket_volume.unwrap_or_default() + amount)
+            },
+        )?;
+        Ok(())
+    }
+}
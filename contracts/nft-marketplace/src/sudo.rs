@@ -0,0 +1,104 @@
+use cosmwasm_std::{Addr, DepsMut, Env, Response};
+
+use crate::events::{CancelEvent, CancelOfferEvent, MarketplaceEvent};
+use crate::msg::ListingHookAction;
+use crate::order_state::order_key;
+use crate::state::{listing_key, MarketplaceContract};
+use crate::ContractError;
+
+// governance handlers, they skip the owner checks of the matching execute messages
+impl MarketplaceContract<'static> {
+    pub fn sudo_update_owner(&self, deps: DepsMut, owner: Addr) -> Result<Response, ContractError> {
+        let mut conf = self.config.load(deps.storage)?;
+        conf.owner = owner.clone();
+        self.config.save(deps.storage, &conf)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "sudo_update_owner")
+            .add_attribute("owner", owner))
+    }
+
+    pub fn sudo_set_paused(&self, deps: DepsMut, paused: bool) -> Result<Response, ContractError> {
+        self.paused.save(deps.storage, &paused)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "sudo_set_paused")
+            .add_attribute("paused", paused.to_string()))
+    }
+
+    pub fn sudo_set_fees(
+        &self,
+        deps: DepsMut,
+        protocol_fee_bps: u64,
+        referral_fee_bps: u64,
+    ) -> Result<Response, ContractError> {
+        self.set_fees(deps.storage, protocol_fee_bps, referral_fee_bps)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "sudo_set_fees")
+            .add_attribute("protocol_fee_bps", protocol_fee_bps.to_string())
+            .add_attribute("referral_fee_bps", referral_fee_bps.to_string()))
+    }
+
+    pub fn sudo_remove_listing(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        contract_address: Addr,
+        token_id: String,
+    ) -> Result<Response, ContractError> {
+        let listing_key = listing_key(&contract_address, &token_id);
+        let listing = self.listings.load(deps.storage, listing_key.clone())?;
+
+        self.listings.remove(deps.storage, listing_key)?;
+        self.remove_listing_stats(deps.storage, &listing)?;
+
+        let hook_msgs =
+            self.listing_hook_msgs(deps.storage, ListingHookAction::Delete, &listing)?;
+
+        // the listing is cancelled by the marketplace itself
+        let event = CancelEvent {
+            contract_address: contract_address.clone(),
+            token_id: token_id.clone(),
+            seller: listing.seller,
+            cancelled_by: env.contract.address.clone(),
+        };
+
+        Ok(Response::new()
+            .add_submessages(hook_msgs)
+            .add_event(event.to_event())
+            .add_attribute("method", "sudo_remove_listing")
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("token_id", token_id)
+            .add_attribute("removed_at", env.block.time.to_string()))
+    }
+
+    pub fn sudo_remove_offer(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        offerer: Addr,
+        contract_address: Addr,
+        token_id: String,
+    ) -> Result<Response, ContractError> {
+        let order_key = order_key(&offerer, &contract_address, &token_id);
+        if !self.offers.has(deps.storage, order_key.clone()) {
+            return Err(ContractError::OfferNotFound {});
+        }
+        self.offers.remove(deps.storage, order_key)?;
+
+        let event = CancelOfferEvent {
+            contract_address: contract_address.clone(),
+            token_id: token_id.clone(),
+            offerer: offerer.clone(),
+        };
+
+        Ok(Response::new()
+            .add_event(event.to_event())
+            .add_attribute("method", "sudo_remove_offer")
+            .add_attribute("offerer", offerer)
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("token_id", token_id)
+            .add_attribute("removed_at", env.block.time.to_string()))
+    }
+}
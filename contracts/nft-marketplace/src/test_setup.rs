@@ -17,7 +17,7 @@ pub mod env {
 
     use crate::contract::{
         execute as MarketPlaceExecute, instantiate as MarketPlaceInstantiate,
-        query as MarketPlaceQuery,
+        query as MarketPlaceQuery, reply as MarketPlaceReply, sudo as MarketPlaceSudo,
     };
     use crate::msg::{ExecuteMsg as MarketPlaceExecuteMsg, InstantiateMsg};
 
@@ -87,7 +87,9 @@ pub mod env {
 
     fn nft_marketplace_contract_template() -> Box<dyn Contract<Empty>> {
         let contract =
-            ContractWrapper::new(MarketPlaceExecute, MarketPlaceInstantiate, MarketPlaceQuery);
+            ContractWrapper::new(MarketPlaceExecute, MarketPlaceInstantiate, MarketPlaceQuery)
+                .with_reply(MarketPlaceReply)
+                .with_sudo(MarketPlaceSudo);
         Box::new(contract)
     }
 